@@ -0,0 +1,126 @@
+//! Derive macros for the ConfigCat OpenFeature Provider for Rust.
+
+#![warn(missing_docs)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::doc_markdown)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Derives `FlagEnum` for an enum with unit variants, mapping each variant to a string flag
+/// value.
+///
+/// By default, a variant maps to its name. The mapping can be changed with the
+/// `#[flag(rename_all = "...")]` container attribute (`lowercase`, `UPPERCASE`, `camelCase`,
+/// `snake_case`, `SCREAMING_SNAKE_CASE` or `kebab-case`), and the `#[flag(rename = "...")]`
+/// variant attribute.
+#[proc_macro_derive(FlagEnum, attributes(flag))]
+pub fn derive_flag_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "FlagEnum can only be derived for enums",
+        ));
+    };
+    let rename_all = flag_attr(&input.attrs, "rename_all")?;
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "FlagEnum variants can't have fields",
+            ));
+        }
+        let value = match flag_attr(&variant.attrs, "rename")? {
+            Some(value) => value.value(),
+            None => match &rename_all {
+                Some(rule) => apply_rule(&variant.ident.to_string(), rule)?,
+                None => variant.ident.to_string(),
+            },
+        };
+        idents.push(&variant.ident);
+        values.push(value);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::configcat_openfeature_provider::FlagEnum for #name #ty_generics #where_clause {
+            const VARIANTS: &'static [&'static str] = &[#(#values),*];
+
+            fn from_flag_value(value: &str) -> ::std::option::Option<Self> {
+                match value {
+                    #(#values => ::std::option::Option::Some(Self::#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn flag_value(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #values,)*
+                }
+            }
+        }
+    })
+}
+
+/// Returns the value of the `#[flag(name = "...")]` attribute, if any.
+fn flag_attr(attrs: &[syn::Attribute], name: &str) -> syn::Result<Option<LitStr>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("flag")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                value = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported flag attribute"))
+            }
+        })?;
+    }
+    Ok(value)
+}
+
+fn apply_rule(ident: &str, rule: &LitStr) -> syn::Result<String> {
+    let words = split_words(ident);
+    let value = match rule.value().as_str() {
+        "lowercase" => ident.to_lowercase(),
+        "UPPERCASE" => ident.to_uppercase(),
+        "camelCase" => {
+            let mut value = String::new();
+            for (i, word) in words.iter().enumerate() {
+                if i == 0 {
+                    value.push_str(&word.to_lowercase());
+                } else {
+                    value.push_str(word);
+                }
+            }
+            value
+        }
+        "snake_case" => words.join("_").to_lowercase(),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-").to_lowercase(),
+        _ => return Err(Error::new_spanned(rule, "unsupported rename_all rule")),
+    };
+    Ok(value)
+}
+
+/// Splits an `UpperCamelCase` identifier to words.
+fn split_words(ident: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for ch in ident.chars() {
+        match words.last_mut() {
+            Some(word) if !ch.is_uppercase() => word.push(ch),
+            _ => words.push(ch.to_string()),
+        }
+    }
+    words
+}
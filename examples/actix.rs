@@ -0,0 +1,27 @@
+use actix_web::{web, App, HttpServer};
+use configcat_openfeature_provider::{configure_flags, ConfigCatProvider, Flags};
+
+async fn index(flags: Flags) -> String {
+    let is_awesome_enabled = flags.bool("isAwesomeFeatureEnabled", false).await;
+    let is_poc_enabled = flags.bool("isPOCFeatureEnabled", false).await;
+    format!(
+        "isAwesomeFeatureEnabled: {is_awesome_enabled}\nisPOCFeatureEnabled: {is_poc_enabled}\n"
+    )
+}
+
+// Run it, then try `curl -H "X-User-Id: #SOME-USER-ID#" http://127.0.0.1:8080`.
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let provider = ConfigCatProvider::builder("PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ")
+        .build()
+        .unwrap();
+
+    HttpServer::new(move || {
+        App::new()
+            .configure(configure_flags(provider.clone()))
+            .route("/", web::get().to(index))
+    })
+    .bind(("127.0.0.1", 8080))?
+    .run()
+    .await
+}
@@ -0,0 +1,50 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use configcat_openfeature_provider::fuzzing;
+use libfuzzer_sys::fuzz_target;
+use open_feature::{EvaluationContext, EvaluationContextFieldValue};
+use time::OffsetDateTime;
+
+#[derive(Arbitrary, Debug)]
+enum FieldValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    DateTime(i64),
+    Struct(Vec<String>),
+}
+
+impl From<FieldValue> for EvaluationContextFieldValue {
+    fn from(value: FieldValue) -> Self {
+        match value {
+            FieldValue::Bool(val) => Self::Bool(val),
+            FieldValue::Int(val) => Self::Int(val),
+            FieldValue::Float(val) => Self::Float(val),
+            FieldValue::String(val) => Self::String(val),
+            FieldValue::DateTime(val) => Self::DateTime(
+                OffsetDateTime::from_unix_timestamp(val).unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            ),
+            FieldValue::Struct(val) => Self::new_struct(val),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+struct Context {
+    targeting_key: Option<String>,
+    custom_fields: Vec<(String, FieldValue)>,
+}
+
+fuzz_target!(|context: Context| {
+    let evaluation_context = EvaluationContext {
+        targeting_key: context.targeting_key,
+        custom_fields: context
+            .custom_fields
+            .into_iter()
+            .map(|(key, value)| (key, value.into()))
+            .collect(),
+    };
+    _ = fuzzing::to_user(&evaluation_context);
+});
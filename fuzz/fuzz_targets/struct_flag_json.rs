@@ -0,0 +1,8 @@
+#![no_main]
+
+use configcat_openfeature_provider::fuzzing;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|value: &str| {
+    _ = fuzzing::to_struct(value);
+});
@@ -0,0 +1,68 @@
+use crate::flags::{header_context, Flags, USER_ID_HEADER};
+use crate::provider::ConfigCatProvider;
+use actix_web::dev::Payload;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::web::{Data, ServiceConfig};
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use open_feature::EvaluationContext;
+use std::future::{ready, Ready};
+
+/// Returns an actix-web configuration function that registers the provider as app data,
+/// so handlers can extract [`Flags`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use actix_web::{web, App};
+/// use configcat_openfeature_provider::{configure_flags, ConfigCatProvider, Flags};
+///
+/// async fn handler(flags: Flags) -> &'static str {
+///     if flags.bool("isAwesomeFeatureEnabled", false).await {
+///         "awesome"
+///     } else {
+///         "regular"
+///     }
+/// }
+///
+/// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+/// let app = App::new()
+///     .configure(configure_flags(provider))
+///     .route("/", web::get().to(handler));
+/// ```
+pub fn configure_flags(provider: ConfigCatProvider) -> impl Fn(&mut ServiceConfig) {
+    move |config| {
+        config.app_data(Data::new(provider.clone()));
+    }
+}
+
+/// Extracts [`Flags`] in actix-web handlers.
+///
+/// The provider is taken from the `Data<ConfigCatProvider>` app data registered with
+/// [`configure_flags`]. The extraction fails with 500 Internal Server Error when it's missing.
+impl FromRequest for Flags {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let Some(provider) = req.app_data::<Data<ConfigCatProvider>>() else {
+            return ready(Err(ErrorInternalServerError(
+                "ConfigCatProvider is not registered as app data",
+            )));
+        };
+        let evaluation_context = req
+            .extensions()
+            .get::<EvaluationContext>()
+            .cloned()
+            .unwrap_or_else(|| {
+                header_context(
+                    req.headers()
+                        .get(USER_ID_HEADER)
+                        .and_then(|value| value.to_str().ok()),
+                )
+            });
+        ready(Ok(Self::new(
+            provider.get_ref().clone(),
+            evaluation_context,
+        )))
+    }
+}
@@ -0,0 +1,100 @@
+use crate::sink::{EvaluationEvent, EvaluationSink};
+use chrono::{DateTime, SecondsFormat, Utc};
+use log::warn;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    timestamp: String,
+    flag_key: &'a str,
+    variant: Option<&'a str>,
+    reason: Option<&'a str>,
+    error_code: Option<&'a str>,
+    targeting_key_hash: Option<String>,
+    config_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span_id: Option<&'a str>,
+}
+
+/// An [`EvaluationSink`] that writes one structured JSON line per evaluation to a writer.
+///
+/// Each line contains the flag key, the served variant, the reason, the error code (if any),
+/// the SHA-256 hash of the targeting key, and the config version (the fetch time of the
+/// config JSON the evaluation was based on). The targeting key itself is never written.
+/// With the `otel` or `tracing` feature, the lines also contain the ID of the current trace
+/// and span, so they can be joined with request traces.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+/// use configcat_openfeature_provider::{AuditLog, ConfigCatProvider};
+///
+/// let file = File::create("flag-audit.jsonl").unwrap();
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .sink(AuditLog::new(file).salt("app-secret"))
+///     .build()
+///     .unwrap();
+/// ```
+pub struct AuditLog {
+    writer: Mutex<Box<dyn Write + Send>>,
+    salt: String,
+}
+
+impl AuditLog {
+    /// Creates a new [`AuditLog`] that writes JSON lines to the given writer.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+            salt: String::new(),
+        }
+    }
+
+    /// Sets a salt mixed into the targeting key hash, so hashes can't be matched
+    /// against a list of known user identifiers.
+    pub fn salt(mut self, salt: &str) -> Self {
+        salt.clone_into(&mut self.salt);
+        self
+    }
+
+    fn hash(&self, targeting_key: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update(targeting_key.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+impl EvaluationSink for AuditLog {
+    fn record(&self, event: &EvaluationEvent) {
+        let record = AuditRecord {
+            timestamp: format_time(&event.timestamp),
+            flag_key: event.flag_key.as_str(),
+            variant: event.variant.as_deref(),
+            reason: event.reason.as_deref(),
+            error_code: event.error_code.as_deref(),
+            targeting_key_hash: event.targeting_key.as_deref().map(|key| self.hash(key)),
+            config_version: event.config_fetch_time.as_ref().map(format_time),
+            trace_id: event.trace_id.as_deref(),
+            span_id: event.span_id.as_deref(),
+        };
+        let Ok(mut line) = serde_json::to_vec(&record) else {
+            return;
+        };
+        line.push(b'\n');
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(err) = writer.write_all(&line).and_then(|()| writer.flush()) {
+            warn!("Failed to write evaluation audit log. ({err})");
+        }
+    }
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
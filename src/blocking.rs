@@ -0,0 +1,273 @@
+use crate::builder::ConfigCatProviderBuilder;
+use crate::provider::ConfigCatProvider;
+use configcat::ClientError;
+use open_feature::provider::{FeatureProvider, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationResult, StructValue};
+use std::future::Future;
+use std::thread::JoinHandle;
+use tokio::runtime::{Handle, Runtime};
+use tokio::sync::oneshot;
+
+/// A synchronous facade of a [`ConfigCatProvider`], for CLI tools and codebases that aren't
+/// async.
+///
+/// It owns a Tokio runtime driven by a background thread, which runs the background polling
+/// and the HTTP requests of the provider, while the methods block the calling thread until the
+/// evaluation completes. The methods must not be called from within an async context.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{BlockingConfigCatProvider, ConfigCatProvider};
+/// use open_feature::EvaluationContext;
+///
+/// let provider =
+///     BlockingConfigCatProvider::new(ConfigCatProvider::builder("sdk-key")).unwrap();
+/// let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///
+/// let enabled = provider.get_bool_value("isNewCheckout", Some(&ctx)).unwrap();
+/// ```
+pub struct BlockingConfigCatProvider {
+    provider: ConfigCatProvider,
+    handle: Handle,
+    shutdown: Option<oneshot::Sender<()>>,
+    driver: Option<JoinHandle<()>>,
+}
+
+impl BlockingConfigCatProvider {
+    /// Creates a new [`BlockingConfigCatProvider`] from the provider configured on the builder.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the provider can't be built (see [`ConfigCatProviderBuilder::build`]).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the runtime or its background thread can't be started.
+    pub fn new(builder: ConfigCatProviderBuilder) -> Result<Self, ClientError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start the runtime of the blocking provider.");
+        let handle = runtime.handle().clone();
+        let provider = {
+            let _guard = runtime.enter();
+            builder.build()?
+        };
+        let (shutdown, stopped) = oneshot::channel();
+        let driver = std::thread::Builder::new()
+            .name("configcat-blocking".to_owned())
+            .spawn(move || drive(&runtime, stopped))
+            .expect("Failed to start the runtime thread of the blocking provider.");
+        Ok(Self {
+            provider,
+            handle,
+            shutdown: Some(shutdown),
+            driver: Some(driver),
+        })
+    }
+
+    /// Returns the wrapped async provider, e.g. to register it with the OpenFeature API.
+    pub fn provider(&self) -> &ConfigCatProvider {
+        &self.provider
+    }
+
+    /// Runs a future on the runtime of the provider, blocking the calling thread until it
+    /// completes.
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called from within an async context.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.handle.block_on(future)
+    }
+
+    /// Initiates a config JSON refresh, and waits for it.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigCatProvider::refresh`].
+    pub fn refresh(&self) -> Result<(), ClientError> {
+        self.block_on(self.provider.refresh())
+    }
+
+    /// Returns the value of a feature flag.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn get_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<bool> {
+        self.resolve_bool_value(flag_key, evaluation_context)
+            .map(|details| details.value)
+    }
+
+    /// Returns the value of a whole number setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn get_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<i64> {
+        self.resolve_int_value(flag_key, evaluation_context)
+            .map(|details| details.value)
+    }
+
+    /// Returns the value of a decimal number setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn get_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<f64> {
+        self.resolve_float_value(flag_key, evaluation_context)
+            .map(|details| details.value)
+    }
+
+    /// Returns the value of a text setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn get_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<String> {
+        self.resolve_string_value(flag_key, evaluation_context)
+            .map(|details| details.value)
+    }
+
+    /// Returns the value of a text setting holding a JSON object.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn get_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<StructValue> {
+        self.resolve_struct_value(flag_key, evaluation_context)
+            .map(|details| details.value)
+    }
+
+    /// Resolves a feature flag, with the variant, the reason and the flag metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        let default = EvaluationContext::default();
+        let evaluation_context = evaluation_context.unwrap_or(&default);
+        self.block_on(
+            self.provider
+                .resolve_bool_value(flag_key, evaluation_context),
+        )
+    }
+
+    /// Resolves a whole number setting, with the variant, the reason and the flag metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        let default = EvaluationContext::default();
+        let evaluation_context = evaluation_context.unwrap_or(&default);
+        self.block_on(
+            self.provider
+                .resolve_int_value(flag_key, evaluation_context),
+        )
+    }
+
+    /// Resolves a decimal number setting, with the variant, the reason and the flag metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        let default = EvaluationContext::default();
+        let evaluation_context = evaluation_context.unwrap_or(&default);
+        self.block_on(
+            self.provider
+                .resolve_float_value(flag_key, evaluation_context),
+        )
+    }
+
+    /// Resolves a text setting, with the variant, the reason and the flag metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        let default = EvaluationContext::default();
+        let evaluation_context = evaluation_context.unwrap_or(&default);
+        self.block_on(
+            self.provider
+                .resolve_string_value(flag_key, evaluation_context),
+        )
+    }
+
+    /// Resolves a text setting holding a JSON object, with the variant, the reason and the
+    /// flag metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let default = EvaluationContext::default();
+        let evaluation_context = evaluation_context.unwrap_or(&default);
+        self.block_on(
+            self.provider
+                .resolve_struct_value(flag_key, evaluation_context),
+        )
+    }
+}
+
+impl Drop for BlockingConfigCatProvider {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            _ = shutdown.send(());
+        }
+        if let Some(driver) = self.driver.take() {
+            _ = driver.join();
+        }
+    }
+}
+
+/// Drives the IO and timer drivers of the runtime until the provider is dropped, so the
+/// background tasks make progress between the blocking calls.
+fn drive(runtime: &Runtime, stopped: oneshot::Receiver<()>) {
+    runtime.block_on(async {
+        _ = stopped.await;
+    });
+}
@@ -0,0 +1,80 @@
+use crate::provider::VARIATION_ID_METADATA_KEY;
+use crate::value::to_json;
+use open_feature::provider::ResolutionDetails;
+use open_feature::{FlagMetadata, FlagMetadataValue, Value};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The bootstrap payload of a user: the flags evaluated for them, in the shape of an
+/// OpenFeature Remote Evaluation Protocol (OFREP) bulk evaluation response.
+#[derive(Serialize)]
+pub(crate) struct BootstrapPayload {
+    flags: Vec<FlagEvaluation>,
+}
+
+/// A successful flag evaluation in OFREP format.
+#[derive(Serialize)]
+pub(crate) struct FlagEvaluation {
+    key: String,
+    value: serde_json::Value,
+    reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variant: Option<String>,
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+impl BootstrapPayload {
+    /// Creates the payload from the given evaluations, keeping only the allowlisted flags
+    /// when an allowlist is given.
+    pub(crate) fn new(
+        evaluations: HashMap<String, ResolutionDetails<Value>>,
+        allowlist: Option<&[&str]>,
+    ) -> Self {
+        let mut flags: Vec<FlagEvaluation> = evaluations
+            .into_iter()
+            .filter(|(key, _)| allowlist.is_none_or(|keys| keys.contains(&key.as_str())))
+            .map(|(key, details)| FlagEvaluation::new(key, details))
+            .collect();
+        flags.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        Self { flags }
+    }
+}
+
+impl FlagEvaluation {
+    pub(crate) fn new(key: String, details: ResolutionDetails<Value>) -> Self {
+        Self {
+            key,
+            value: to_json(&details.value),
+            reason: details
+                .reason
+                .map(|reason| reason.to_string())
+                .unwrap_or_default(),
+            variant: details.variant,
+            metadata: details
+                .flag_metadata
+                .map(metadata_to_json)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Converts the flag metadata sent to clients, keeping only the variation ID. The matched
+/// targeting rule and percentage option are left out, as they reveal the targeting rules and
+/// their comparison values (e.g. customer lists).
+fn metadata_to_json(metadata: FlagMetadata) -> serde_json::Map<String, serde_json::Value> {
+    metadata
+        .values
+        .into_iter()
+        .filter(|(key, _)| key == VARIATION_ID_METADATA_KEY)
+        .map(|(key, value)| {
+            let value = match value {
+                FlagMetadataValue::Bool(val) => serde_json::Value::Bool(val),
+                FlagMetadataValue::Int(val) => serde_json::Value::from(val),
+                FlagMetadataValue::Float(val) => serde_json::Value::from(val),
+                FlagMetadataValue::String(val) => serde_json::Value::String(val),
+            };
+            (key, value)
+        })
+        .collect()
+}
@@ -0,0 +1,1140 @@
+use crate::cache::{BridgeCache, CacheBridge, ProviderCache};
+use crate::change::ConfigChange;
+use crate::download::{Downloader, SharedCache, EU_CDN_URL, GLOBAL_CDN_URL};
+use crate::format::{StructFormat, StructFormats};
+use crate::persist::PersistentCache;
+use crate::provider::ConfigCatProvider;
+use crate::refresh::{RefreshMode, Refresher, DEFAULT_MAX_INIT_WAIT};
+use crate::sdk_key;
+use crate::sink::EvaluationSink;
+use crate::snapshot::{copy_behavior, ClientTemplate, SharedSource};
+use crate::source::ConfigSource;
+use crate::tap::{ConfigTap, TapCache};
+use crate::tracking::TrackingSink;
+use crate::transform::{StringTransform, StringTransforms};
+use crate::verify::{FlagSchema, VerificationReport};
+use configcat::{
+    Client, ClientBuilder, ClientError, ConfigCache, DataGovernance, OverrideBehavior,
+    OverrideDataSource, PollingMode, User,
+};
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationResult, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_INIT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_mins(1);
+
+/// A callback invoked before each evaluation that can rewrite the evaluation context.
+pub(crate) type BeforeFn = dyn Fn(&str, &mut EvaluationContext) + Send + Sync;
+
+/// A callback invoked after each evaluation that can post-process the result.
+pub(crate) type AfterFn =
+    dyn Fn(&str, &mut EvaluationResult<ResolutionDetails<Value>>) + Send + Sync;
+
+/// A callback invoked after each evaluation with the ConfigCat SDK's evaluation details.
+pub(crate) type EvaluatedFn =
+    dyn Fn(&str, &configcat::EvaluationDetails<configcat::Value>) + Send + Sync;
+
+/// A callback invoked each time the config JSON changes.
+pub(crate) type ConfigChangedFn = dyn Fn() + Send + Sync;
+
+/// A callback invoked with the affected flag keys each time the config JSON changes.
+pub(crate) type FlagsChangedFn = dyn Fn(&ConfigChange) + Send + Sync;
+
+/// A callback invoked when the config JSON drifts from the flag schema.
+pub(crate) type SchemaDriftFn = dyn Fn(&VerificationReport) + Send + Sync;
+
+/// Provider level options collected by the [`ConfigCatProviderBuilder`].
+pub(crate) struct ProviderOptions {
+    pub(crate) sinks: Vec<Arc<dyn EvaluationSink>>,
+    pub(crate) init_timeout: Duration,
+    pub(crate) before: Vec<Box<BeforeFn>>,
+    pub(crate) after: Vec<Box<AfterFn>>,
+    pub(crate) on_evaluated: Vec<Box<EvaluatedFn>>,
+    pub(crate) aliases: HashMap<String, String>,
+    pub(crate) key_prefix: String,
+    pub(crate) tracking_sink: Option<Arc<dyn TrackingSink>>,
+    pub(crate) exposure_window: Option<Duration>,
+    pub(crate) on_config_changed: Vec<Box<ConfigChangedFn>>,
+    pub(crate) on_flags_changed: Vec<(String, Box<FlagsChangedFn>)>,
+    pub(crate) schema: Option<FlagSchema>,
+    pub(crate) on_schema_drift: Vec<Box<SchemaDriftFn>>,
+    pub(crate) struct_formats: StructFormats,
+    pub(crate) interpolate_strings: bool,
+    pub(crate) count_served_values: bool,
+    pub(crate) string_transforms: StringTransforms,
+}
+
+impl Default for ProviderOptions {
+    fn default() -> Self {
+        Self {
+            sinks: Vec::new(),
+            init_timeout: DEFAULT_INIT_TIMEOUT,
+            before: Vec::new(),
+            after: Vec::new(),
+            on_evaluated: Vec::new(),
+            aliases: HashMap::new(),
+            key_prefix: String::new(),
+            tracking_sink: None,
+            exposure_window: None,
+            on_config_changed: Vec::new(),
+            on_flags_changed: Vec::new(),
+            schema: None,
+            on_schema_drift: Vec::new(),
+            struct_formats: StructFormats::default(),
+            interpolate_strings: false,
+            count_served_values: false,
+            string_transforms: StringTransforms::default(),
+        }
+    }
+}
+
+/// Builder to create a [`ConfigCatProvider`].
+///
+/// Besides the provider specific options, it exposes the configuration options of the
+/// underlying ConfigCat SDK client, so the provider can be configured in one place.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use configcat::PollingMode;
+/// use configcat_openfeature_provider::ConfigCatProvider;
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+///     .build()
+///     .unwrap();
+/// ```
+pub struct ConfigCatProviderBuilder {
+    client_builder: ClientBuilder,
+    options: ProviderOptions,
+    polling_mode: PollingMode,
+    template: ClientTemplate,
+    cache: Option<Arc<dyn ProviderCache>>,
+    persist_path: Option<PathBuf>,
+    fallback_config: Option<String>,
+    revalidate_ttl: Option<Duration>,
+    serverless: Option<(Duration, Duration)>,
+    base_url: Option<String>,
+    cdn_url: &'static str,
+    offline: bool,
+    http_client: Option<reqwest::Client>,
+    refresh_interval: Option<Duration>,
+    max_init_wait: Duration,
+    http_timeout: Option<Duration>,
+    shared: bool,
+    #[cfg(feature = "tracing")]
+    log_bridge: Option<crate::SdkLogBridge>,
+    #[cfg(feature = "hot-reload")]
+    watched_file: Option<PathBuf>,
+    #[cfg(feature = "proxy-grpc")]
+    proxy_grpc: Option<(tonic::transport::Channel, String)>,
+    #[cfg(feature = "proxy-sse")]
+    proxy_sse: Option<String>,
+}
+
+impl ConfigCatProviderBuilder {
+    pub(crate) fn new(sdk_key: &str) -> Self {
+        Self {
+            client_builder: Client::builder(sdk_key),
+            options: ProviderOptions::default(),
+            polling_mode: PollingMode::AutoPoll(DEFAULT_POLL_INTERVAL),
+            template: ClientTemplate {
+                sdk_key: sdk_key.to_owned(),
+                default_user: None,
+                overrides: None,
+            },
+            cache: None,
+            persist_path: None,
+            fallback_config: None,
+            revalidate_ttl: None,
+            serverless: None,
+            base_url: None,
+            cdn_url: GLOBAL_CDN_URL,
+            offline: false,
+            http_client: None,
+            refresh_interval: None,
+            max_init_wait: DEFAULT_MAX_INIT_WAIT,
+            http_timeout: None,
+            shared: false,
+            #[cfg(feature = "tracing")]
+            log_bridge: None,
+            #[cfg(feature = "hot-reload")]
+            watched_file: None,
+            #[cfg(feature = "proxy-grpc")]
+            proxy_grpc: None,
+            #[cfg(feature = "proxy-sse")]
+            proxy_sse: None,
+        }
+    }
+
+    /// Sets the polling mode used to keep the config JSON up-to-date.
+    ///
+    /// The fetches are driven by the provider (the underlying ConfigCat SDK client is used in
+    /// manual polling mode), so their outcome can be tracked with [`ConfigCatProvider::fetch_metrics`].
+    ///
+    /// Default is [`PollingMode::AutoPoll`] with 60 seconds poll interval.
+    pub fn polling_mode(mut self, polling_mode: PollingMode) -> Self {
+        self.polling_mode = polling_mode;
+        self
+    }
+
+    /// Sets how long the evaluations wait for the first config JSON fetch in
+    /// [`PollingMode::AutoPoll`] mode. When it elapses, the evaluations are served from the cached
+    /// config JSON, or with the default values when there's none.
+    ///
+    /// Default is 5 seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .max_init_wait_time(Duration::from_secs(2))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn max_init_wait_time(mut self, wait: Duration) -> Self {
+        self.max_init_wait = wait;
+        self
+    }
+
+    /// Switches the provider to stale-while-revalidate mode, which takes precedence over the
+    /// [`ConfigCatProviderBuilder::polling_mode`].
+    ///
+    /// Unlike [`PollingMode::LazyLoad`], evaluations never wait for a refetch once the config JSON
+    /// is downloaded: when it's older than the given TTL, they are served from the cached config
+    /// JSON with the [`crate::STALE_REASON`] reason, while a refresh happens in the background.
+    /// Only the evaluations before the first successful fetch wait for it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .stale_while_revalidate(Duration::from_secs(60))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn stale_while_revalidate(mut self, ttl: Duration) -> Self {
+        self.revalidate_ttl = Some(ttl);
+        self
+    }
+
+    /// Switches the provider to serverless mode, for short-lived executions like AWS Lambda
+    /// functions, which takes precedence over the [`ConfigCatProviderBuilder::polling_mode`] and
+    /// [`ConfigCatProviderBuilder::stale_while_revalidate`].
+    ///
+    /// No background task is spawned: the config JSON is loaded on the first evaluation, from
+    /// the [`ConfigCatProviderBuilder::cache`] when the cached config JSON is younger than the
+    /// given TTL, otherwise from the ConfigCat CDN. Later, it's only fetched by
+    /// [`ConfigCatProvider::refresh`] calls. The downloaded config JSON is written into the
+    /// cache before the fetch completes, so the next executions can use it. The fetches wait at
+    /// most `fetch_timeout`, after that the evaluations are served from the cached config JSON,
+    /// or fail when there's none.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .serverless(Duration::from_secs(300), Duration::from_millis(500))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn serverless(mut self, ttl: Duration, fetch_timeout: Duration) -> Self {
+        self.serverless = Some((ttl, fetch_timeout));
+        self
+    }
+
+    /// Limits the [`ConfigCatProvider::refresh`] calls to one config JSON download per the given
+    /// interval, so webhook storms or buggy callers can't hammer the ConfigCat CDN.
+    ///
+    /// A call made within the interval after the previous refresh waits for the end of the
+    /// interval, and the calls made in the meantime are coalesced into one pending refresh,
+    /// whose result they all return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .refresh_rate_limit(Duration::from_secs(5))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn refresh_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.refresh_interval = Some(min_interval);
+        self
+    }
+
+    /// Indicates whether the provider should share its config JSON with the other providers
+    /// built for the same SDK key with this option (e.g. for different OpenFeature domains).
+    ///
+    /// Shared providers download the config JSON through one ConfigCat SDK client and polling
+    /// loop, which avoids duplicate downloads and memory use. The SDK client and polling options
+    /// of the first built provider apply to all of them (a warning is logged when a later one is
+    /// built with different options), while the provider level options (like sinks and
+    /// callbacks) remain per provider. The shared state is released when the last
+    /// provider using it is dropped.
+    ///
+    /// Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let checkout = ConfigCatProvider::builder("sdk-key").shared(true).build().unwrap();
+    ///     // Uses the config JSON downloaded for `checkout`.
+    ///     let billing = ConfigCatProvider::builder("sdk-key").shared(true).build().unwrap();
+    /// }
+    /// ```
+    pub fn shared(mut self, shared: bool) -> Self {
+        self.shared = shared;
+        self
+    }
+
+    /// Indicates whether the underlying ConfigCat SDK client should be initialized in offline mode.
+    ///
+    /// Default is `false`.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.client_builder = self.client_builder.offline(offline);
+        self.offline = offline;
+        self
+    }
+
+    /// Sets the HTTP request timeout of the underlying ConfigCat SDK client.
+    ///
+    /// Default is 30 seconds.
+    pub fn http_timeout(mut self, timeout: Duration) -> Self {
+        self.client_builder = self.client_builder.http_timeout(timeout);
+        self.http_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the custom base URL of the underlying ConfigCat SDK client.
+    ///
+    /// Useful when the config JSON is served by a [ConfigCat Proxy](https://configcat.com/docs/advanced/proxy/proxy-overview/).
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.client_builder = self.client_builder.base_url(base_url);
+        self.base_url = Some(base_url.to_owned());
+        self
+    }
+
+    /// Sets the HTTP client used to download the config JSON, instead of the one created by the
+    /// underlying ConfigCat SDK client.
+    ///
+    /// Useful when the [ConfigCat Proxy](https://configcat.com/docs/advanced/proxy/proxy-overview/)
+    /// or an egress gateway requires mutual TLS, so the requests have to present a client
+    /// certificate, or trust a private root certificate. The timeouts and proxy settings of the
+    /// given client apply instead of [`ConfigCatProviderBuilder::http_timeout`]. The config JSON
+    /// is downloaded from the [`ConfigCatProviderBuilder::base_url`] (or the ConfigCat CDN
+    /// selected by [`ConfigCatProviderBuilder::data_governance`]), following the redirects of
+    /// the data governance settings like the ConfigCat SDK.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// fn proxy_provider(identity: reqwest::Identity, root: reqwest::Certificate) -> ConfigCatProvider {
+    ///     let http_client = reqwest::Client::builder()
+    ///         .identity(identity)
+    ///         .add_root_certificate(root)
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     ConfigCatProvider::builder("configcat-proxy/my-proxy")
+    ///         .base_url("https://configcat-proxy.internal")
+    ///         .http_client(http_client)
+    ///         .build()
+    ///         .unwrap()
+    /// }
+    /// ```
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the data governance mode of the underlying ConfigCat SDK client.
+    ///
+    /// Default is [`DataGovernance::Global`].
+    pub fn data_governance(mut self, data_governance: DataGovernance) -> Self {
+        self.cdn_url = match data_governance {
+            DataGovernance::Global => GLOBAL_CDN_URL,
+            DataGovernance::EU => EU_CDN_URL,
+        };
+        self.client_builder = self.client_builder.data_governance(data_governance);
+        self
+    }
+
+    /// Sets the default user of the underlying ConfigCat SDK client.
+    ///
+    /// It's used when the evaluation context doesn't contain any targeting information.
+    pub fn default_user(mut self, user: User) -> Self {
+        self.template.default_user = Some(user.clone());
+        self.client_builder = self.client_builder.default_user(user);
+        self
+    }
+
+    /// Sets feature flag and setting overrides for the underlying ConfigCat SDK client.
+    pub fn overrides(
+        mut self,
+        source: Box<dyn OverrideDataSource>,
+        behavior: OverrideBehavior,
+    ) -> Self {
+        let source: Arc<dyn OverrideDataSource> = Arc::from(source);
+        self.client_builder = self.client_builder.overrides(
+            Box::new(SharedSource(source.clone())),
+            copy_behavior(&behavior),
+        );
+        self.template.overrides = Some((source, behavior));
+        self
+    }
+
+    /// Sets the schema of the feature flags the application expects, which is validated
+    /// against the downloaded config JSON when the provider initializes, and again each time
+    /// the config JSON changes.
+    ///
+    /// All the missing required flags and type mismatches are reported at once as a warning
+    /// (and to Sentry with the `sentry` feature) whenever the result of the validation changes,
+    /// e.g. when a flag is deleted or its type is changed on the ConfigCat Dashboard. The latest
+    /// result is available with [`ConfigCatProvider::schema_report`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::{ConfigCatProvider, FlagSchema, FlagType};
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .schema(
+    ///         FlagSchema::new()
+    ///             .required("isNewCheckout", FlagType::Bool)
+    ///             .optional("maxItems", FlagType::Int),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn schema(mut self, schema: FlagSchema) -> Self {
+        self.options.schema = Some(schema);
+        self
+    }
+
+    /// Adds a callback invoked when the config JSON stops matching the schema set with
+    /// [`ConfigCatProviderBuilder::schema`], or when the mismatches change, with the result of
+    /// the validation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::{ConfigCatProvider, FlagSchema, FlagType};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key")
+    ///         .schema(FlagSchema::new().required("isNewCheckout", FlagType::Bool))
+    ///         .on_schema_drift(|report| eprintln!("Breaking flag change: {report}"))
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn on_schema_drift<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&VerificationReport) + Send + Sync + 'static,
+    {
+        self.options.on_schema_drift.push(Box::new(callback));
+        self
+    }
+
+    /// Sets a custom cache for the config JSON downloaded by the provider.
+    ///
+    /// Instances sharing the same cache (e.g. a Redis instance) can use each other's downloads
+    /// instead of fetching the config JSON from the ConfigCat CDN. See [`ProviderCache`] for details.
+    pub fn cache(mut self, cache: impl ProviderCache + 'static) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Persists the last fetched config JSON to the given file, and loads it at startup.
+    ///
+    /// The persisted config JSON is used until the first successful fetch, and whenever the
+    /// config JSON can't be fetched, which gives resilience against flaky connectivity (e.g. for
+    /// CLI tools and edge deployments). The file is replaced each time a new config JSON is
+    /// downloaded.
+    pub fn persist_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persist_path = Some(path.into());
+        self
+    }
+
+    /// Sets a config JSON baked into the binary, which is used when the config JSON can't be
+    /// fetched and no cached or persisted config JSON exists.
+    ///
+    /// Useful for air-gapped and on-prem deployments. The config JSON can be downloaded from
+    /// the ConfigCat CDN (or a ConfigCat Proxy) at build time.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .fallback_config_bytes(include_bytes!("flags.json"))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn fallback_config_bytes(mut self, config_json: &[u8]) -> Self {
+        self.fallback_config = Some(String::from_utf8_lossy(config_json).into_owned());
+        self
+    }
+
+    /// Sets how long the provider's initialization waits for the underlying ConfigCat SDK
+    /// client to become ready (to have flag data to evaluate).
+    ///
+    /// Default is 10 seconds.
+    pub fn init_timeout(mut self, timeout: Duration) -> Self {
+        self.options.init_timeout = timeout;
+        self
+    }
+
+    /// Adds an [`EvaluationSink`] that gets notified about each flag evaluation performed by the provider.
+    ///
+    /// Multiple sinks can be added; they are notified in the order they were added.
+    pub fn sink(mut self, sink: impl EvaluationSink + 'static) -> Self {
+        self.options.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Sets the [`TrackingSink`] the events tracked with [`ConfigCatProvider::track`] are
+    /// forwarded to.
+    pub fn tracking_sink(mut self, sink: impl TrackingSink + 'static) -> Self {
+        self.options.tracking_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Enables counting the evaluations of each flag by served value, reported in
+    /// [`crate::FlagUsage::values`] of [`ConfigCatProvider::usage_report`].
+    ///
+    /// The values are serialized on each evaluation, and at most
+    /// [`crate::MAX_COUNTED_VALUES`] distinct values are counted separately per flag.
+    pub fn count_served_values(mut self) -> Self {
+        self.options.count_served_values = true;
+        self
+    }
+
+    /// Enables attaching the variants served to a targeting key within the given time window
+    /// to the events tracked for the same targeting key with [`ConfigCatProvider::track`], in
+    /// [`crate::TrackingEvent::exposures`].
+    ///
+    /// The tracking events can then be analyzed as experiment results without joining them
+    /// with the evaluation events. Only the latest variant of each flag is kept, for a bounded
+    /// number of targeting keys.
+    pub fn correlate_exposures(mut self, window: Duration) -> Self {
+        self.options.exposure_window = Some(window);
+        self
+    }
+
+    /// Adds a callback invoked before each evaluation with the flag key and the evaluation
+    /// context, which it can rewrite.
+    ///
+    /// Unlike OpenFeature hooks, these callbacks also run when the provider is used directly.
+    /// Multiple callbacks can be added; they are invoked in the order they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .before(|_flag_key, ctx| {
+    ///         ctx.add_custom_field("service", "checkout");
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn before<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &mut EvaluationContext) + Send + Sync + 'static,
+    {
+        self.options.before.push(Box::new(callback));
+        self
+    }
+
+    /// Adds a callback invoked after each evaluation with the flag key and the evaluation
+    /// result, which it can post-process.
+    ///
+    /// The value of the result is represented as an OpenFeature [`Value`]. When a callback
+    /// changes it to a different type than the requested one, the evaluation fails with a
+    /// type mismatch error. Multiple callbacks can be added; they are invoked in the order
+    /// they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use open_feature::{EvaluationErrorCode, Value};
+    /// use open_feature::provider::ResolutionDetails;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .after(|flag_key, result| {
+    ///         // Turn kill switches off when they are missing.
+    ///         if flag_key.starts_with("killSwitch") {
+    ///             if let Err(err) = result {
+    ///                 if err.code == EvaluationErrorCode::FlagNotFound {
+    ///                     *result = Ok(ResolutionDetails::new(Value::Bool(false)));
+    ///                 }
+    ///             }
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn after<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &mut EvaluationResult<ResolutionDetails<Value>>) + Send + Sync + 'static,
+    {
+        self.options.after.push(Box::new(callback));
+        self
+    }
+
+    /// Adds a callback invoked after each flag evaluation with the flag key and the ConfigCat
+    /// SDK's raw evaluation details.
+    ///
+    /// Besides the evaluated value, the details contain the matched targeting rule and
+    /// percentage option and the User Object used for the evaluation, which are useful for
+    /// custom exposure logging. The callback isn't invoked when the evaluation context
+    /// couldn't be converted to a User Object.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .on_evaluated(|flag_key, details| {
+    ///         if let Some(option) = details.matched_percentage_option.as_ref() {
+    ///             println!("'{flag_key}' served a {}% option to {:?}", option.percentage, details.user);
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_evaluated<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &configcat::EvaluationDetails<configcat::Value>) + Send + Sync + 'static,
+    {
+        self.options.on_evaluated.push(Box::new(callback));
+        self
+    }
+
+    /// Adds a callback invoked each time the config JSON changes, either by a download or by a
+    /// reload of the watched flag overrides file. It's the counterpart of OpenFeature's
+    /// `PROVIDER_CONFIGURATION_CHANGED` event, which isn't supported by the OpenFeature SDK yet.
+    ///
+    /// The initial load of the config JSON isn't reported. Changes are only tracked when the
+    /// provider is created with this builder.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key")
+    ///         .on_configuration_changed(|| println!("The flags have changed."))
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn on_configuration_changed<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.options.on_config_changed.push(Box::new(callback));
+        self
+    }
+
+    /// Adds a callback invoked with the keys of the added, removed and changed flags each time
+    /// the config JSON changes, so consumers can invalidate their caches selectively.
+    ///
+    /// Like with [`ConfigCatProviderBuilder::on_configuration_changed`], the initial load of
+    /// the config JSON isn't reported. Changes not affecting any flag aren't reported either.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key")
+    ///         .on_flags_changed(|change| {
+    ///             for flag_key in change.keys() {
+    ///                 println!("'{flag_key}' has changed.");
+    ///             }
+    ///         })
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn on_flags_changed<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ConfigChange) + Send + Sync + 'static,
+    {
+        self.options
+            .on_flags_changed
+            .push((String::new(), Box::new(callback)));
+        self
+    }
+
+    /// Adds a callback like [`ConfigCatProviderBuilder::on_flags_changed`], which is only
+    /// invoked when flags whose key starts with the given prefix change, with those flags.
+    ///
+    /// Useful for large configs shared by several components, so a component isn't notified
+    /// about the changes of unrelated flags.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key")
+    ///         .on_flags_changed_with_prefix("checkout_", |change| {
+    ///             println!("Checkout flags changed: {:?}", change.changed);
+    ///         })
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn on_flags_changed_with_prefix<F>(mut self, prefix: &str, callback: F) -> Self
+    where
+        F: Fn(&ConfigChange) + Send + Sync + 'static,
+    {
+        self.options
+            .on_flags_changed
+            .push((prefix.to_owned(), Box::new(callback)));
+        self
+    }
+
+    /// Adds an alias for a flag key, so code referencing the old key of a renamed flag keeps
+    /// working.
+    ///
+    /// Evaluations of `alias` evaluate `flag_key` instead, and are reported to the callbacks,
+    /// sinks and statistics under `flag_key`. A deprecation warning is logged the first time
+    /// each alias is used.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .alias("old_checkout_flag", "checkoutV2Enabled")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn alias(mut self, alias: &str, flag_key: &str) -> Self {
+        self.options
+            .aliases
+            .insert(alias.to_owned(), flag_key.to_owned());
+        self
+    }
+
+    /// Sets a namespace prepended to the requested flag keys.
+    ///
+    /// It lets multiple services (or OpenFeature domains) share one ConfigCat config, each
+    /// using the flags under its own prefix by their short keys. [`ConfigCatProvider::resolve_all`]
+    /// and [`ConfigCatProvider::flag_keys`] only return the flags under the prefix, without the
+    /// prefix. Aliases (see [`ConfigCatProviderBuilder::alias`]) are resolved before the prefix
+    /// is prepended.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// // Evaluations of "checkoutEnabled" evaluate "payments_checkoutEnabled".
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .key_prefix("payments_")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn key_prefix(mut self, prefix: &str) -> Self {
+        prefix.clone_into(&mut self.options.key_prefix);
+        self
+    }
+
+    /// Sets the format of the object values stored in text settings, used when they're resolved
+    /// with `resolve_struct_value`.
+    ///
+    /// Default is [`StructFormat::Json`].
+    pub fn struct_format(mut self, format: StructFormat) -> Self {
+        self.options.struct_formats.default = format;
+        self
+    }
+
+    /// Sets the format of the object value stored in the given text setting, overriding the
+    /// one set with [`ConfigCatProviderBuilder::struct_format`] for that flag key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::{ConfigCatProvider, StructFormat};
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .struct_format_for("retryPolicy", StructFormat::Auto)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn struct_format_for(mut self, flag_key: &str, format: StructFormat) -> Self {
+        self.options
+            .struct_formats
+            .per_key
+            .insert(flag_key.to_owned(), format);
+        self
+    }
+
+    /// Substitutes the placeholders of the evaluated text settings with the attributes of the
+    /// ConfigCat User Object built from the evaluation context.
+    ///
+    /// The predefined attributes are referenced by their name (e.g. `{{Identifier}}` or
+    /// `{{Email}}`), the custom ones with the `custom.` prefix (e.g. `{{custom.region}}`).
+    /// Placeholders of missing attributes are kept as is. The placeholders are substituted in
+    /// the values returned by all evaluation methods, including the bulk ones like
+    /// [`ConfigCatProvider::resolve_all`], but not in the text settings resolved as objects.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// // "https://{{custom.region}}.example.com" is resolved to "https://eu.example.com"
+    /// // for a context with a "region" attribute of "eu".
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .interpolate_strings()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn interpolate_strings(mut self) -> Self {
+        self.options.interpolate_strings = true;
+        self
+    }
+
+    /// Adds a transformation applied to the values of all text settings, after the placeholders
+    /// are substituted (see [`ConfigCatProviderBuilder::interpolate_strings`]).
+    ///
+    /// The transformations of all text settings run before the ones added for a flag key with
+    /// [`ConfigCatProviderBuilder::transform_string`], both in the order they were added. Like
+    /// the placeholder substitution, they apply to the values returned by all evaluation
+    /// methods, except the text settings resolved as objects.
+    pub fn transform_strings(mut self, transform: StringTransform) -> Self {
+        self.options.string_transforms.all.push(transform);
+        self
+    }
+
+    /// Adds a transformation applied to the value of the given text setting.
+    ///
+    /// See [`ConfigCatProviderBuilder::transform_strings`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::{ConfigCatProvider, StringTransform};
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .transform_string("theme", StringTransform::Lowercase)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn transform_string(mut self, flag_key: &str, transform: StringTransform) -> Self {
+        self.options
+            .string_transforms
+            .per_key
+            .entry(flag_key.to_owned())
+            .or_default()
+            .push(transform);
+        self
+    }
+
+    /// Routes the ConfigCat SDK's internal log messages into `tracing` through the given [`crate::SdkLogBridge`].
+    ///
+    /// The bridge is installed as the global `log` logger when the provider is built.
+    #[cfg(feature = "tracing")]
+    pub fn sdk_log_bridge(mut self, bridge: crate::SdkLogBridge) -> Self {
+        self.log_bridge = Some(bridge);
+        self
+    }
+
+    /// Serves the flag overrides of a local JSON file, and reloads them each time the file
+    /// changes, so flag edits are picked up without restarting the application during local
+    /// development.
+    ///
+    /// The file can be a config JSON, or in the simplified `{"flags": {"key": value}}` format,
+    /// like with the ConfigCat SDK's `FileDataSource`. Like with local-only flag overrides, the
+    /// provider doesn't make network requests, although the SDK key still has to be in a valid
+    /// format. When the changed file can't be loaded, a warning
+    /// is logged and the previous flags are kept. Reloads are reported to the flag watchers,
+    /// and to the callbacks added with [`ConfigCatProviderBuilder::on_configuration_changed`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .watch_file_overrides("flags.json")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "hot-reload")]
+    pub fn watch_file_overrides(mut self, path: impl Into<PathBuf>) -> Self {
+        self.watched_file = Some(path.into());
+        self.polling_mode = PollingMode::Manual;
+        self.offline(true)
+    }
+
+    /// Subscribes to the flag value updates of a [ConfigCat Proxy](https://configcat.com/docs/advanced/proxy/proxy-overview/)
+    /// over gRPC, and refreshes the config JSON as soon as the Proxy reports a change, instead
+    /// of waiting for the next poll.
+    ///
+    /// The Proxy's gRPC API streams evaluated flag values rather than the config JSON, so the
+    /// config JSON is still downloaded from the [`ConfigCatProviderBuilder::base_url`] of the
+    /// Proxy. As the stream reports the values evaluated without user attributes, changes of
+    /// targeting rules only are picked up by the polling, which can run with a longer interval
+    /// as a fallback. The `sdk_id` is the SDK identifier configured in the Proxy. The stream is
+    /// reopened after a short delay when it's interrupted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::PollingMode;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use tonic::transport::Channel;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let channel = Channel::from_static("http://configcat-proxy:50051").connect_lazy();
+    ///     let provider = ConfigCatProvider::builder("configcat-proxy/my-sdk")
+    ///         .base_url("http://configcat-proxy:8050")
+    ///         .polling_mode(PollingMode::AutoPoll(Duration::from_secs(600)))
+    ///         .proxy_grpc(channel, "my-sdk")
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "proxy-grpc")]
+    pub fn proxy_grpc(mut self, channel: tonic::transport::Channel, sdk_id: &str) -> Self {
+        self.proxy_grpc = Some((channel, sdk_id.to_owned()));
+        self
+    }
+
+    /// Subscribes to the server-sent events stream of a [ConfigCat Proxy](https://configcat.com/docs/advanced/proxy/proxy-overview/),
+    /// and refreshes the config JSON as soon as the Proxy reports a change, instead of waiting
+    /// for the next poll. The config JSON changes are reported to the flag watchers and to the
+    /// callbacks added with [`ConfigCatProviderBuilder::on_configuration_changed`] right away.
+    ///
+    /// Like with [`ConfigCatProviderBuilder::proxy_grpc`], the stream carries the flag values
+    /// evaluated without user attributes, so the config JSON is still downloaded from the
+    /// [`ConfigCatProviderBuilder::base_url`] of the Proxy, and changes of targeting rules only
+    /// are picked up by the polling. The `sdk_id` is the SDK identifier configured in the Proxy.
+    /// The stream is requested with the [`ConfigCatProviderBuilder::http_client`] when set, and
+    /// it's reopened after a short delay when it's interrupted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::PollingMode;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("configcat-proxy/my-sdk")
+    ///         .base_url("http://configcat-proxy:8050")
+    ///         .polling_mode(PollingMode::AutoPoll(Duration::from_secs(600)))
+    ///         .proxy_sse("http://configcat-proxy:8050", "my-sdk")
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "proxy-sse")]
+    pub fn proxy_sse(mut self, proxy_url: &str, sdk_id: &str) -> Self {
+        self.proxy_sse = Some(crate::proxy_sse::stream_url(proxy_url, sdk_id));
+        self
+    }
+
+    /// Creates a [`ConfigCatProvider`] from the configuration made on the builder.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the underlying ConfigCat SDK client can't be created, e.g. when the
+    /// given SDK key is empty or has an invalid format (the error message describes the likely
+    /// mistake, like a truncated key), or when the watched flag overrides file
+    /// can't be loaded.
+    ///
+    /// # Panics
+    ///
+    /// With [`PollingMode::AutoPoll`], configuration change callbacks, a flag schema or a
+    /// ConfigCat Proxy update stream, this method panics when called outside of a Tokio
+    /// runtime, as it spawns background tasks.
+    pub fn build(mut self) -> Result<ConfigCatProvider, ClientError> {
+        if !matches!(
+            self.template.overrides,
+            Some((_, OverrideBehavior::LocalOnly))
+        ) {
+            sdk_key::validate(&self.template.sdk_key, self.base_url.is_some())?;
+        }
+        #[cfg(feature = "tracing")]
+        if let Some(bridge) = self.log_bridge.take() {
+            bridge.install();
+        }
+        let options = std::mem::take(&mut self.options);
+        let source = if self.shared {
+            let sdk_key = self.template.sdk_key.clone();
+            let source_options = self.source_options();
+            ConfigSource::shared(sdk_key.as_str(), source_options, || self.build_source())?
+        } else {
+            self.build_source()?
+        };
+        Ok(ConfigCatProvider::with_options(source, options))
+    }
+
+    /// Describes the options that apply to the [`ConfigSource`], so the providers sharing it can
+    /// detect when their options are ignored. The custom caches, HTTP clients, default users and
+    /// override sources are only compared by their presence.
+    fn source_options(&self) -> String {
+        let options = format!(
+            "{:?}{:?}",
+            (
+                &self.polling_mode,
+                self.revalidate_ttl,
+                self.serverless,
+                &self.base_url,
+                self.cdn_url,
+                self.offline,
+                self.refresh_interval,
+                self.max_init_wait,
+                self.http_timeout,
+            ),
+            (
+                &self.persist_path,
+                &self.fallback_config,
+                self.cache.is_some(),
+                self.http_client.is_some(),
+                self.template.default_user.is_some(),
+                self.template.overrides.is_some(),
+            )
+        );
+        #[cfg(feature = "hot-reload")]
+        let options = format!("{options}{:?}", self.watched_file);
+        #[cfg(feature = "proxy-grpc")]
+        let options = format!("{options}{:?}", self.proxy_grpc.as_ref().map(|(_, id)| id));
+        #[cfg(feature = "proxy-sse")]
+        let options = format!("{options}{:?}", self.proxy_sse);
+        options
+    }
+
+    fn build_source(self) -> Result<Arc<ConfigSource>, ClientError> {
+        let tap = Arc::new(ConfigTap::default());
+        let serverless = self.serverless;
+        let bridge = self.cache.map(|cache| {
+            let bridge = CacheBridge::new(cache, self.template.sdk_key.as_str());
+            Arc::new(if serverless.is_some() {
+                bridge.deferred()
+            } else {
+                bridge
+            })
+        });
+        let mut inner_cache = bridge
+            .clone()
+            .map(|bridge| Box::new(BridgeCache(bridge)) as Box<dyn ConfigCache>);
+        if self.persist_path.is_some() || self.fallback_config.is_some() {
+            inner_cache = Some(Box::new(PersistentCache::new(
+                self.persist_path,
+                self.fallback_config.as_deref(),
+                inner_cache,
+            )));
+        }
+        #[cfg(feature = "hot-reload")]
+        if let Some(path) = self.watched_file.as_deref() {
+            let cache = crate::hot_reload::WatchedFileCache::new(path, tap.clone())?;
+            inner_cache = Some(Box::new(cache));
+        }
+        #[cfg(feature = "proxy-sse")]
+        let stream_client = self.http_client.clone().unwrap_or_default();
+        let mut downloader = None;
+        let client_builder = self.client_builder.polling_mode(PollingMode::Manual);
+        let client = if let Some(http_client) = self.http_client {
+            // The SDK client reads the downloaded config JSON from the cache, which needs an
+            // in-memory layer when nothing else keeps it.
+            let inner_cache =
+                inner_cache.unwrap_or_else(|| Box::new(PersistentCache::new(None, None, None)));
+            let cache: Arc<dyn ConfigCache> =
+                Arc::new(TapCache::new(tap.clone(), Some(inner_cache)));
+            downloader = Some(Downloader::new(
+                http_client,
+                self.base_url.as_deref(),
+                self.cdn_url,
+                self.template.sdk_key.as_str(),
+                cache.clone(),
+                self.offline,
+            ));
+            client_builder
+                .offline(true)
+                .cache(Box::new(SharedCache(cache)))
+                .build()?
+        } else {
+            client_builder
+                .cache(Box::new(TapCache::new(tap.clone(), inner_cache)))
+                .build()?
+        };
+        let local_only = matches!(
+            self.template.overrides,
+            Some((_, OverrideBehavior::LocalOnly))
+        );
+        let refresh_mode = match (self.polling_mode, self.revalidate_ttl, serverless) {
+            _ if local_only => RefreshMode::Manual,
+            (_, _, Some((ttl, timeout))) => RefreshMode::Serverless { ttl, timeout },
+            (_, Some(ttl), None) => RefreshMode::Revalidate(ttl),
+            (PollingMode::AutoPoll(interval), None, None) => RefreshMode::Poll(interval),
+            (PollingMode::LazyLoad(ttl), None, None) => RefreshMode::Lazy(ttl),
+            (PollingMode::Manual, None, None) => RefreshMode::Manual,
+        };
+        let source = ConfigSource::new(
+            client,
+            Refresher::new(refresh_mode, bridge)
+                .with_refresh_interval(self.refresh_interval)
+                .with_max_init_wait(self.max_init_wait)
+                .with_downloader(downloader),
+            Some(tap),
+            Some(self.template),
+        );
+        #[cfg(feature = "proxy-grpc")]
+        if let Some((channel, sdk_id)) = self.proxy_grpc {
+            crate::proxy_grpc::subscribe(&source, channel, sdk_id);
+        }
+        #[cfg(feature = "proxy-sse")]
+        if let Some(url) = self.proxy_sse {
+            crate::proxy_sse::subscribe(&source, stream_client, url);
+        }
+        Ok(source)
+    }
+}
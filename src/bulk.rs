@@ -0,0 +1,103 @@
+use open_feature::provider::ResolutionDetails;
+use open_feature::{StructValue, Value};
+use std::collections::HashMap;
+
+/// A set of feature flags evaluated together into a single struct.
+///
+/// Implement it for a struct with one field per flag, then evaluate the whole set with
+/// [`crate::ConfigCatProvider::resolve_set`]. All flags of the set are evaluated against
+/// the same config JSON with one await, so rendering code can read plain fields afterwards.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagSet, FlagValues};
+/// use open_feature::EvaluationContext;
+///
+/// struct PageFlags {
+///     new_header: bool,
+///     max_items: i64,
+///     banner_text: String,
+/// }
+///
+/// impl FlagSet for PageFlags {
+///     fn from_flags(flags: &FlagValues) -> Self {
+///         Self {
+///             new_header: flags.bool("newHeader", false),
+///             max_items: flags.int("maxItems", 10),
+///             banner_text: flags.string("bannerText", ""),
+///         }
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     let ctx = EvaluationContext::default().with_targeting_key("user-id");
+///     let flags: PageFlags = provider.resolve_set(&ctx).await;
+///     if flags.new_header {
+///         // ...
+///     }
+/// }
+/// ```
+pub trait FlagSet: Sized {
+    /// Creates the struct from the evaluated flag values.
+    fn from_flags(flags: &FlagValues) -> Self;
+}
+
+/// The feature flag values evaluated for a [`FlagSet`].
+///
+/// The typed accessors return the given default value when the flag is missing, failed to
+/// evaluate, or has a different type than the requested one.
+pub struct FlagValues {
+    values: HashMap<String, ResolutionDetails<Value>>,
+}
+
+impl FlagValues {
+    pub(crate) fn new(values: HashMap<String, ResolutionDetails<Value>>) -> Self {
+        Self { values }
+    }
+
+    /// Returns the value of a feature flag.
+    pub fn bool(&self, key: &str, default: bool) -> bool {
+        self.value(key).and_then(Value::as_bool).unwrap_or(default)
+    }
+
+    /// Returns the value of a whole number setting.
+    pub fn int(&self, key: &str, default: i64) -> i64 {
+        self.value(key).and_then(Value::as_i64).unwrap_or(default)
+    }
+
+    /// Returns the value of a decimal number setting.
+    pub fn float(&self, key: &str, default: f64) -> f64 {
+        self.value(key).and_then(Value::as_f64).unwrap_or(default)
+    }
+
+    /// Returns the value of a text setting.
+    pub fn string(&self, key: &str, default: &str) -> String {
+        self.value(key)
+            .and_then(Value::as_str)
+            .unwrap_or(default)
+            .to_owned()
+    }
+
+    /// Returns the value of a text setting parsed as a JSON object.
+    pub fn object(&self, key: &str) -> Option<StructValue> {
+        let json: serde_json::Value = serde_json::from_str(self.value(key)?.as_str()?).ok()?;
+        match Value::try_from(json).ok()? {
+            Value::Struct(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the resolution details of a flag, or `None` when it's missing or failed
+    /// to evaluate.
+    pub fn details(&self, key: &str) -> Option<&ResolutionDetails<Value>> {
+        self.values.get(key)
+    }
+
+    fn value(&self, key: &str) -> Option<&Value> {
+        self.values.get(key).map(|details| &details.value)
+    }
+}
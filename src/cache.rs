@@ -0,0 +1,161 @@
+use crate::runtime;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use configcat::ConfigCache;
+use sha1::{Digest, Sha1};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+/// A cache that stores the config JSON downloaded by the [`crate::ConfigCatProvider`].
+///
+/// Unlike the ConfigCat SDK's `ConfigCache`, its methods are async, so it can be backed by
+/// a distributed store (like Redis or a database) without blocking the evaluations.
+/// Evaluations are served from memory; the cache is read before the provider fetches the
+/// config JSON, and is written after each successful fetch.
+///
+/// When the cached config JSON is younger than the poll interval (or the cache TTL in lazy
+/// loading mode), the provider uses it instead of downloading it, so instances sharing a
+/// cache hit the ConfigCat CDN only once per interval.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+/// use async_trait::async_trait;
+/// use configcat_openfeature_provider::{ConfigCatProvider, ProviderCache};
+///
+/// #[derive(Default)]
+/// struct MapCache(Mutex<HashMap<String, String>>);
+///
+/// #[async_trait]
+/// impl ProviderCache for MapCache {
+///     async fn read(&self, key: &str) -> Option<String> {
+///         self.0.lock().unwrap().get(key).cloned()
+///     }
+///
+///     async fn write(&self, key: &str, value: &str) {
+///         self.0.lock().unwrap().insert(key.to_owned(), value.to_owned());
+///     }
+/// }
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .cache(MapCache::default())
+///     .build()
+///     .unwrap();
+/// ```
+#[async_trait]
+pub trait ProviderCache: Send + Sync {
+    /// Reads the cached value of the given key.
+    async fn read(&self, key: &str) -> Option<String>;
+
+    /// Writes the value of the given key into the cache.
+    async fn write(&self, key: &str, value: &str);
+}
+
+/// Connects a [`ProviderCache`] to the underlying ConfigCat SDK client.
+///
+/// The SDK reads its cache synchronously on each evaluation, so it's served from an
+/// in-memory mirror, which is loaded from the [`ProviderCache`] before fetches.
+pub(crate) struct CacheBridge {
+    cache: Arc<dyn ProviderCache>,
+    key: String,
+    mirror: RwLock<Option<String>>,
+    deferred: bool,
+    dirty: AtomicBool,
+}
+
+impl CacheBridge {
+    pub(crate) fn new(cache: Arc<dyn ProviderCache>, sdk_key: &str) -> Self {
+        Self {
+            cache,
+            key: cache_key(sdk_key),
+            mirror: RwLock::new(None),
+            deferred: false,
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Defers the cache writes until [`CacheBridge::flush`] is called, instead of performing
+    /// them in background tasks.
+    pub(crate) fn deferred(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+
+    /// Writes the config JSON downloaded since the last flush into the cache.
+    pub(crate) async fn flush(&self) {
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        let value = self
+            .mirror
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(value) = value {
+            self.cache.write(self.key.as_str(), value.as_str()).await;
+        }
+    }
+
+    /// Loads the cached config JSON into the mirror, and returns whether it's younger than
+    /// the given age.
+    pub(crate) async fn load(&self, max_age: Duration) -> bool {
+        let Some(value) = self.cache.read(self.key.as_str()).await else {
+            return false;
+        };
+        let fresh = fetch_time(value.as_str()).is_some_and(|fetch_time| {
+            (Utc::now() - fetch_time)
+                .to_std()
+                .is_ok_and(|age| age < max_age)
+        });
+        *self.mirror.write().unwrap_or_else(PoisonError::into_inner) = Some(value);
+        fresh
+    }
+}
+
+/// The [`ConfigCache`] side of a [`CacheBridge`] installed into the ConfigCat SDK client.
+pub(crate) struct BridgeCache(pub(crate) Arc<CacheBridge>);
+
+impl ConfigCache for BridgeCache {
+    fn read(&self, _: &str) -> Option<String> {
+        self.0
+            .mirror
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn write(&self, _: &str, value: &str) {
+        *self
+            .0
+            .mirror
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(value.to_owned());
+        if self.0.deferred {
+            self.0.dirty.store(true, Ordering::Release);
+            return;
+        }
+        let bridge = self.0.clone();
+        let value = value.to_owned();
+        runtime::spawn(async move {
+            bridge
+                .cache
+                .write(bridge.key.as_str(), value.as_str())
+                .await;
+        });
+    }
+}
+
+/// Produces the same cache key the ConfigCat SDK uses for the given SDK key.
+pub(crate) fn cache_key(sdk_key: &str) -> String {
+    let hash = Sha1::digest(format!("{sdk_key}_config_v6.json_v2").as_bytes());
+    format!("{hash:x}")
+}
+
+/// Reads the fetch time of an SDK cache entry.
+pub(crate) fn fetch_time(cache_str: &str) -> Option<DateTime<Utc>> {
+    let (millis, _) = cache_str.split_once('\n')?;
+    DateTime::from_timestamp_millis(millis.parse().ok()?)
+}
@@ -0,0 +1,101 @@
+use serde_json::{Map, Value};
+
+/// The flag keys affected by a config JSON change, passed to the
+/// [`crate::ConfigCatProviderBuilder::on_flags_changed`] callbacks.
+///
+/// The keys are in alphabetical order. Consumers caching evaluation results can use it to
+/// invalidate only the entries of the affected flags.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// The keys of the flags added to the config JSON.
+    pub added: Vec<String>,
+    /// The keys of the flags removed from the config JSON.
+    pub removed: Vec<String>,
+    /// The keys of the flags whose type, values or targeting changed. When the segments or
+    /// the salt of the config JSON change, all the flags are reported as changed.
+    pub changed: Vec<String>,
+}
+
+impl ConfigChange {
+    /// Returns whether no flag is affected by the change.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Returns the part of the change affecting the flags whose key starts with the given
+    /// prefix.
+    pub fn with_prefix(&self, prefix: &str) -> ConfigChange {
+        let filter = |keys: &[String]| {
+            keys.iter()
+                .filter(|key| key.starts_with(prefix))
+                .cloned()
+                .collect()
+        };
+        ConfigChange {
+            added: filter(&self.added),
+            removed: filter(&self.removed),
+            changed: filter(&self.changed),
+        }
+    }
+
+    /// Returns the keys of all the affected flags.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.added
+            .iter()
+            .chain(&self.removed)
+            .chain(&self.changed)
+            .map(String::as_str)
+    }
+}
+
+/// Parses a config JSON, or returns an empty map when it can't be parsed.
+pub(crate) fn parse(config_json: &str) -> Map<String, Value> {
+    match serde_json::from_str(config_json) {
+        Ok(Value::Object(config)) => config,
+        _ => Map::new(),
+    }
+}
+
+/// Compares the flags of two config JSONs. Only the flags under the key prefix are reported,
+/// without the prefix.
+pub(crate) fn diff(
+    previous: &Map<String, Value>,
+    current: &Map<String, Value>,
+    key_prefix: &str,
+) -> ConfigChange {
+    let empty = Map::new();
+    let previous_settings = settings(previous).unwrap_or(&empty);
+    let current_settings = settings(current).unwrap_or(&empty);
+    // Segments and the salt can affect the evaluation of any flag.
+    let shared_changed =
+        previous.get("p") != current.get("p") || previous.get("s") != current.get("s");
+    let mut change = ConfigChange::default();
+    for (flag_key, setting) in current_settings {
+        let Some(key) = flag_key.strip_prefix(key_prefix) else {
+            continue;
+        };
+        match previous_settings.get(flag_key) {
+            None => change.added.push(key.to_owned()),
+            Some(previous) if shared_changed || previous != setting => {
+                change.changed.push(key.to_owned());
+            }
+            Some(_) => {}
+        }
+    }
+    for flag_key in previous_settings.keys() {
+        if let (Some(key), false) = (
+            flag_key.strip_prefix(key_prefix),
+            current_settings.contains_key(flag_key),
+        ) {
+            change.removed.push(key.to_owned());
+        }
+    }
+    change.added.sort_unstable();
+    change.removed.sort_unstable();
+    change.changed.sort_unstable();
+    change
+}
+
+fn settings(config: &Map<String, Value>) -> Option<&Map<String, Value>> {
+    config.get("f")?.as_object()
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+/// Generates Rust source code declaring typed accessors for the settings of a ConfigCat config
+/// JSON, using [`declare_flags!`](crate::declare_flags).
+///
+/// Each setting gets a public unit struct named after its key in `UpperCamelCase`
+/// (e.g. `isNewCheckoutEnabled` becomes `IsNewCheckoutEnabled`), typed according to the
+/// setting type.
+///
+/// # Errors
+///
+/// This function fails if the config JSON is invalid, or two setting keys map to the same
+/// Rust identifier.
+pub fn generate_flags(config_json: &str) -> Result<String, Error> {
+    let config: serde_json::Value =
+        serde_json::from_str(config_json).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+    let settings = config
+        .get("f")
+        .and_then(serde_json::Value::as_object)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing settings ('f')"))?;
+
+    let mut names = HashMap::new();
+    let mut out = String::from(
+        "// @generated by configcat-openfeature-provider from a config JSON. Do not edit.\n\n\
+         ::configcat_openfeature_provider::declare_flags! {\n",
+    );
+    for (key, setting) in settings {
+        let value_type = match setting.get("t").and_then(serde_json::Value::as_u64) {
+            Some(0) => "bool",
+            Some(1) => "::std::string::String",
+            Some(2) => "i64",
+            Some(3) => "f64",
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown setting type of '{key}'"),
+                ))
+            }
+        };
+        let name = type_name(key);
+        if let Some(other) = names.insert(name.clone(), key) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("'{other}' and '{key}' map to the same name '{name}'"),
+            ));
+        }
+        _ = writeln!(out, "    pub {name}: {value_type} = {key:?};");
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// Reads a ConfigCat config JSON from `config_path`, and writes the typed accessors generated
+/// by [`generate_flags`] to `out_path`. The file is only rewritten when its content changes.
+///
+/// Intended to be called from a build script, with the generated file included into the crate.
+///
+/// # Errors
+///
+/// This function fails if the config JSON can't be read or is invalid, or the generated code
+/// can't be written.
+///
+/// # Examples
+///
+/// In `build.rs`:
+///
+/// ```no_run
+/// use std::path::PathBuf;
+///
+/// fn main() {
+///     let out_path = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("flags.rs");
+///     configcat_openfeature_provider::write_flags("config/flags.json", &out_path).unwrap();
+///     println!("cargo::rerun-if-changed=config/flags.json");
+/// }
+/// ```
+///
+/// Then in the crate:
+///
+/// ```ignore
+/// mod flags {
+///     include!(concat!(env!("OUT_DIR"), "/flags.rs"));
+/// }
+///
+/// let enabled = flags::IsNewCheckoutEnabled::get(&provider, &ctx, false).await;
+/// ```
+pub fn write_flags(config_path: impl AsRef<Path>, out_path: impl AsRef<Path>) -> Result<(), Error> {
+    let code = generate_flags(std::fs::read_to_string(config_path)?.as_str())?;
+    if std::fs::read_to_string(out_path.as_ref()).is_ok_and(|existing| existing == code) {
+        return Ok(());
+    }
+    std::fs::write(out_path, code)
+}
+
+/// Converts a setting key to an `UpperCamelCase` Rust identifier.
+fn type_name(key: &str) -> String {
+    let mut name = String::with_capacity(key.len());
+    let mut upper = true;
+    for ch in key.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if upper {
+                name.push(ch.to_ascii_uppercase());
+            } else {
+                name.push(ch);
+            }
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+    if !name.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+        name.insert(0, 'F');
+    }
+    if name == "Self" {
+        name.push('_');
+    }
+    name
+}
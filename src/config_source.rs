@@ -0,0 +1,122 @@
+use crate::provider::ConfigCatProvider;
+use async_trait::async_trait;
+use config::{AsyncSource, ConfigError, Map, Value as ConfigValue, ValueKind};
+use open_feature::{EvaluationContext, Value};
+use std::fmt::{Debug, Formatter};
+
+const ORIGIN: &str = "configcat";
+
+/// A [`config`] crate source that exposes feature flags and settings as configuration values.
+///
+/// Add it to a `ConfigBuilder` with `add_async_source` to blend the flags into the layered
+/// configuration of the application. The flags are evaluated with the given evaluation
+/// context each time the configuration is built. By default all flags are exposed under their
+/// own keys; select a subset with [`ConfigCatSource::flag`] and [`ConfigCatSource::flag_as`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use config::builder::AsyncState;
+/// use config::ConfigBuilder;
+/// use configcat_openfeature_provider::{ConfigCatProvider, ConfigCatSource};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     let config = ConfigBuilder::<AsyncState>::default()
+///         .add_async_source(
+///             ConfigCatSource::new(provider)
+///                 .flag("maxConnections")
+///                 .flag_as("paymentsTimeoutSecs", "payments.timeout"),
+///         )
+///         .build()
+///         .await
+///         .unwrap();
+///     let timeout: u64 = config.get("payments.timeout").unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ConfigCatSource {
+    provider: ConfigCatProvider,
+    evaluation_context: EvaluationContext,
+    flags: Vec<(String, String)>,
+}
+
+impl ConfigCatSource {
+    /// Creates a new [`ConfigCatSource`] that evaluates the flags with the given provider.
+    pub fn new(provider: ConfigCatProvider) -> Self {
+        Self {
+            provider,
+            evaluation_context: EvaluationContext::default(),
+            flags: Vec::new(),
+        }
+    }
+
+    /// Sets the evaluation context used to evaluate the flags.
+    pub fn context(mut self, evaluation_context: EvaluationContext) -> Self {
+        self.evaluation_context = evaluation_context;
+        self
+    }
+
+    /// Exposes a flag under its own key.
+    pub fn flag(self, flag_key: &str) -> Self {
+        self.flag_as(flag_key, flag_key)
+    }
+
+    /// Exposes a flag under the given configuration key.
+    pub fn flag_as(mut self, flag_key: &str, config_key: &str) -> Self {
+        self.flags
+            .push((flag_key.to_owned(), config_key.to_owned()));
+        self
+    }
+}
+
+impl Debug for ConfigCatSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigCatSource")
+            .field("flags", &self.flags)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl AsyncSource for ConfigCatSource {
+    async fn collect(&self) -> Result<Map<String, ConfigValue>, ConfigError> {
+        let mut all = self
+            .provider
+            .try_resolve_all(&self.evaluation_context)
+            .await
+            .map_err(|err| {
+                ConfigError::Message(format!(
+                    "Failed to evaluate the ConfigCat flags. ({})",
+                    err.message.unwrap_or_default()
+                ))
+            })?;
+        let origin = ORIGIN.to_owned();
+        let to_config = |value: Value| {
+            let kind = match value {
+                Value::Bool(val) => ValueKind::from(val),
+                Value::Int(val) => ValueKind::from(val),
+                Value::Float(val) => ValueKind::from(val),
+                Value::String(val) => ValueKind::from(val),
+                _ => return None,
+            };
+            Some(ConfigValue::new(Some(&origin), kind))
+        };
+        if self.flags.is_empty() {
+            return Ok(all
+                .into_iter()
+                .filter_map(|(key, details)| Some((key, to_config(details.value)?)))
+                .collect());
+        }
+        Ok(self
+            .flags
+            .iter()
+            .filter_map(|(flag_key, config_key)| {
+                let details = all.remove(flag_key)?;
+                Some((config_key.clone(), to_config(details.value)?))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,71 @@
+use configcat::{Condition, EvaluationDetails, PercentageOption, TargetingRule};
+use std::fmt::{Display, Write};
+
+/// Produces a human readable trace of a single flag evaluation.
+pub(crate) fn trace<T: Display>(details: &EvaluationDetails<T>) -> String {
+    let mut out = format!("Evaluation trace of '{}'", details.key);
+    match details.user.as_ref() {
+        Some(user) => _ = write!(out, " for User {user}"),
+        None => out.push_str(" without a User"),
+    }
+    if let Some(rule) = details.matched_targeting_rule.as_ref() {
+        _ = write!(out, "\n  Matched targeting rule: {}", describe_rule(rule));
+    }
+    if let Some(option) = details.matched_percentage_option.as_ref() {
+        _ = write!(
+            out,
+            "\n  Matched percentage option: {}",
+            describe_option(option)
+        );
+    }
+    if details.matched_targeting_rule.is_none() && details.matched_percentage_option.is_none() {
+        out.push_str(
+            "\n  No targeting rule or percentage option matched, served the default value",
+        );
+    }
+    match details.error.as_ref() {
+        Some(err) => _ = write!(out, "\n  Error: {err}"),
+        None => _ = write!(out, "\n  Result: '{}'", details.value),
+    }
+    if let Some(variation_id) = details.variation_id.as_ref() {
+        _ = write!(out, " (variation '{variation_id}')");
+    }
+    if let Some(fetch_time) = details.fetch_time.as_ref() {
+        _ = write!(out, "\n  Config fetched at: {fetch_time}");
+    }
+    out
+}
+
+pub(crate) fn describe_rule(rule: &TargetingRule) -> String {
+    let conditions = rule
+        .conditions
+        .iter()
+        .flatten()
+        .map(describe_condition)
+        .collect::<Vec<String>>()
+        .join(" AND ");
+    let mut out = format!("IF {conditions}");
+    if let Some(served) = rule.served_value.as_ref() {
+        _ = write!(out, " THEN '{}'", served.value);
+    } else if rule.percentage_options.is_some() {
+        out.push_str(" THEN % options");
+    }
+    out
+}
+
+fn describe_condition(condition: &Condition) -> String {
+    if let Some(cond) = condition.user_condition.as_ref() {
+        return cond.to_string();
+    }
+    if let Some(cond) = condition.segment_condition.as_ref() {
+        return cond.to_string();
+    }
+    if let Some(cond) = condition.prerequisite_flag_condition.as_ref() {
+        return cond.to_string();
+    }
+    "<invalid condition>".to_owned()
+}
+
+fn describe_option(option: &PercentageOption) -> String {
+    format!("{}% '{}'", option.percentage, option.served_value)
+}
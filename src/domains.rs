@@ -0,0 +1,53 @@
+use crate::builder::ConfigCatProviderBuilder;
+use crate::provider::ConfigCatProvider;
+use configcat::ClientError;
+use open_feature::OpenFeature;
+use std::collections::HashMap;
+
+impl ConfigCatProvider {
+    /// Builds a provider for each OpenFeature domain, and registers them as named providers
+    /// on the OpenFeature API singleton.
+    ///
+    /// Returns the registered providers by domain, so they can be refreshed or inspected
+    /// later. Nothing is registered when any of the builders fails.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if any of the providers can't be built.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::OpenFeature;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let providers = ConfigCatProvider::register_domains([
+    ///         ("payments", ConfigCatProvider::builder("payments-sdk-key")),
+    ///         ("search", ConfigCatProvider::builder("search-sdk-key").key_prefix("search_")),
+    ///     ])
+    ///     .await
+    ///     .unwrap();
+    ///
+    ///     let client = OpenFeature::singleton().await.create_named_client("payments");
+    /// }
+    /// ```
+    pub async fn register_domains<I, D>(
+        domains: I,
+    ) -> Result<HashMap<String, ConfigCatProvider>, ClientError>
+    where
+        I: IntoIterator<Item = (D, ConfigCatProviderBuilder)>,
+        D: Into<String>,
+    {
+        let providers = domains
+            .into_iter()
+            .map(|(domain, builder)| Ok((domain.into(), builder.build()?)))
+            .collect::<Result<HashMap<_, _>, ClientError>>()?;
+        let mut api = OpenFeature::singleton_mut().await;
+        for (domain, provider) in &providers {
+            api.set_named_provider(domain, provider.clone()).await;
+        }
+        Ok(providers)
+    }
+}
@@ -0,0 +1,245 @@
+use crate::cache::{cache_key, fetch_time};
+use chrono::Utc;
+use configcat::{ClientError, ConfigCache, ErrorKind};
+use log::{error, warn};
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use sha1::{Digest, Sha1};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The base URL of the ConfigCat CDN used with [`configcat::DataGovernance::Global`].
+pub(crate) const GLOBAL_CDN_URL: &str = "https://cdn-global.configcat.com";
+
+/// The base URL of the ConfigCat CDN used with [`configcat::DataGovernance::EU`].
+pub(crate) const EU_CDN_URL: &str = "https://cdn-eu.configcat.com";
+
+/// The prefix of the SDK keys of the ConfigCat Proxy, which are never redirected.
+const PROXY_SDK_KEY_PREFIX: &str = "configcat-proxy/";
+
+/// The maximum number of requests of a download, following the redirects of the config JSON.
+const MAX_REQUESTS: usize = 3;
+
+/// The redirect mode of the config JSON preferences (`p.r`).
+const REDIRECT_NO: u64 = 0;
+const REDIRECT_SHOULD: u64 = 1;
+const REDIRECT_FORCE: u64 = 2;
+
+/// Downloads the config JSON with an HTTP client configured by the application (e.g. with a
+/// client certificate for mutual TLS), instead of the one created by the ConfigCat SDK.
+///
+/// The underlying ConfigCat SDK client is kept offline, and picks up the downloaded config
+/// JSON from its cache, which is read on each evaluation. Like the SDK, it follows the data
+/// governance redirects of the config JSON preferences (`p.u` and `p.r`), unless a custom base
+/// URL is set and the redirect isn't forced.
+pub(crate) struct Downloader {
+    http: reqwest::Client,
+    base_url: Mutex<String>,
+    is_custom_url: bool,
+    sdk_key: String,
+    key: String,
+    cache: Arc<dyn ConfigCache>,
+    offline: bool,
+}
+
+/// A downloaded config JSON.
+struct Download {
+    etag: String,
+    body: String,
+    /// The base URL and redirect mode of the config JSON preferences.
+    preferences: Option<(Option<String>, u64)>,
+}
+
+impl Downloader {
+    /// Creates a downloader fetching from the custom base URL when given, otherwise from the
+    /// given ConfigCat CDN URL.
+    pub(crate) fn new(
+        http: reqwest::Client,
+        base_url: Option<&str>,
+        cdn_url: &str,
+        sdk_key: &str,
+        cache: Arc<dyn ConfigCache>,
+        offline: bool,
+    ) -> Self {
+        Self {
+            http,
+            base_url: Mutex::new(base_url.unwrap_or(cdn_url).trim_end_matches('/').to_owned()),
+            is_custom_url: base_url.is_some(),
+            sdk_key: sdk_key.to_owned(),
+            key: cache_key(sdk_key),
+            cache,
+            offline,
+        }
+    }
+
+    /// Returns whether the provider was configured to be offline, so no downloads happen.
+    pub(crate) fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Downloads the latest config JSON, and writes it into the cache of the SDK client.
+    pub(crate) async fn fetch(&self) -> Result<(), ClientError> {
+        if self.offline {
+            return Err(ClientError {
+                kind: ErrorKind::OfflineClient,
+                message: "Client is in offline mode, it cannot initiate HTTP calls.".to_owned(),
+            });
+        }
+        let cached = self.cache.read(self.key.as_str());
+        for _ in 0..MAX_REQUESTS {
+            let base_url = self
+                .base_url
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+            let Some(download) = self.download(base_url.as_str(), cached.as_deref()).await? else {
+                return Ok(());
+            };
+            let (preferred_url, redirect) = download.preferences.unwrap_or((None, REDIRECT_NO));
+            let served = preferred_url.is_none()
+                || preferred_url.as_deref() == Some(base_url.as_str())
+                || (self.is_custom_url
+                    && (self.sdk_key.starts_with(PROXY_SDK_KEY_PREFIX)
+                        || redirect != REDIRECT_FORCE));
+            if let (false, Some(url)) = (served, preferred_url) {
+                *self.base_url.lock().unwrap_or_else(PoisonError::into_inner) = url;
+            }
+            if served || redirect == REDIRECT_NO {
+                self.write(download.etag.as_str(), download.body.as_str());
+                return Ok(());
+            }
+            if redirect == REDIRECT_SHOULD {
+                warn!("The `.data_governance()` parameter specified at the client initialization is not in sync with the preferences on the ConfigCat Dashboard. Read more: https://configcat.com/docs/advanced/data-governance");
+            }
+        }
+        Err(failure(
+            ErrorKind::RedirectLoop,
+            "Redirection loop encountered while trying to fetch config JSON. Please contact us at https://configcat.com/support".to_owned(),
+        ))
+    }
+
+    /// Downloads the config JSON from the given base URL. Returns `None` when the cached
+    /// config JSON is up to date, after refreshing its fetch time.
+    async fn download(
+        &self,
+        base_url: &str,
+        cached: Option<&str>,
+    ) -> Result<Option<Download>, ClientError> {
+        let url = format!(
+            "{base_url}/configuration-files/{}/config_v6.json",
+            self.sdk_key
+        );
+        let mut request = self.http.get(url);
+        if let Some(etag) = cached.and_then(etag) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await.map_err(|err| {
+            if err.is_timeout() {
+                failure(
+                    ErrorKind::HttpRequestTimeout,
+                    "Request timed out while trying to fetch config JSON.".to_owned(),
+                )
+            } else {
+                failure(
+                    ErrorKind::HttpRequestFailure,
+                    format!("Unexpected error occurred while trying to fetch config JSON. Make sure the configured HTTP client can reach the ConfigCat CDN servers (or your proxy server). {err}"),
+                )
+            }
+        })?;
+        match response.status().as_u16() {
+            200 => {
+                let etag = response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(str::to_owned);
+                let body = response.text().await.map_err(|err| {
+                    failure(
+                        ErrorKind::InvalidHttpResponseContent,
+                        format!("Fetching config JSON was successful but the HTTP response content was invalid. {err}"),
+                    )
+                })?;
+                let config = serde_json::from_str::<serde_json::Map<_, _>>(body.as_str())
+                    .map_err(|err| {
+                        failure(
+                            ErrorKind::InvalidHttpResponseContent,
+                            format!("Fetching config JSON was successful but the HTTP response content was invalid. {err}"),
+                        )
+                    })?;
+                let preferences = config.get("p").and_then(serde_json::Value::as_object).map(
+                    |preferences| {
+                        let url = preferences
+                            .get("u")
+                            .and_then(serde_json::Value::as_str)
+                            .map(str::to_owned);
+                        let redirect = preferences
+                            .get("r")
+                            .and_then(serde_json::Value::as_u64)
+                            .unwrap_or(REDIRECT_NO);
+                        (url, redirect)
+                    },
+                );
+                // The SDK client tells the config JSON versions apart by their ETag.
+                let etag = etag
+                    .filter(|etag| !etag.is_empty())
+                    .unwrap_or_else(|| format!("{:x}", Sha1::digest(body.as_bytes())));
+                Ok(Some(Download {
+                    etag,
+                    body,
+                    preferences,
+                }))
+            }
+            304 => {
+                let Some((etag, config_json)) = cached.and_then(split_entry) else {
+                    return Err(failure(
+                        ErrorKind::InvalidHttpResponseWhenLocalCacheIsEmpty,
+                        "Unexpected HTTP response was received when no config JSON is cached locally: 304 Not Modified".to_owned(),
+                    ));
+                };
+                self.write(etag, config_json);
+                Ok(None)
+            }
+            code @ (403 | 404) => Err(failure(
+                ErrorKind::InvalidSdkKey,
+                format!("Your SDK Key seems to be wrong. You can find the valid SDK Key at https://app.configcat.com/sdkkey. Status code: {code}"),
+            )),
+            code => Err(failure(
+                ErrorKind::UnexpectedHttpResponse,
+                format!("Unexpected HTTP response was received while trying to fetch config JSON. Status code: {code}"),
+            )),
+        }
+    }
+
+    fn write(&self, etag: &str, config_json: &str) {
+        let value = format!("{}\n{etag}\n{config_json}", Utc::now().timestamp_millis());
+        self.cache.write(self.key.as_str(), value.as_str());
+    }
+}
+
+/// Forwards the calls of the SDK client to the cache shared with the [`Downloader`].
+pub(crate) struct SharedCache(pub(crate) Arc<dyn ConfigCache>);
+
+impl ConfigCache for SharedCache {
+    fn read(&self, key: &str) -> Option<String> {
+        self.0.read(key)
+    }
+
+    fn write(&self, key: &str, value: &str) {
+        self.0.write(key, value);
+    }
+}
+
+fn failure(kind: ErrorKind, message: String) -> ClientError {
+    error!("{message}");
+    ClientError { kind, message }
+}
+
+fn split_entry(cache_str: &str) -> Option<(&str, &str)> {
+    fetch_time(cache_str)?;
+    let mut parts = cache_str.splitn(3, '\n').skip(1);
+    Some((parts.next()?, parts.next()?))
+}
+
+fn etag(cache_str: &str) -> Option<&str> {
+    split_entry(cache_str)
+        .map(|(etag, _)| etag)
+        .filter(|etag| !etag.is_empty())
+}
@@ -0,0 +1,293 @@
+use crate::runtime;
+use crate::sink::{EvaluationEvent, EvaluationSink};
+use async_trait::async_trait;
+use log::warn;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+const DEFAULT_QUEUE_CAPACITY: usize = 10_000;
+const DEFAULT_MAX_BATCH_SIZE: usize = 500;
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(1);
+const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error returned by a [`Transport`] when a batch couldn't be delivered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportError {
+    /// The error message.
+    pub message: String,
+}
+
+impl ExportError {
+    /// Creates a new [`ExportError`] with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message.as_str())
+    }
+}
+
+impl Error for ExportError {}
+
+/// Delivers batches of [`EvaluationEvent`]s collected by a [`BatchExporter`].
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Sends a batch of events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ExportError`] when the batch couldn't be delivered. The batch is dropped in that case.
+    async fn send(&self, batch: &[EvaluationEvent]) -> Result<(), ExportError>;
+}
+
+/// The default [`Transport`] that sends batches as JSON lines in the body of an HTTP POST request.
+pub struct HttpTransport {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+    http_client: reqwest::Client,
+}
+
+impl HttpTransport {
+    /// Creates a new [`HttpTransport`] that posts batches to the given URL.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_owned(),
+            headers: Vec::new(),
+            timeout: DEFAULT_HTTP_TIMEOUT,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Sets the timeout of the requests, after which the batch is dropped, so an unresponsive
+    /// collector doesn't block the export.
+    ///
+    /// Default is 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Adds an HTTP header sent with each request (e.g. for authorization).
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send(&self, batch: &[EvaluationEvent]) -> Result<(), ExportError> {
+        let mut body = String::new();
+        for event in batch {
+            match serde_json::to_string(event) {
+                Ok(line) => {
+                    body.push_str(line.as_str());
+                    body.push('\n');
+                }
+                Err(err) => return Err(ExportError::new(err.to_string())),
+            }
+        }
+        let mut request = self
+            .http_client
+            .post(self.url.as_str())
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .timeout(self.timeout)
+            .body(body);
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(ExportError::new(format!(
+                "Unexpected HTTP response status: {}",
+                response.status()
+            ))),
+            Err(err) => Err(ExportError::new(err.to_string())),
+        }
+    }
+}
+
+enum Command {
+    Event(Box<EvaluationEvent>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// An [`EvaluationSink`] that buffers evaluation events in a bounded queue and exports
+/// them in batches through a [`Transport`] on a background task.
+///
+/// A batch is sent when it reaches the maximum batch size, when the flush interval elapses,
+/// or when [`BatchExporter::flush`] is called. When the queue is full, new events are dropped
+/// instead of slowing down flag evaluations; the number of dropped events is available via
+/// [`BatchExporter::dropped_events`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{BatchExporter, ConfigCatProvider};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let exporter = BatchExporter::http("https://collector.example.com/exposures");
+///
+///     let provider = ConfigCatProvider::builder("sdk-key")
+///         .sink(exporter.clone())
+///         .build()
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BatchExporter {
+    sender: mpsc::Sender<Command>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BatchExporter {
+    /// Creates a new [`BatchExporterBuilder`] used to build a [`BatchExporter`] that delivers batches through the given [`Transport`].
+    pub fn builder(transport: impl Transport + 'static) -> BatchExporterBuilder {
+        BatchExporterBuilder {
+            transport: Box::new(transport),
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+        }
+    }
+
+    /// Creates a new [`BatchExporter`] with default options that posts JSON lines to the given URL.
+    ///
+    /// It must be called within a Tokio runtime as it spawns the background export task.
+    pub fn http(url: &str) -> Self {
+        Self::builder(HttpTransport::new(url)).build()
+    }
+
+    /// Exports the currently buffered events and waits until the export is finished.
+    pub async fn flush(&self) {
+        let (tx, rx) = oneshot::channel();
+        if self.sender.send(Command::Flush(tx)).await.is_ok() {
+            _ = rx.await;
+        }
+    }
+
+    /// Returns the number of events dropped because the queue was full.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl EvaluationSink for BatchExporter {
+    fn record(&self, event: &EvaluationEvent) {
+        if self
+            .sender
+            .try_send(Command::Event(Box::new(event.clone())))
+            .is_err()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Builder to create a [`BatchExporter`].
+pub struct BatchExporterBuilder {
+    transport: Box<dyn Transport>,
+    queue_capacity: usize,
+    max_batch_size: usize,
+    flush_interval: Duration,
+}
+
+impl BatchExporterBuilder {
+    /// Sets the maximum number of events waiting to be exported.
+    ///
+    /// Default is 10000.
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Sets the maximum number of events sent in one batch.
+    ///
+    /// Default is 500.
+    pub fn max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = size.max(1);
+        self
+    }
+
+    /// Sets how often the buffered events are exported. Intervals shorter than 1 millisecond
+    /// are rounded up to 1 millisecond.
+    ///
+    /// Default is 10 seconds.
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval.max(MIN_FLUSH_INTERVAL);
+        self
+    }
+
+    /// Creates a [`BatchExporter`] from the configuration made on the builder.
+    ///
+    /// It must be called within a Tokio runtime as it spawns the background export task.
+    pub fn build(self) -> BatchExporter {
+        let (sender, receiver) = mpsc::channel(self.queue_capacity);
+        runtime::spawn(run_export(
+            receiver,
+            self.transport,
+            self.max_batch_size,
+            self.flush_interval,
+        ));
+        BatchExporter {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+async fn run_export(
+    mut receiver: mpsc::Receiver<Command>,
+    transport: Box<dyn Transport>,
+    max_batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut batch = Vec::with_capacity(max_batch_size);
+    let mut interval = tokio::time::interval(flush_interval);
+    loop {
+        tokio::select! {
+            command = receiver.recv() => match command {
+                Some(Command::Event(event)) => {
+                    batch.push(*event);
+                    if batch.len() >= max_batch_size {
+                        export(transport.as_ref(), &mut batch).await;
+                    }
+                }
+                Some(Command::Flush(done)) => {
+                    export(transport.as_ref(), &mut batch).await;
+                    _ = done.send(());
+                }
+                None => {
+                    export(transport.as_ref(), &mut batch).await;
+                    break;
+                }
+            },
+            _ = interval.tick() => export(transport.as_ref(), &mut batch).await,
+        }
+    }
+}
+
+async fn export(transport: &dyn Transport, batch: &mut Vec<EvaluationEvent>) {
+    if batch.is_empty() {
+        return;
+    }
+    if let Err(err) = transport.send(batch).await {
+        warn!(
+            "Failed to export {} evaluation events. ({err})",
+            batch.len()
+        );
+    }
+    batch.clear();
+}
@@ -0,0 +1,53 @@
+use crate::flags::{header_context, Flags, USER_ID_HEADER};
+use crate::provider::ConfigCatProvider;
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::request::Parts;
+use open_feature::EvaluationContext;
+use std::convert::Infallible;
+
+/// Extracts [`Flags`] in axum handlers.
+///
+/// The provider is taken from the router state via [`FromRef`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use axum::routing::get;
+/// use axum::Router;
+/// use configcat_openfeature_provider::{ConfigCatProvider, Flags};
+///
+/// async fn handler(flags: Flags) -> &'static str {
+///     if flags.bool("isAwesomeFeatureEnabled", false).await {
+///         "awesome"
+///     } else {
+///         "regular"
+///     }
+/// }
+///
+/// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+/// let app: Router = Router::new().route("/", get(handler)).with_state(provider);
+/// ```
+impl<S> FromRequestParts<S> for Flags
+where
+    ConfigCatProvider: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let evaluation_context = if let Some(ctx) = parts.extensions.get::<EvaluationContext>() {
+            ctx.clone()
+        } else {
+            header_context(
+                parts
+                    .headers
+                    .get(USER_ID_HEADER)
+                    .and_then(|value| value.to_str().ok()),
+            )
+        };
+        Ok(Self::new(
+            ConfigCatProvider::from_ref(state),
+            evaluation_context,
+        ))
+    }
+}
@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use log::warn;
+use open_feature::provider::{FeatureProvider, ProviderMetadata, ResolutionDetails};
+use open_feature::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult, StructValue,
+};
+
+type FallbackFn = dyn Fn(&str, &EvaluationError) + Send + Sync;
+
+/// An OpenFeature provider that evaluates with a primary provider, and falls back to a
+/// secondary provider when the primary one can't serve the evaluation.
+///
+/// The fallback happens when the primary provider fails with
+/// [`EvaluationErrorCode::ProviderNotReady`], [`EvaluationErrorCode::General`] or
+/// [`EvaluationErrorCode::ParseError`] (which [`ConfigCatProvider`](crate::ConfigCatProvider)
+/// reports when the config JSON couldn't be downloaded yet). Other errors (like a missing flag
+/// or a type mismatch) are returned as is. Each fallback is logged as a warning, and reported to the
+/// callbacks added with [`FallbackProvider::on_fallback`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat::FileDataSource;
+/// use configcat::OverrideBehavior::LocalOnly;
+/// use configcat_openfeature_provider::{ConfigCatProvider, FallbackProvider};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let primary = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///     let secondary = ConfigCatProvider::builder("local")
+///         .overrides(Box::new(FileDataSource::new("flags.json").unwrap()), LocalOnly)
+///         .build()
+///         .unwrap();
+///
+///     let provider = FallbackProvider::new(primary, secondary).on_fallback(|flag_key, err| {
+///         println!("'{flag_key}' was evaluated with the fallback provider: {err:?}");
+///     });
+///
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(provider).await;
+/// }
+/// ```
+pub struct FallbackProvider<P, S> {
+    metadata: ProviderMetadata,
+    primary: P,
+    secondary: S,
+    on_fallback: Vec<Box<FallbackFn>>,
+}
+
+impl<P: FeatureProvider, S: FeatureProvider> FallbackProvider<P, S> {
+    /// Creates a new [`FallbackProvider`] with the given primary and secondary providers.
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            metadata: ProviderMetadata::new(format!(
+                "FallbackProvider({}, {})",
+                primary.metadata().name,
+                secondary.metadata().name
+            )),
+            primary,
+            secondary,
+            on_fallback: Vec::new(),
+        }
+    }
+
+    /// Adds a callback invoked with the flag key and the primary provider's error each time an
+    /// evaluation falls back to the secondary provider.
+    pub fn on_fallback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, &EvaluationError) + Send + Sync + 'static,
+    {
+        self.on_fallback.push(Box::new(callback));
+        self
+    }
+
+    fn should_fall_back(&self, flag_key: &str, err: &EvaluationError) -> bool {
+        if !matches!(
+            err.code,
+            EvaluationErrorCode::ProviderNotReady
+                | EvaluationErrorCode::General(_)
+                | EvaluationErrorCode::ParseError
+        ) {
+            return false;
+        }
+        warn!(
+            "Evaluating '{flag_key}' with the fallback provider, as the primary provider failed. ({})",
+            err.message.as_deref().unwrap_or(err.code.to_string().as_str())
+        );
+        for callback in &self.on_fallback {
+            callback(flag_key, err);
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider, S: FeatureProvider> FeatureProvider for FallbackProvider<P, S> {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        self.primary.initialize(context).await;
+        self.secondary.initialize(context).await;
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        match self
+            .primary
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+        {
+            Err(err) if self.should_fall_back(flag_key, &err) => {
+                self.secondary
+                    .resolve_bool_value(flag_key, evaluation_context)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        match self
+            .primary
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+        {
+            Err(err) if self.should_fall_back(flag_key, &err) => {
+                self.secondary
+                    .resolve_int_value(flag_key, evaluation_context)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        match self
+            .primary
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+        {
+            Err(err) if self.should_fall_back(flag_key, &err) => {
+                self.secondary
+                    .resolve_float_value(flag_key, evaluation_context)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        match self
+            .primary
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+        {
+            Err(err) if self.should_fall_back(flag_key, &err) => {
+                self.secondary
+                    .resolve_string_value(flag_key, evaluation_context)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        match self
+            .primary
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+        {
+            Err(err) if self.should_fall_back(flag_key, &err) => {
+                self.secondary
+                    .resolve_struct_value(flag_key, evaluation_context)
+                    .await
+            }
+            result => result,
+        }
+    }
+}
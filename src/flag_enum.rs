@@ -0,0 +1,142 @@
+use crate::provider::ConfigCatProvider;
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult};
+
+/// An enum backed by a text setting, where each variant maps to one of the setting's values.
+///
+/// It can be derived for enums with unit variants with `#[derive(FlagEnum)]` when the
+/// `derive` feature is enabled.
+///
+/// # Examples
+///
+/// ```ignore
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagEnum};
+/// use open_feature::EvaluationContext;
+///
+/// #[derive(FlagEnum)]
+/// #[flag(rename_all = "lowercase")]
+/// enum Theme {
+///     Light,
+///     Dark,
+///     #[flag(rename = "high-contrast")]
+///     HighContrast,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     provider.verify_enum::<Theme>("theme").await.unwrap();
+///     let theme: Theme = provider
+///         .resolve_enum("theme", &EvaluationContext::default())
+///         .await
+///         .unwrap()
+///         .value;
+/// }
+/// ```
+pub trait FlagEnum: Sized {
+    /// The setting values mapped to the variants.
+    const VARIANTS: &'static [&'static str];
+
+    /// Returns the variant mapped to the given setting value.
+    fn from_flag_value(value: &str) -> Option<Self>;
+
+    /// Returns the setting value mapped to the variant.
+    fn flag_value(&self) -> &'static str;
+}
+
+impl ConfigCatProvider {
+    /// Resolves a text setting as a [`FlagEnum`] for the given evaluation context.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors of the text setting's evaluation, this method fails with
+    /// [`EvaluationErrorCode::TypeMismatch`] when the served value doesn't map to a variant.
+    pub async fn resolve_enum<E: FlagEnum>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<E>> {
+        let details = self
+            .resolve_string_on(None, flag_key, evaluation_context)
+            .await?;
+        let value = E::from_flag_value(&details.value).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::TypeMismatch)
+                .message(format!(
+                    "The value '{}' of '{flag_key}' doesn't match any of the allowed values: {}.",
+                    details.value,
+                    E::VARIANTS.join(", ")
+                ))
+                .build()
+        })?;
+        Ok(ResolutionDetails {
+            value,
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        })
+    }
+
+    /// Verifies that all the values the text setting can serve (its default value, and the
+    /// values of its targeting rules and percentage options) map to a variant of a
+    /// [`FlagEnum`], so a mismatch between the dashboard and the code is detected at startup
+    /// instead of at evaluation.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::TypeMismatch`] listing the unmapped
+    /// values, with [`EvaluationErrorCode::FlagNotFound`] when the setting doesn't exist, and
+    /// with [`EvaluationErrorCode::ProviderNotReady`] when there's no downloaded config JSON
+    /// to verify (e.g. when the provider uses local-only flag overrides).
+    pub async fn verify_enum<E: FlagEnum>(&self, flag_key: &str) -> EvaluationResult<()> {
+        let settings = self.settings().await?;
+        let Some(setting) = settings.get(self.resolve_key(flag_key).as_ref()) else {
+            return Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!("The setting '{flag_key}' doesn't exist."))
+                .build());
+        };
+        let mut unmapped = Vec::new();
+        collect_text_values(setting, &mut |value| {
+            if E::from_flag_value(value).is_none() && !unmapped.contains(&value) {
+                unmapped.push(value);
+            }
+        });
+        if unmapped.is_empty() {
+            return Ok(());
+        }
+        Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::TypeMismatch)
+            .message(format!(
+                "The values '{}' of '{flag_key}' don't match any of the allowed values: {}.",
+                unmapped.join("', '"),
+                E::VARIANTS.join(", ")
+            ))
+            .build())
+    }
+}
+
+/// Collects the text values a setting can serve, found in the `{"v": {"s": "..."}}` objects
+/// of the setting, its targeting rules and percentage options.
+fn collect_text_values<'a>(value: &'a serde_json::Value, collect: &mut impl FnMut(&'a str)) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, child) in object {
+                if key == "v" {
+                    if let Some(text) = child.get("s").and_then(serde_json::Value::as_str) {
+                        collect(text);
+                        continue;
+                    }
+                }
+                collect_text_values(child, collect);
+            }
+        }
+        serde_json::Value::Array(array) => {
+            for child in array {
+                collect_text_values(child, collect);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,93 @@
+use crate::provider::ConfigCatProvider;
+use crate::value::FlagValue;
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationResult};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// A feature flag key along with the type of its value.
+///
+/// Resolving a flag through a [`FlagKey`] with [`ConfigCatProvider::get`] or
+/// [`ConfigCatProvider::resolve`] always requests the declared type, so the type can't be
+/// accidentally mixed up at the call sites.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagKey};
+/// use open_feature::EvaluationContext;
+///
+/// const NEW_CHECKOUT: FlagKey<bool> = FlagKey::new("isNewCheckoutEnabled");
+/// const MAX_ITEMS: FlagKey<i64> = FlagKey::new("maxCartItems");
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///
+///     if provider.get(NEW_CHECKOUT, &ctx, false).await {
+///         let max_items = provider.get(MAX_ITEMS, &ctx, 10).await;
+///     }
+/// }
+/// ```
+pub struct FlagKey<T> {
+    key: &'static str,
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<T> FlagKey<T> {
+    /// Creates a new [`FlagKey`] for the given flag key.
+    pub const fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            value_type: PhantomData,
+        }
+    }
+
+    /// Returns the flag key.
+    pub const fn key(&self) -> &'static str {
+        self.key
+    }
+}
+
+impl<T> Clone for FlagKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FlagKey<T> {}
+
+impl<T> Debug for FlagKey<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FlagKey").field(&self.key).finish()
+    }
+}
+
+impl ConfigCatProvider {
+    /// Resolves the flag identified by a [`FlagKey`] for the given evaluation context.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the evaluation.
+    pub async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: FlagKey<T>,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        T::resolve(self, flag_key.key, evaluation_context).await
+    }
+
+    /// Returns the value of the flag identified by a [`FlagKey`] for the given evaluation
+    /// context, or `default` when the evaluation fails.
+    pub async fn get<T: FlagValue>(
+        &self,
+        flag_key: FlagKey<T>,
+        evaluation_context: &EvaluationContext,
+        default: T,
+    ) -> T {
+        self.resolve(flag_key, evaluation_context)
+            .await
+            .map_or(default, |details| details.value)
+    }
+}
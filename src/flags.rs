@@ -0,0 +1,128 @@
+use crate::bulk::FlagSet;
+use crate::provider::ConfigCatProvider;
+use open_feature::EvaluationContext;
+
+/// The request header used as the targeting key by the web framework extractors of [`Flags`],
+/// when the request has no [`EvaluationContext`] extension.
+pub const USER_ID_HEADER: &str = "x-user-id";
+
+/// Evaluates feature flags for a single request.
+///
+/// It pairs a provider with the evaluation context of the request, so handlers don't need
+/// to pass the context around. With the web framework features enabled, it can be extracted
+/// in request handlers directly: the evaluation context is the [`EvaluationContext`] request
+/// extension (set by [`EvaluationContextLayer`](crate::EvaluationContextLayer) or an
+/// authentication middleware, for example), or when there is none, a context with the value
+/// of the [`USER_ID_HEADER`] header as the targeting key.
+///
+/// The typed evaluation methods return the given default value when the evaluation fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, Flags};
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     let flags = Flags::new(provider, EvaluationContext::default().with_targeting_key("user-id"));
+///     if flags.bool("isAwesomeFeatureEnabled", false).await {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Flags {
+    provider: ConfigCatProvider,
+    evaluation_context: EvaluationContext,
+}
+
+impl Flags {
+    /// Creates a new [`Flags`] that evaluates with the given provider and evaluation context.
+    pub fn new(provider: ConfigCatProvider, evaluation_context: EvaluationContext) -> Self {
+        Self {
+            provider,
+            evaluation_context,
+        }
+    }
+
+    /// Returns the evaluation context of the request.
+    pub fn context(&self) -> &EvaluationContext {
+        &self.evaluation_context
+    }
+
+    /// Evaluates a feature flag.
+    pub async fn bool(&self, flag_key: &str, default: bool) -> bool {
+        self.provider
+            .resolve_bool_on(None, flag_key, &self.evaluation_context)
+            .await
+            .map_or(default, |details| details.value)
+    }
+
+    /// Evaluates a whole number setting.
+    pub async fn int(&self, flag_key: &str, default: i64) -> i64 {
+        self.provider
+            .resolve_int_on(None, flag_key, &self.evaluation_context)
+            .await
+            .map_or(default, |details| details.value)
+    }
+
+    /// Evaluates a decimal number setting.
+    pub async fn float(&self, flag_key: &str, default: f64) -> f64 {
+        self.provider
+            .resolve_float_on(None, flag_key, &self.evaluation_context)
+            .await
+            .map_or(default, |details| details.value)
+    }
+
+    /// Evaluates a text setting.
+    pub async fn string(&self, flag_key: &str, default: &str) -> String {
+        self.provider
+            .resolve_string_on(None, flag_key, &self.evaluation_context)
+            .await
+            .map_or_else(|_| default.to_owned(), |details| details.value)
+    }
+
+    /// Evaluates a [`FlagSet`] with [`ConfigCatProvider::resolve_set`].
+    pub async fn set<F: FlagSet>(&self) -> F {
+        self.provider.resolve_set(&self.evaluation_context).await
+    }
+}
+
+/// Returns the evaluation context for a request without an [`EvaluationContext`] extension.
+#[cfg(any(feature = "axum", feature = "actix", feature = "warp"))]
+pub(crate) fn header_context(user_id: Option<&str>) -> EvaluationContext {
+    user_id.map_or_else(EvaluationContext::default, |user_id| {
+        EvaluationContext::default().with_targeting_key(user_id)
+    })
+}
+
+/// Maps request headers (or gRPC metadata) to evaluation context fields.
+#[cfg(any(feature = "tower", feature = "tonic"))]
+#[derive(Clone, Default)]
+pub(crate) struct ContextMapping {
+    pub(crate) targeting_key: Option<String>,
+    pub(crate) attributes: Vec<(String, String)>,
+}
+
+#[cfg(any(feature = "tower", feature = "tonic"))]
+impl ContextMapping {
+    /// Adds the mapped values returned by `lookup` to the evaluation context.
+    pub(crate) fn apply<'a>(
+        &self,
+        mut ctx: EvaluationContext,
+        lookup: impl Fn(&str) -> Option<&'a str>,
+    ) -> EvaluationContext {
+        if let Some(targeting_key) = self.targeting_key.as_deref().and_then(&lookup) {
+            ctx.targeting_key = Some(targeting_key.to_owned());
+        }
+        for (name, attribute) in &self.attributes {
+            if let Some(value) = lookup(name) {
+                ctx.add_custom_field(attribute.as_str(), value);
+            }
+        }
+        ctx
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+/// The text format of the object values stored in ConfigCat text settings.
+///
+/// It determines how the text of a setting is decoded when it's resolved as an object
+/// with [`open_feature::provider::FeatureProvider::resolve_struct_value`]. The formats
+/// other than JSON are available with the feature of the same name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, StructFormat};
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .struct_format(StructFormat::Auto)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StructFormat {
+    /// JSON.
+    #[default]
+    Json,
+    /// JSON5, a relaxed JSON syntax that allows comments, trailing commas and unquoted keys,
+    /// which are common mistakes in hand-edited values.
+    #[cfg(feature = "json5")]
+    Json5,
+    /// YAML.
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// TOML.
+    #[cfg(feature = "toml")]
+    Toml,
+    /// JSON encoded in Base64, for values that would otherwise need escaping in the dashboard.
+    #[cfg(feature = "base64")]
+    Base64,
+    /// Tries JSON first, then the other enabled formats, and uses the first one the text is
+    /// valid in. With the `base64` feature, texts starting with [`BASE64_PREFIX`] are decoded
+    /// as [`StructFormat::Base64`].
+    Auto,
+}
+
+/// The prefix marking Base64 encoded JSON values for [`StructFormat::Auto`].
+#[cfg(feature = "base64")]
+pub const BASE64_PREFIX: &str = "base64:";
+
+/// The formats tried by [`StructFormat::Auto`] after JSON, in order.
+const AUTO_FORMATS: &[StructFormat] = &[
+    #[cfg(feature = "json5")]
+    StructFormat::Json5,
+    #[cfg(feature = "toml")]
+    StructFormat::Toml,
+    #[cfg(feature = "yaml")]
+    StructFormat::Yaml,
+];
+
+/// The object value formats configured for a provider.
+#[derive(Default)]
+pub(crate) struct StructFormats {
+    pub(crate) default: StructFormat,
+    pub(crate) per_key: HashMap<String, StructFormat>,
+}
+
+impl StructFormats {
+    pub(crate) fn get(&self, flag_key: &str) -> StructFormat {
+        self.per_key.get(flag_key).copied().unwrap_or(self.default)
+    }
+}
+
+/// Decodes the text of an object setting in the given format, or returns the error message.
+pub(crate) fn parse(text: &str, format: StructFormat) -> Result<serde_json::Value, String> {
+    match format {
+        StructFormat::Json => serde_json::from_str(text)
+            .map_err(|err| format!("Failed to parse JSON from evaluated string: {err}")),
+        #[cfg(feature = "json5")]
+        StructFormat::Json5 => json5::from_str(text)
+            .map_err(|err| format!("Failed to parse JSON5 from evaluated string: {err}")),
+        #[cfg(feature = "yaml")]
+        StructFormat::Yaml => serde_yaml_ng::from_str(text)
+            .map_err(|err| format!("Failed to parse YAML from evaluated string: {err}")),
+        #[cfg(feature = "toml")]
+        StructFormat::Toml => toml::from_str(text)
+            .map_err(|err| format!("Failed to parse TOML from evaluated string: {err}")),
+        #[cfg(feature = "base64")]
+        StructFormat::Base64 => {
+            use base64::Engine;
+            let decoded = base64::engine::general_purpose::STANDARD
+                .decode(text.trim())
+                .map_err(|err| format!("Failed to decode Base64 from evaluated string: {err}"))?;
+            let text = String::from_utf8(decoded)
+                .map_err(|err| format!("Failed to decode Base64 from evaluated string: {err}"))?;
+            parse(&text, StructFormat::Json)
+        }
+        StructFormat::Auto => {
+            #[cfg(feature = "base64")]
+            if let Some(encoded) = text.strip_prefix(BASE64_PREFIX) {
+                return parse(encoded, StructFormat::Base64);
+            }
+            let json = parse(text, StructFormat::Json);
+            if json.as_ref().is_ok_and(serde_json::Value::is_object) {
+                return json;
+            }
+            // Report the JSON result when no other format gives an object, as it's the
+            // expected format of most values.
+            AUTO_FORMATS
+                .iter()
+                .filter_map(|format| parse(text, *format).ok())
+                .find(serde_json::Value::is_object)
+                .map_or(json, Ok)
+        }
+    }
+}
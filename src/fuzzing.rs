@@ -0,0 +1,25 @@
+use crate::format::StructFormat;
+use crate::provider;
+use open_feature::{EvaluationContext, EvaluationResult, StructValue};
+
+/// Converts an evaluation context to a ConfigCat User Object, like the evaluations do.
+///
+/// # Errors
+///
+/// Returns the error of the conversion, e.g. for struct fields.
+pub fn to_user(evaluation_context: &EvaluationContext) -> EvaluationResult<()> {
+    provider::to_user(evaluation_context).map(drop)
+}
+
+/// Parses the value of a text setting as an object flag, like the struct evaluations do.
+///
+/// # Errors
+///
+/// Returns the error of the parsing, e.g. for invalid JSON or non-object values.
+pub fn to_struct(value: &str) -> EvaluationResult<StructValue> {
+    let details = configcat::EvaluationDetails {
+        value: value.to_owned(),
+        ..configcat::EvaluationDetails::default()
+    };
+    provider::to_struct_details(&details, StructFormat::Json).map(|details| details.value)
+}
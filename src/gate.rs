@@ -0,0 +1,102 @@
+use crate::provider::ConfigCatProvider;
+use open_feature::EvaluationContext;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+
+/// A future gated by a feature flag.
+///
+/// Created by [`ConfigCatProvider::when_enabled`]. Awaiting it evaluates the flag, then runs
+/// the gated future only if the flag is on, and returns its output wrapped in `Some`.
+/// Use [`Gate::or_else`] to run another future when the flag is off.
+#[must_use = "gates do nothing unless awaited"]
+pub struct Gate<'a, F> {
+    provider: &'a ConfigCatProvider,
+    flag_key: &'a str,
+    evaluation_context: &'a EvaluationContext,
+    enabled: F,
+}
+
+impl<'a, F> Gate<'a, F> {
+    pub(crate) fn new(
+        provider: &'a ConfigCatProvider,
+        flag_key: &'a str,
+        evaluation_context: &'a EvaluationContext,
+        enabled: F,
+    ) -> Self {
+        Self {
+            provider,
+            flag_key,
+            evaluation_context,
+            enabled,
+        }
+    }
+
+    /// Sets the future to run when the flag is off.
+    pub fn or_else<G>(self, disabled: G) -> GateOrElse<'a, F, G> {
+        GateOrElse {
+            gate: self,
+            disabled,
+        }
+    }
+}
+
+async fn is_enabled(
+    provider: &ConfigCatProvider,
+    flag_key: &str,
+    evaluation_context: &EvaluationContext,
+) -> bool {
+    provider
+        .resolve_bool_on(None, flag_key, evaluation_context)
+        .await
+        .is_ok_and(|details| details.value)
+}
+
+impl<'a, F> IntoFuture for Gate<'a, F>
+where
+    F: Future + Send + 'a,
+    F::Output: Send,
+{
+    type Output = Option<F::Output>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            if is_enabled(self.provider, self.flag_key, self.evaluation_context).await {
+                Some(self.enabled.await)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// A future gated by a feature flag, with an alternative for when the flag is off.
+///
+/// Created by [`Gate::or_else`]. Awaiting it evaluates the flag, then runs one of the two
+/// futures and returns its output.
+#[must_use = "gates do nothing unless awaited"]
+pub struct GateOrElse<'a, F, G> {
+    gate: Gate<'a, F>,
+    disabled: G,
+}
+
+impl<'a, F, G> IntoFuture for GateOrElse<'a, F, G>
+where
+    F: Future + Send + 'a,
+    G: Future<Output = F::Output> + Send + 'a,
+    F::Output: Send,
+{
+    type Output = F::Output;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let gate = self.gate;
+            if is_enabled(gate.provider, gate.flag_key, gate.evaluation_context).await {
+                gate.enabled.await
+            } else {
+                self.disabled.await
+            }
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use crate::provider::ConfigCatProvider;
+use async_graphql::{Context, Error, Guard, Result};
+use open_feature::EvaluationContext;
+
+/// An async-graphql [`Guard`] that only allows resolving a field when a feature flag is on.
+///
+/// The provider is taken from the schema (or request) data, and the flag is evaluated with
+/// the [`EvaluationContext`] in the request data, or an empty context when there is none.
+/// When the flag is off, fails to evaluate, or the provider is missing from the data, the
+/// field resolves to an error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagGuard};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     #[graphql(guard = "FlagGuard::new(\"betaFieldEnabled\")")]
+///     async fn beta_field(&self) -> i32 {
+///         42
+///     }
+/// }
+///
+/// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .data(provider)
+///     .finish();
+/// ```
+pub struct FlagGuard {
+    flag_key: String,
+}
+
+impl FlagGuard {
+    /// Creates a new [`FlagGuard`] gated by the given feature flag.
+    pub fn new(flag_key: &str) -> Self {
+        Self {
+            flag_key: flag_key.to_owned(),
+        }
+    }
+}
+
+impl Guard for FlagGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        let Some(provider) = ctx.data_opt::<ConfigCatProvider>() else {
+            return Err(Error::new(
+                "ConfigCatProvider is missing from the GraphQL data",
+            ));
+        };
+        let default_context = EvaluationContext::default();
+        let evaluation_context = ctx
+            .data_opt::<EvaluationContext>()
+            .unwrap_or(&default_context);
+        let enabled = provider
+            .resolve_bool_on(None, self.flag_key.as_str(), evaluation_context)
+            .await
+            .is_ok_and(|details| details.value);
+        if enabled {
+            Ok(())
+        } else {
+            Err(Error::new(format!(
+                "Feature '{}' is not enabled",
+                self.flag_key
+            )))
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use open_feature::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationDetails, EvaluationError, Hook,
+    HookContext, HookHints, Value,
+};
+use std::sync::Arc;
+
+type ComputeFn = dyn Fn() -> Option<EvaluationContextFieldValue> + Send + Sync;
+
+#[derive(Clone)]
+enum Attribute {
+    Static(EvaluationContextFieldValue),
+    Computed(Arc<ComputeFn>),
+}
+
+/// An OpenFeature [`Hook`] that adds configured attributes to every evaluation context.
+///
+/// Attributes can be static (like the application version or the environment name) or
+/// computed at each evaluation (like the region of the current request). Attributes already
+/// present in the evaluation context are never overwritten, so call sites can still
+/// override the enriched values.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, ContextEnrichmentHook};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(ConfigCatProvider::builder("sdk-key").build().unwrap())
+///         .await;
+///     api.add_hook(
+///         ContextEnrichmentHook::new()
+///             .attribute("appVersion", env!("CARGO_PKG_VERSION"))
+///             .attribute("environment", "production")
+///             .computed("region", || std::env::var("REGION").ok().map(Into::into)),
+///     )
+///     .await;
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct ContextEnrichmentHook {
+    attributes: Vec<(String, Attribute)>,
+}
+
+impl ContextEnrichmentHook {
+    /// Creates a new [`ContextEnrichmentHook`] without any attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a static attribute to every evaluation context.
+    pub fn attribute(mut self, key: &str, value: impl Into<EvaluationContextFieldValue>) -> Self {
+        self.attributes
+            .push((key.to_owned(), Attribute::Static(value.into())));
+        self
+    }
+
+    /// Adds an attribute computed by the given function at each evaluation.
+    ///
+    /// The attribute is skipped when the function returns `None`.
+    pub fn computed<F>(mut self, key: &str, compute: F) -> Self
+    where
+        F: Fn() -> Option<EvaluationContextFieldValue> + Send + Sync + 'static,
+    {
+        self.attributes
+            .push((key.to_owned(), Attribute::Computed(Arc::new(compute))));
+        self
+    }
+}
+
+#[async_trait]
+impl Hook for ContextEnrichmentHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _: Option<&'a HookHints>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        let mut enriched = context.evaluation_context.clone();
+        for (key, attribute) in &self.attributes {
+            if enriched.custom_fields.contains_key(key) {
+                continue;
+            }
+            let value = match attribute {
+                Attribute::Static(value) => Some(value.clone()),
+                Attribute::Computed(compute) => compute(),
+            };
+            if let Some(value) = value {
+                enriched.custom_fields.insert(key.clone(), value);
+            }
+        }
+        Ok(Some(enriched))
+    }
+
+    async fn after<'a>(
+        &self,
+        _: &HookContext<'a>,
+        _: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) -> Result<(), EvaluationError> {
+        Ok(())
+    }
+
+    async fn error<'a>(&self, _: &HookContext<'a>, _: &EvaluationError, _: Option<&'a HookHints>) {}
+
+    async fn finally<'a>(
+        &self,
+        _: &HookContext<'a>,
+        _: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) {
+    }
+}
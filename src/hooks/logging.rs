@@ -0,0 +1,156 @@
+use crate::provider::{
+    MATCHED_PERCENTAGE_OPTION_METADATA_KEY, MATCHED_TARGETING_RULE_METADATA_KEY,
+    VARIATION_ID_METADATA_KEY,
+};
+use crate::value::to_json;
+use async_trait::async_trait;
+use log::{log, Level};
+use open_feature::{
+    EvaluationContext, EvaluationDetails, EvaluationError, FlagMetadataValue, Hook, HookContext,
+    HookHints, Value,
+};
+use std::fmt::Write;
+
+/// An OpenFeature [`Hook`] that logs each stage of the flag evaluation lifecycle with
+/// ConfigCat specific details, like the variation ID and the matched targeting rule.
+///
+/// The before and after stages are logged at the configured level (debug by default),
+/// evaluation errors are logged at warning level.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatLoggingHook, ConfigCatProvider};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(ConfigCatProvider::builder("sdk-key").build().unwrap())
+///         .await;
+///     api.add_hook(ConfigCatLoggingHook::new().level(log::Level::Info))
+///         .await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConfigCatLoggingHook {
+    level: Level,
+    include_evaluation_context: bool,
+}
+
+impl ConfigCatLoggingHook {
+    /// Creates a new [`ConfigCatLoggingHook`] that logs at debug level.
+    pub fn new() -> Self {
+        Self {
+            level: Level::Debug,
+            include_evaluation_context: false,
+        }
+    }
+
+    /// Sets the level of the before and after stage log messages.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Indicates whether the evaluation context should be included in the log messages.
+    ///
+    /// Default is `false`, as the context may contain personal data.
+    pub fn include_evaluation_context(mut self, include: bool) -> Self {
+        self.include_evaluation_context = include;
+        self
+    }
+
+    fn context_suffix(&self, context: &HookContext<'_>) -> String {
+        if self.include_evaluation_context {
+            format!(", evaluation context: {:?}", context.evaluation_context)
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl Default for ConfigCatLoggingHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Hook for ConfigCatLoggingHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _: Option<&'a HookHints>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        let default_value = context
+            .default_value
+            .as_ref()
+            .map_or_else(|| "none".to_owned(), |value| to_json(value).to_string());
+        log!(
+            self.level,
+            "Evaluating '{}' with provider '{}', default value: {default_value}{}",
+            context.flag_key,
+            context.provider_metadata.name,
+            self.context_suffix(context)
+        );
+        Ok(None)
+    }
+
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) -> Result<(), EvaluationError> {
+        let mut message = format!(
+            "Evaluated '{}': value: {}",
+            context.flag_key,
+            to_json(&details.value)
+        );
+        if let Some(reason) = details.reason.as_ref() {
+            _ = write!(message, ", reason: {reason}");
+        }
+        let metadata = &details.flag_metadata.values;
+        if let Some(FlagMetadataValue::String(id)) = metadata.get(VARIATION_ID_METADATA_KEY) {
+            _ = write!(message, ", variation ID: '{id}'");
+        }
+        if let Some(FlagMetadataValue::String(rule)) =
+            metadata.get(MATCHED_TARGETING_RULE_METADATA_KEY)
+        {
+            _ = write!(message, ", matched targeting rule: {rule}");
+        }
+        if let Some(FlagMetadataValue::Int(percentage)) =
+            metadata.get(MATCHED_PERCENTAGE_OPTION_METADATA_KEY)
+        {
+            _ = write!(message, ", matched percentage option: {percentage}%");
+        }
+        message.push_str(self.context_suffix(context).as_str());
+        log!(self.level, "{message}");
+        Ok(())
+    }
+
+    async fn error<'a>(
+        &self,
+        context: &HookContext<'a>,
+        error: &EvaluationError,
+        _: Option<&'a HookHints>,
+    ) {
+        log!(
+            Level::Warn,
+            "Failed to evaluate '{}': [{}] {}{}",
+            context.flag_key,
+            error.code,
+            error.message.as_deref().unwrap_or_default(),
+            self.context_suffix(context)
+        );
+    }
+
+    async fn finally<'a>(
+        &self,
+        _: &HookContext<'a>,
+        _: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) {
+    }
+}
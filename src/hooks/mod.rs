@@ -0,0 +1,13 @@
+mod logging;
+pub use logging::ConfigCatLoggingHook;
+
+mod validation;
+pub use validation::ContextValidationHook;
+
+mod enrichment;
+pub use enrichment::ContextEnrichmentHook;
+
+#[cfg(feature = "otel")]
+mod telemetry;
+#[cfg(feature = "otel")]
+pub use telemetry::OtelTelemetryHook;
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use open_feature::{
+    EvaluationContext, EvaluationDetails, EvaluationError, Hook, HookContext, HookHints, Value,
+};
+use opentelemetry::metrics::{Counter, Meter, UpDownCounter};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::KeyValue;
+
+const EVENT_NAME: &str = "feature_flag.evaluation";
+const FLAG_KEY: &str = "feature_flag.key";
+const PROVIDER_NAME: &str = "feature_flag.provider.name";
+const VARIANT: &str = "feature_flag.result.variant";
+const REASON: &str = "feature_flag.result.reason";
+const ERROR_TYPE: &str = "error.type";
+const ERROR_MESSAGE: &str = "error.message";
+
+/// An OpenFeature [`Hook`] that reports flag evaluations to OpenTelemetry.
+///
+/// For each evaluation, it adds a `feature_flag.evaluation` event to the current span, and
+/// records the following metrics (following the OpenFeature semantic conventions):
+/// - `feature_flag.evaluation_requests_total`: the number of evaluation requests.
+/// - `feature_flag.evaluation_success_total`: the number of successful evaluations.
+/// - `feature_flag.evaluation_error_total`: the number of failed evaluations.
+/// - `feature_flag.evaluation_active_count`: the number of in-flight evaluations.
+///
+/// The hook only relies on the resolution details, so it works with any OpenFeature provider.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, OtelTelemetryHook};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(ConfigCatProvider::builder("sdk-key").build().unwrap())
+///         .await;
+///     api.add_hook(OtelTelemetryHook::new()).await;
+/// }
+/// ```
+pub struct OtelTelemetryHook {
+    requests: Counter<u64>,
+    successes: Counter<u64>,
+    errors: Counter<u64>,
+    active: UpDownCounter<i64>,
+    span_events: bool,
+}
+
+impl OtelTelemetryHook {
+    /// Creates a new [`OtelTelemetryHook`] that records metrics with the global meter provider.
+    pub fn new() -> Self {
+        Self::with_meter(&opentelemetry::global::meter(env!("CARGO_PKG_NAME")))
+    }
+
+    /// Creates a new [`OtelTelemetryHook`] that records metrics with the given meter.
+    pub fn with_meter(meter: &Meter) -> Self {
+        Self {
+            requests: meter
+                .u64_counter("feature_flag.evaluation_requests_total")
+                .with_description("The number of feature flag evaluation requests.")
+                .build(),
+            successes: meter
+                .u64_counter("feature_flag.evaluation_success_total")
+                .with_description("The number of successful feature flag evaluations.")
+                .build(),
+            errors: meter
+                .u64_counter("feature_flag.evaluation_error_total")
+                .with_description("The number of failed feature flag evaluations.")
+                .build(),
+            active: meter
+                .i64_up_down_counter("feature_flag.evaluation_active_count")
+                .with_description("The number of in-flight feature flag evaluations.")
+                .build(),
+            span_events: true,
+        }
+    }
+
+    /// Indicates whether evaluations should be added as events to the current span.
+    ///
+    /// Default is `true`.
+    pub fn span_events(mut self, span_events: bool) -> Self {
+        self.span_events = span_events;
+        self
+    }
+
+    fn add_span_event(&self, attributes: Vec<KeyValue>) {
+        if self.span_events {
+            opentelemetry::Context::current()
+                .span()
+                .add_event(EVENT_NAME, attributes);
+        }
+    }
+}
+
+impl Default for OtelTelemetryHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Hook for OtelTelemetryHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _: Option<&'a HookHints>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        let attributes = base_attributes(context);
+        self.requests.add(1, &attributes);
+        self.active.add(1, &attributes);
+        Ok(None)
+    }
+
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) -> Result<(), EvaluationError> {
+        let mut attributes = base_attributes(context);
+        if let Some(variant) = details.variant.as_ref() {
+            attributes.push(KeyValue::new(VARIANT, variant.clone()));
+        }
+        if let Some(reason) = details.reason.as_ref() {
+            attributes.push(KeyValue::new(REASON, reason.to_string().to_lowercase()));
+        }
+        self.successes.add(1, &attributes);
+        self.add_span_event(attributes);
+        Ok(())
+    }
+
+    async fn error<'a>(
+        &self,
+        context: &HookContext<'a>,
+        error: &EvaluationError,
+        _: Option<&'a HookHints>,
+    ) {
+        let mut attributes = base_attributes(context);
+        attributes.push(KeyValue::new(
+            ERROR_TYPE,
+            error.code.to_string().to_lowercase(),
+        ));
+        self.errors.add(1, &attributes);
+        if let Some(message) = error.message.as_ref() {
+            attributes.push(KeyValue::new(ERROR_MESSAGE, message.clone()));
+        }
+        self.add_span_event(attributes);
+    }
+
+    async fn finally<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) {
+        self.active.add(-1, &base_attributes(context));
+    }
+}
+
+fn base_attributes(context: &HookContext<'_>) -> Vec<KeyValue> {
+    vec![
+        KeyValue::new(FLAG_KEY, context.flag_key.to_owned()),
+        KeyValue::new(PROVIDER_NAME, context.provider_metadata.name.clone()),
+    ]
+}
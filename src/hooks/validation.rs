@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use open_feature::{
+    EvaluationContext, EvaluationDetails, EvaluationError, EvaluationErrorCode, Hook, HookContext,
+    HookHints, Value,
+};
+use std::collections::HashSet;
+
+/// An OpenFeature [`Hook`] that validates the evaluation context before the evaluation reaches
+/// the provider.
+///
+/// When the context breaks one of the configured rules, the evaluation fails early with
+/// [`EvaluationErrorCode::InvalidContext`] and a message describing the violation, and the
+/// default value is served.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, ContextValidationHook};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(ConfigCatProvider::builder("sdk-key").build().unwrap())
+///         .await;
+///     api.add_hook(
+///         ContextValidationHook::new()
+///             .require_targeting_key()
+///             .allow_attributes(&["Email", "Country", "plan"]),
+///     )
+///     .await;
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ContextValidationHook {
+    require_targeting_key: bool,
+    allowed_attributes: Option<HashSet<String>>,
+}
+
+impl ContextValidationHook {
+    /// Creates a new [`ContextValidationHook`] without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires a non-empty targeting key in the evaluation context.
+    pub fn require_targeting_key(mut self) -> Self {
+        self.require_targeting_key = true;
+        self
+    }
+
+    /// Allows only the given custom attributes in the evaluation context.
+    ///
+    /// Can be called multiple times; the allowed attributes are accumulated.
+    pub fn allow_attributes(mut self, attributes: &[&str]) -> Self {
+        self.allowed_attributes
+            .get_or_insert_with(HashSet::new)
+            .extend(attributes.iter().map(|attr| (*attr).to_owned()));
+        self
+    }
+
+    fn validate(&self, context: &EvaluationContext) -> Result<(), String> {
+        if self.require_targeting_key && context.targeting_key.as_ref().is_none_or(String::is_empty)
+        {
+            return Err("the targeting key is missing".to_owned());
+        }
+        if let Some(allowed) = self.allowed_attributes.as_ref() {
+            let mut keys: Vec<&String> = context.custom_fields.keys().collect();
+            keys.sort();
+            if let Some(key) = keys.into_iter().find(|key| !allowed.contains(*key)) {
+                return Err(format!("the '{key}' attribute is not allowed"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Hook for ContextValidationHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _: Option<&'a HookHints>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        match self.validate(context.evaluation_context) {
+            Ok(()) => Ok(None),
+            Err(violation) => Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::InvalidContext)
+                .message(format!(
+                    "The evaluation context of '{}' is invalid: {violation}.",
+                    context.flag_key
+                ))
+                .build()),
+        }
+    }
+
+    async fn after<'a>(
+        &self,
+        _: &HookContext<'a>,
+        _: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) -> Result<(), EvaluationError> {
+        Ok(())
+    }
+
+    async fn error<'a>(&self, _: &HookContext<'a>, _: &EvaluationError, _: Option<&'a HookHints>) {}
+
+    async fn finally<'a>(
+        &self,
+        _: &HookContext<'a>,
+        _: &EvaluationDetails<Value>,
+        _: Option<&'a HookHints>,
+    ) {
+    }
+}
@@ -0,0 +1,143 @@
+use crate::tap::ConfigTap;
+use chrono::Utc;
+use configcat::{ClientError, ConfigCache, ErrorKind};
+use log::{info, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A [`ConfigCache`] serving the flag overrides of a local file as the config JSON of an
+/// offline ConfigCat SDK client, reloaded each time the file changes.
+///
+/// The file can be in either format the ConfigCat SDK's `FileDataSource` accepts: a complete
+/// config JSON, or the simplified `{"flags": {"key": value}}` format.
+pub(crate) struct WatchedFileCache {
+    current: Arc<RwLock<String>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl WatchedFileCache {
+    /// Loads the file, and starts watching it. Reloads are reported to the tap right away, so
+    /// the flag watchers get notified without waiting for an evaluation.
+    pub(crate) fn new(path: &Path, tap: Arc<ConfigTap>) -> Result<Self, ClientError> {
+        let initial = load(path).map_err(|message| ClientError {
+            kind: ErrorKind::ConfigJsonNotAvailable,
+            message: format!(
+                "Failed to load the flag overrides from '{}'. ({message})",
+                path.display()
+            ),
+        })?;
+        tap.store(&initial);
+        let current = Arc::new(RwLock::new(initial));
+        let watcher = watch(path, current.clone(), tap).map_err(|err| ClientError {
+            kind: ErrorKind::ConfigJsonNotAvailable,
+            message: format!("Failed to watch '{}'. ({err})", path.display()),
+        })?;
+        Ok(Self {
+            current,
+            _watcher: watcher,
+        })
+    }
+}
+
+impl ConfigCache for WatchedFileCache {
+    fn read(&self, _: &str) -> Option<String> {
+        Some(
+            self.current
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+        )
+    }
+
+    fn write(&self, _: &str, _: &str) {}
+}
+
+/// Watches the directory of the file rather than the file itself, so the changes are picked
+/// up even when an editor replaces the file instead of writing it in place.
+fn watch(
+    path: &Path,
+    current: Arc<RwLock<String>>,
+    tap: Arc<ConfigTap>,
+) -> notify::Result<RecommendedWatcher> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.canonicalize()?,
+        _ => std::env::current_dir()?,
+    };
+    let file: PathBuf = dir.join(path.file_name().unwrap_or_default());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            || !event.paths.contains(&file)
+        {
+            return;
+        }
+        match load(&file) {
+            Ok(cache_str) => {
+                let mut current = current.write().unwrap_or_else(PoisonError::into_inner);
+                if config_json(&current) != config_json(&cache_str) {
+                    info!("Reloaded the flag overrides from '{}'.", file.display());
+                    tap.store(&cache_str);
+                    *current = cache_str;
+                }
+            }
+            Err(message) => warn!(
+                "Failed to reload the flag overrides from '{}', the previous ones are kept. ({message})",
+                file.display()
+            ),
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// Reads the file, and returns it as an SDK cache entry.
+fn load(path: &Path) -> Result<String, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let json: Value = serde_json::from_str(&content).map_err(|err| err.to_string())?;
+    let config = match json.get("flags") {
+        Some(Value::Object(flags)) => simplified_config(flags)?,
+        _ if json.get("f").is_some_and(Value::is_object) => json,
+        _ => {
+            return Err(
+                "The file is neither a config JSON nor in the simplified format.".to_owned(),
+            )
+        }
+    };
+    // The SDK compares the cache entries by their ETag, so it's derived from the content to
+    // make the SDK pick up the changed flags.
+    let config = config.to_string();
+    let etag = format!("{:x}", Sha256::digest(config.as_bytes()));
+    Ok(format!(
+        "{}\n{etag}\n{config}",
+        Utc::now().timestamp_millis()
+    ))
+}
+
+/// Converts the simplified `{"flags": {"key": value}}` format to a config JSON.
+fn simplified_config(flags: &Map<String, Value>) -> Result<Value, String> {
+    let mut settings = Map::new();
+    for (key, value) in flags {
+        let setting = match value {
+            Value::Bool(val) => json!({ "t": 0, "v": { "b": val } }),
+            Value::String(val) => json!({ "t": 1, "v": { "s": val } }),
+            Value::Number(val) if val.is_i64() => json!({ "t": 2, "v": { "i": val } }),
+            Value::Number(val) => json!({ "t": 3, "v": { "d": val } }),
+            _ => {
+                return Err(format!(
+                    "The value of '{key}' isn't a bool, string or number."
+                ))
+            }
+        };
+        settings.insert(key.clone(), setting);
+    }
+    Ok(json!({ "f": settings }))
+}
+
+fn config_json(cache_str: &str) -> &str {
+    cache_str.splitn(3, '\n').nth(2).unwrap_or_default()
+}
@@ -0,0 +1,71 @@
+use crate::flags::ContextMapping;
+use open_feature::EvaluationContext;
+use std::sync::Arc;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// A tonic [`Interceptor`] that builds an [`EvaluationContext`] from gRPC metadata, and stores
+/// it in the request extensions.
+///
+/// Service methods can pick up the context from the extensions to evaluate feature flags for
+/// the caller, for example with [`Flags`](crate::Flags). When the request already has an
+/// [`EvaluationContext`] extension, the metadata values are added to that context.
+/// Missing metadata and metadata values that are not valid text are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, Flags, MetadataContextInterceptor};
+/// use open_feature::EvaluationContext;
+/// use tonic::{Request, Status};
+///
+/// let interceptor = MetadataContextInterceptor::new()
+///     .targeting_key_metadata("x-user-id")
+///     .attribute_metadata("x-tenant-id", "Tenant");
+///
+/// // In a service method:
+/// fn flags<T>(provider: &ConfigCatProvider, request: &Request<T>) -> Flags {
+///     let ctx = request.extensions().get::<EvaluationContext>().cloned().unwrap_or_default();
+///     Flags::new(provider.clone(), ctx)
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct MetadataContextInterceptor {
+    mapping: Arc<ContextMapping>,
+}
+
+impl MetadataContextInterceptor {
+    /// Creates a new [`MetadataContextInterceptor`] with no metadata keys configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the metadata key whose value becomes the targeting key of the context.
+    pub fn targeting_key_metadata(mut self, key: &str) -> Self {
+        Arc::make_mut(&mut self.mapping).targeting_key = Some(key.to_owned());
+        self
+    }
+
+    /// Adds a metadata key whose value becomes the given custom attribute of the context.
+    pub fn attribute_metadata(mut self, key: &str, attribute: &str) -> Self {
+        Arc::make_mut(&mut self.mapping)
+            .attributes
+            .push((key.to_owned(), attribute.to_owned()));
+        self
+    }
+}
+
+impl Interceptor for MetadataContextInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let ctx = request
+            .extensions_mut()
+            .remove::<EvaluationContext>()
+            .unwrap_or_default();
+        let metadata = request.metadata();
+        let ctx = self.mapping.apply(ctx, |key| {
+            metadata.get(key).and_then(|value| value.to_str().ok())
+        });
+        request.extensions_mut().insert(ctx);
+        Ok(request)
+    }
+}
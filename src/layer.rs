@@ -0,0 +1,198 @@
+use crate::flags::ContextMapping;
+use crate::provider::ConfigCatProvider;
+use http::{Request, Response, StatusCode};
+use open_feature::EvaluationContext;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// A tower [`Layer`] that builds an [`EvaluationContext`] from request headers, and stores it
+/// in the request extensions.
+///
+/// Handlers (or the axum `Flags` extractor) can pick up the context from the extensions to
+/// evaluate feature flags for the current request. When the request already has an
+/// [`EvaluationContext`] extension, the header values are added to that context.
+/// Missing headers and headers that are not valid text are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::EvaluationContextLayer;
+///
+/// let layer = EvaluationContextLayer::new()
+///     .targeting_key_header("x-user-id")
+///     .attribute_header("x-tenant-id", "Tenant")
+///     .attribute_header("cf-ipcountry", "Country");
+/// ```
+#[derive(Clone, Default)]
+pub struct EvaluationContextLayer {
+    mapping: Arc<ContextMapping>,
+}
+
+impl EvaluationContextLayer {
+    /// Creates a new [`EvaluationContextLayer`] with no headers configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the header whose value becomes the targeting key of the context.
+    pub fn targeting_key_header(mut self, header: &str) -> Self {
+        Arc::make_mut(&mut self.mapping).targeting_key = Some(header.to_owned());
+        self
+    }
+
+    /// Adds a header whose value becomes the given custom attribute of the context.
+    pub fn attribute_header(mut self, header: &str, attribute: &str) -> Self {
+        Arc::make_mut(&mut self.mapping)
+            .attributes
+            .push((header.to_owned(), attribute.to_owned()));
+        self
+    }
+}
+
+impl<S> Layer<S> for EvaluationContextLayer {
+    type Service = EvaluationContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EvaluationContextService {
+            inner,
+            mapping: self.mapping.clone(),
+        }
+    }
+}
+
+/// The service created by [`EvaluationContextLayer`].
+#[derive(Clone)]
+pub struct EvaluationContextService<S> {
+    inner: S,
+    mapping: Arc<ContextMapping>,
+}
+
+impl<S, B> Service<Request<B>> for EvaluationContextService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let ctx = request
+            .extensions_mut()
+            .remove::<EvaluationContext>()
+            .unwrap_or_default();
+        let headers = request.headers();
+        let ctx = self.mapping.apply(ctx, |name| {
+            headers.get(name).and_then(|value| value.to_str().ok())
+        });
+        request.extensions_mut().insert(ctx);
+        self.inner.call(request)
+    }
+}
+
+/// A tower [`Layer`] that only lets requests through when a feature flag is on.
+///
+/// The flag is evaluated for each request with the [`EvaluationContext`] request extension
+/// (see [`EvaluationContextLayer`]), or an empty context when there is none. When the flag
+/// is off (or fails to evaluate), the request is answered with the configured status code and
+/// an empty body, so whole endpoints can be dark launched from the ConfigCat Dashboard.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagGateLayer};
+/// use http::StatusCode;
+///
+/// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+/// let layer = FlagGateLayer::new(provider, "betaApiEnabled", StatusCode::NOT_FOUND);
+/// ```
+#[derive(Clone)]
+pub struct FlagGateLayer {
+    gate: Arc<FlagGate>,
+}
+
+struct FlagGate {
+    provider: ConfigCatProvider,
+    flag_key: String,
+    status: StatusCode,
+}
+
+impl FlagGateLayer {
+    /// Creates a new [`FlagGateLayer`] gated by the given feature flag, which responds with
+    /// `status` when the flag is off.
+    pub fn new(provider: ConfigCatProvider, flag_key: &str, status: StatusCode) -> Self {
+        Self {
+            gate: Arc::new(FlagGate {
+                provider,
+                flag_key: flag_key.to_owned(),
+                status,
+            }),
+        }
+    }
+}
+
+impl<S> Layer<S> for FlagGateLayer {
+    type Service = FlagGateService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FlagGateService {
+            inner,
+            gate: self.gate.clone(),
+        }
+    }
+}
+
+/// The service created by [`FlagGateLayer`].
+#[derive(Clone)]
+pub struct FlagGateService<S> {
+    inner: S,
+    gate: Arc<FlagGate>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for FlagGateService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+    ResBody: Default + Send,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // Take the service that was polled ready, and leave a fresh clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let gate = self.gate.clone();
+        Box::pin(async move {
+            let ctx = request
+                .extensions()
+                .get::<EvaluationContext>()
+                .cloned()
+                .unwrap_or_default();
+            let enabled = gate
+                .provider
+                .resolve_bool_on(None, gate.flag_key.as_str(), &ctx)
+                .await
+                .is_ok_and(|details| details.value);
+            if enabled {
+                inner.call(request).await
+            } else {
+                let mut response = Response::new(ResBody::default());
+                *response.status_mut() = gate.status;
+                Ok(response)
+            }
+        })
+    }
+}
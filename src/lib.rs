@@ -4,10 +4,268 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::doc_markdown)]
 #![allow(clippy::module_name_repetitions)]
+#![allow(clippy::return_self_not_must_use)]
+#![allow(clippy::must_use_candidate)]
 
 /// ConfigCat provider module.
 mod provider;
 pub use provider::*;
 
+/// Provider builder module.
+mod builder;
+pub use builder::ConfigCatProviderBuilder;
+
+/// SDK key validation module.
+mod sdk_key;
+
+/// ConfigCat User Object evaluation module.
+mod user;
+
+/// Typed text setting module.
+mod typed;
+
+/// Evaluation trace module.
+mod debug;
+
+/// Typed bulk evaluation module.
+mod bulk;
+pub use bulk::{FlagSet, FlagValues};
+
+/// Per-request flag evaluation module.
+mod flags;
+pub use flags::{Flags, USER_ID_HEADER};
+
+/// Typed flag key module.
+mod flag_key;
+pub use flag_key::FlagKey;
+
+/// Enum flag module.
+mod flag_enum;
+#[cfg(feature = "derive")]
+pub use configcat_openfeature_provider_derive::FlagEnum;
+pub use flag_enum::FlagEnum;
+
+/// Declared flag verification module.
+mod verify;
+pub use verify::{FlagDefinition, FlagSchema, FlagType, FlagTypeMismatch, VerificationReport};
+
+/// Synchronous provider facade module.
+mod blocking;
+pub use blocking::BlockingConfigCatProvider;
+
+/// Flag gate module.
+mod gate;
+pub use gate::{Gate, GateOrElse};
+
+/// Flag change subscription module.
+mod watch;
+pub use watch::FlagBinding;
+
+/// Config JSON change diff module.
+mod change;
+pub use change::ConfigChange;
+
+/// OpenFeature domain registration module.
+mod domains;
+
+/// Multi-environment provider module.
+mod multi_env;
+pub use multi_env::MultiEnvConfigCatProvider;
+
+/// Fallback provider module.
+mod fallback;
+pub use fallback::FallbackProvider;
+
+/// Flag migration module.
+mod migration;
+pub use migration::{FlagMigration, MigrationExposure};
+
+/// SDK key rotation module.
+mod rotation;
+pub use rotation::KeyRotationProvider;
+
+/// Shadow evaluation module.
+mod shadow;
+pub use shadow::{ShadowMismatch, ShadowProvider};
+
+/// Evaluation sink module.
+mod sink;
+pub use sink::*;
+
+/// Tracking event module.
+mod tracking;
+pub use tracking::{FlagExposure, TrackingEvent, TrackingSink};
+
+/// Trace context module.
+mod trace_context;
+
+/// Batched evaluation event exporter module.
+mod exporter;
+pub use exporter::*;
+
+/// Evaluation audit log module.
+mod audit;
+pub use audit::*;
+
+/// Provider runtime statistics module.
+mod stats;
+pub use stats::{FlagUsage, ProviderStats, UsageReport, MAX_COUNTED_VALUES, OTHER_VALUES_KEY};
+
+/// Periodic evaluation summary logging module.
+mod summary;
+
+/// Config JSON fetching and fetch health metrics.
+mod refresh;
+pub use refresh::FetchMetrics;
+
+/// Config JSON download module.
+mod download;
+
+/// Shared config source module.
+mod source;
+
+/// Downloaded config JSON tracking module.
+mod tap;
+
+/// Background task and timer runtime module.
+mod runtime;
+
+/// Request-scoped snapshot provider module.
+mod snapshot;
+pub use snapshot::ConfigCatSnapshotProvider;
+
+/// Bootstrap payload module.
+mod bootstrap;
+
+/// Custom config JSON cache module.
+mod cache;
+pub use cache::ProviderCache;
+
+/// Persisted and fallback config JSON cache module.
+mod persist;
+
+/// OpenFeature hooks module.
+mod hooks;
+pub use hooks::*;
+
+/// Sampling and rate limiting of evaluation events.
+mod sampling;
+pub use sampling::*;
+
+/// Bridge between the ConfigCat SDK's logs and `tracing`.
+#[cfg(feature = "tracing")]
+mod log_bridge;
+#[cfg(feature = "tracing")]
+pub use log_bridge::*;
+
+/// Flag-controlled `tracing` filter module.
+#[cfg(feature = "tracing-reload")]
+mod log_filter;
+
+/// Sentry integration module.
+#[cfg(feature = "sentry")]
+mod sentry;
+#[cfg(feature = "sentry")]
+pub use sentry::SentrySink;
+
+/// OpenFeature Remote Evaluation Protocol (OFREP) server adapter module.
+#[cfg(feature = "ofrep")]
+mod ofrep;
+#[cfg(feature = "ofrep")]
+pub use ofrep::*;
+
+/// axum integration module.
+#[cfg(feature = "axum")]
+mod extract;
+
+/// actix-web integration module.
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "actix")]
+pub use actix::*;
+
+/// warp integration module.
+#[cfg(feature = "warp")]
+mod warp_filter;
+#[cfg(feature = "warp")]
+pub use warp_filter::*;
+
+/// tower middleware module.
+#[cfg(feature = "tower")]
+mod layer;
+#[cfg(feature = "tower")]
+pub use layer::*;
+
+/// tonic integration module.
+#[cfg(feature = "tonic")]
+mod interceptor;
+#[cfg(feature = "tonic")]
+pub use interceptor::*;
+
+/// ConfigCat Proxy gRPC update stream module.
+#[cfg(feature = "proxy-grpc")]
+mod proxy_grpc;
+
+/// ConfigCat Proxy server-sent events update stream module.
+#[cfg(feature = "proxy-sse")]
+mod proxy_sse;
+
+/// async-graphql integration module.
+#[cfg(feature = "async-graphql")]
+mod guard;
+#[cfg(feature = "async-graphql")]
+pub use guard::FlagGuard;
+
+/// `config` crate integration module.
+#[cfg(feature = "config")]
+mod config_source;
+#[cfg(feature = "config")]
+pub use config_source::ConfigCatSource;
+
+/// Build-time flag code generation module.
+#[cfg(feature = "codegen")]
+mod codegen;
+#[cfg(feature = "codegen")]
+pub use codegen::{generate_flags, write_flags};
+
+/// Testing utilities module.
+#[cfg(feature = "testing")]
+pub mod testing;
+
+/// Entry points of the fuzz targets in the `fuzz` directory, not part of the public API.
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing;
+
+/// Hot-reloaded flag overrides file module.
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+
+/// Redis-backed config JSON cache module.
+#[cfg(feature = "redis")]
+mod redis_cache;
+#[cfg(feature = "redis")]
+pub use redis_cache::RedisCache;
+
+/// Typed flag declaration macros.
+mod macros;
+
+/// Object flag value format module.
+mod format;
+pub use format::StructFormat;
+#[cfg(feature = "base64")]
+pub use format::BASE64_PREFIX;
+
+/// String flag template module.
+mod template;
+
+/// String flag transformation module.
+mod transform;
+pub use transform::StringTransform;
+
+/// Flag value conversion module.
+mod value;
+pub use value::FlagValue;
+
 pub use configcat;
 pub use open_feature;
@@ -0,0 +1,118 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use tracing_log::AsLog;
+
+/// The `tracing` target of the log messages emitted by the ConfigCat SDK when they are
+/// routed through a [`SdkLogBridge`].
+pub const SDK_LOG_TARGET: &str = "configcat_sdk";
+
+const SDK_LOG_PREFIX: &str = "configcat";
+
+/// Routes the ConfigCat SDK's internal log messages into the `tracing` ecosystem.
+///
+/// The SDK logs through the `log` crate; the bridge re-emits its messages as `tracing` events
+/// with the [`SDK_LOG_TARGET`] target and the configured level mapping, so they show up in the
+/// application's structured logs. Log messages of other crates are forwarded to `tracing`
+/// unchanged.
+///
+/// The bridge is installed as the global `log` logger when the provider is built, so it
+/// can't be combined with another `log` logger.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, SdkLogBridge};
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .sdk_log_bridge(
+///         SdkLogBridge::new()
+///             // Report the SDK's info level evaluation logs as debug events.
+///             .map_level(log::Level::Info, tracing::Level::DEBUG),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct SdkLogBridge {
+    levels: [tracing::Level; 5],
+}
+
+impl SdkLogBridge {
+    /// Creates a new [`SdkLogBridge`] that maps each `log` level to its `tracing` equivalent.
+    pub fn new() -> Self {
+        Self {
+            levels: [
+                tracing::Level::ERROR,
+                tracing::Level::WARN,
+                tracing::Level::INFO,
+                tracing::Level::DEBUG,
+                tracing::Level::TRACE,
+            ],
+        }
+    }
+
+    /// Sets the `tracing` level used for the SDK's log messages of the given `log` level.
+    pub fn map_level(mut self, from: Level, to: tracing::Level) -> Self {
+        self.levels[level_index(from)] = to;
+        self
+    }
+
+    pub(crate) fn install(self) {
+        if log::set_boxed_logger(Box::new(self)).is_ok() {
+            log::set_max_level(LevelFilter::Trace);
+        } else {
+            log::warn!("A global logger is already set, the ConfigCat SDK logs won't be routed to tracing.");
+        }
+    }
+
+    fn mapped_level(&self, level: Level) -> Level {
+        self.levels[level_index(level)].as_log()
+    }
+}
+
+impl Default for SdkLogBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Log for SdkLogBridge {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let level = if is_sdk_target(metadata.target()) {
+            self.mapped_level(metadata.level())
+        } else {
+            metadata.level()
+        };
+        level <= tracing::level_filters::LevelFilter::current().as_log()
+    }
+
+    fn log(&self, record: &Record) {
+        if !is_sdk_target(record.target()) {
+            _ = tracing_log::format_trace(record);
+            return;
+        }
+        let args = record.args();
+        match self.levels[level_index(record.level())] {
+            tracing::Level::ERROR => tracing::error!(target: SDK_LOG_TARGET, "{args}"),
+            tracing::Level::WARN => tracing::warn!(target: SDK_LOG_TARGET, "{args}"),
+            tracing::Level::INFO => tracing::info!(target: SDK_LOG_TARGET, "{args}"),
+            tracing::Level::DEBUG => tracing::debug!(target: SDK_LOG_TARGET, "{args}"),
+            _ => tracing::trace!(target: SDK_LOG_TARGET, "{args}"),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn is_sdk_target(target: &str) -> bool {
+    target == SDK_LOG_PREFIX || target.starts_with("configcat::")
+}
+
+fn level_index(level: Level) -> usize {
+    match level {
+        Level::Error => 0,
+        Level::Warn => 1,
+        Level::Info => 2,
+        Level::Debug => 3,
+        Level::Trace => 4,
+    }
+}
@@ -0,0 +1,75 @@
+use crate::provider::ConfigCatProvider;
+use crate::runtime;
+use log::warn;
+use open_feature::EvaluationContext;
+use std::str::FromStr;
+use tokio::task::JoinHandle;
+use tracing_subscriber::reload::Handle;
+
+impl ConfigCatProvider {
+    /// Keeps a reloadable `tracing` filter in sync with the value of a text setting.
+    ///
+    /// The setting's value is parsed as a filter of type `F` (like
+    /// `tracing_subscriber::filter::LevelFilter`, or `EnvFilter` with directives like
+    /// `"info,my_crate=debug"`), and installed through the reload handle each time the config
+    /// JSON changes the value, so operators can turn on debug logging from the ConfigCat
+    /// Dashboard. Empty and unparsable values leave the current filter in place.
+    ///
+    /// The returned task runs until it's aborted, or until the first change after the
+    /// subscriber of the handle is dropped. Changes are only tracked when the provider was created with
+    /// [`ConfigCatProvider::builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    /// use tracing_subscriber::filter::LevelFilter;
+    /// use tracing_subscriber::prelude::*;
+    /// use tracing_subscriber::reload;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (filter, handle) = reload::Layer::new(LevelFilter::INFO);
+    ///     tracing_subscriber::registry().with(filter).init();
+    ///
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///     provider
+    ///         .reload_log_filter("logLevel", EvaluationContext::default(), handle)
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn reload_log_filter<F, S>(
+        &self,
+        flag_key: &str,
+        evaluation_context: EvaluationContext,
+        handle: Handle<F, S>,
+    ) -> JoinHandle<()>
+    where
+        F: FromStr + Send + Sync + 'static,
+        S: 'static,
+    {
+        let mut filter = self.watch_string(flag_key, evaluation_context, "").await;
+        let flag_key = flag_key.to_owned();
+        runtime::spawn(async move {
+            loop {
+                let directives = filter.borrow_and_update().clone();
+                if !directives.is_empty() {
+                    match directives.parse::<F>() {
+                        Ok(new_filter) => {
+                            if handle.reload(new_filter).is_err() {
+                                return;
+                            }
+                        }
+                        Err(_) => warn!(
+                            "The value of '{flag_key}' is not a valid log filter: '{directives}'."
+                        ),
+                    }
+                }
+                if filter.changed().await.is_err() {
+                    return;
+                }
+            }
+        })
+    }
+}
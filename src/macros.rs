@@ -0,0 +1,77 @@
+/// Declares typed accessors for feature flags, so flag keys and value types are spelled out
+/// in one place instead of being repeated as string literals at each evaluation.
+///
+/// Each declaration produces a unit struct with the flag key in its `KEY` constant, the
+/// [`FlagKey`](crate::FlagKey) in its `FLAG` constant, and the following associated functions:
+/// - `get(provider, evaluation_context, default)` returns the value of the flag, or `default`
+///   when the evaluation fails.
+/// - `details(provider, evaluation_context)` returns the whole resolution details.
+///
+/// The supported value types are `bool`, `i64`, `f64` and `String`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{declare_flags, ConfigCatProvider};
+/// use open_feature::EvaluationContext;
+///
+/// declare_flags! {
+///     /// Enables the new checkout flow.
+///     pub IsNewCheckout: bool = "isNewCheckoutEnabled";
+///     MaxItems: i64 = "maxCartItems";
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///
+///     if IsNewCheckout::get(&provider, &ctx, false).await {
+///         let max_items = MaxItems::get(&provider, &ctx, 10).await;
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_flags {
+    ($($(#[$meta:meta])* $vis:vis $name:ident: $ty:ty = $key:literal;)*) => {
+        $(
+            $(#[$meta])*
+            #[doc = concat!("The `", $key, "` feature flag.")]
+            #[derive(Clone, Copy, Debug)]
+            $vis struct $name;
+
+            impl $name {
+                /// The key of the feature flag.
+                pub const KEY: &'static str = $key;
+
+                /// The typed key of the feature flag.
+                pub const FLAG: $crate::FlagKey<$ty> = $crate::FlagKey::new($key);
+
+                /// Returns the value of the feature flag, or `default` when the evaluation fails.
+                pub async fn get(
+                    provider: &$crate::ConfigCatProvider,
+                    evaluation_context: &$crate::open_feature::EvaluationContext,
+                    default: $ty,
+                ) -> $ty {
+                    Self::details(provider, evaluation_context)
+                        .await
+                        .map_or(default, |details| details.value)
+                }
+
+                /// Returns the resolution details of the feature flag.
+                ///
+                /// # Errors
+                ///
+                /// Returns the error of the evaluation.
+                pub async fn details(
+                    provider: &$crate::ConfigCatProvider,
+                    evaluation_context: &$crate::open_feature::EvaluationContext,
+                ) -> $crate::open_feature::EvaluationResult<
+                    $crate::open_feature::provider::ResolutionDetails<$ty>,
+                > {
+                    provider.resolve(Self::FLAG, evaluation_context).await
+                }
+            }
+        )*
+    };
+}
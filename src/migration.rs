@@ -0,0 +1,166 @@
+use crate::provider::ConfigCatProvider;
+use crate::value::FlagValue;
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationResult};
+use sha2::{Digest, Sha256};
+
+type ExposureFn = dyn Fn(&MigrationExposure) + Send + Sync;
+
+/// Describes which flag was served to a user during a [`FlagMigration`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationExposure {
+    /// The key of the flag whose value was served.
+    pub flag_key: String,
+    /// Whether the value of the new flag was served.
+    pub served_new: bool,
+    /// The key of the flag being migrated from.
+    pub old_key: String,
+    /// The key of the flag being migrated to.
+    pub new_key: String,
+    /// The targeting key of the evaluation context, if any.
+    pub targeting_key: Option<String>,
+}
+
+/// Serves either an old or a new flag while migrating between restructured flags.
+///
+/// Only the served flag is evaluated, so the other one doesn't show up in the sinks,
+/// statistics and exposures. The value of the new flag is served to the given percentage of
+/// users. The split is consistent: a user (identified by the targeting key of
+/// the evaluation context) keeps getting the same flag as long as the percentage doesn't
+/// decrease. Evaluation contexts without a targeting key are always served the old flag.
+/// Each evaluation is reported to the callbacks added with [`FlagMigration::on_exposure`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagMigration};
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     let migration = FlagMigration::new(provider, "newCheckout", "checkout.v2.enabled", 20)
+///         .on_exposure(|exposure| {
+///             println!("served '{}' to {:?}", exposure.flag_key, exposure.targeting_key);
+///         });
+///
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///     let enabled = migration.resolve_bool(&ctx).await.unwrap().value;
+/// }
+/// ```
+pub struct FlagMigration {
+    provider: ConfigCatProvider,
+    old_key: String,
+    new_key: String,
+    percentage: u8,
+    on_exposure: Vec<Box<ExposureFn>>,
+}
+
+impl FlagMigration {
+    /// Creates a new [`FlagMigration`] serving the flag with `new_key` instead of the flag with
+    /// `old_key` to `percentage` percent of users. Percentages above 100 are treated as 100.
+    pub fn new(provider: ConfigCatProvider, old_key: &str, new_key: &str, percentage: u8) -> Self {
+        Self {
+            provider,
+            old_key: old_key.to_owned(),
+            new_key: new_key.to_owned(),
+            percentage: percentage.min(100),
+            on_exposure: Vec::new(),
+        }
+    }
+
+    /// Adds a callback invoked with the served flag each time the migration is evaluated.
+    pub fn on_exposure<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&MigrationExposure) + Send + Sync + 'static,
+    {
+        self.on_exposure.push(Box::new(callback));
+        self
+    }
+
+    /// Returns whether the new flag is served for the given evaluation context.
+    pub fn serves_new(&self, evaluation_context: &EvaluationContext) -> bool {
+        let Some(targeting_key) = evaluation_context.targeting_key.as_deref() else {
+            return false;
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(self.new_key.as_bytes());
+        hasher.update(targeting_key.as_bytes());
+        let hash = hasher.finalize();
+        let bucket = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) % 100;
+        bucket < u32::from(self.percentage)
+    }
+
+    /// Resolves the migrated feature flag for the given evaluation context.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the served flag's evaluation.
+    pub async fn resolve_bool(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(evaluation_context).await
+    }
+
+    /// Resolves the migrated whole number setting for the given evaluation context.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the served flag's evaluation.
+    pub async fn resolve_int(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(evaluation_context).await
+    }
+
+    /// Resolves the migrated decimal number setting for the given evaluation context.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the served flag's evaluation.
+    pub async fn resolve_float(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(evaluation_context).await
+    }
+
+    /// Resolves the migrated text setting for the given evaluation context.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the served flag's evaluation.
+    pub async fn resolve_string(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(evaluation_context).await
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let served_new = self.serves_new(evaluation_context);
+        let flag_key = if served_new {
+            &self.new_key
+        } else {
+            &self.old_key
+        };
+        let result = T::resolve(&self.provider, flag_key, evaluation_context).await;
+        let exposure = MigrationExposure {
+            flag_key: flag_key.clone(),
+            served_new,
+            old_key: self.old_key.clone(),
+            new_key: self.new_key.clone(),
+            targeting_key: evaluation_context.targeting_key.clone(),
+        };
+        for callback in &self.on_exposure {
+            callback(&exposure);
+        }
+        result
+    }
+}
@@ -0,0 +1,152 @@
+use crate::provider::ConfigCatProvider;
+use async_trait::async_trait;
+use open_feature::provider::{FeatureProvider, ProviderMetadata, ResolutionDetails};
+use open_feature::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
+    EvaluationResult, StructValue,
+};
+use std::collections::HashMap;
+
+/// An OpenFeature provider that routes each evaluation to one of multiple ConfigCat
+/// environments, based on an attribute of the evaluation context.
+///
+/// It's meant for multi-tenant applications where each tenant has its own ConfigCat
+/// environment (and SDK key): the value of the configured context attribute (like the tenant
+/// id) selects the [`ConfigCatProvider`] that evaluates the flag. Evaluations with an unknown
+/// or missing attribute value go to the default environment, or fail with
+/// [`EvaluationErrorCode::InvalidContext`] when there is none.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, MultiEnvConfigCatProvider};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = MultiEnvConfigCatProvider::new("Tenant")
+///         .environment("acme", ConfigCatProvider::builder("acme-sdk-key").build().unwrap())
+///         .environment("globex", ConfigCatProvider::builder("globex-sdk-key").build().unwrap())
+///         .default_environment(ConfigCatProvider::builder("sdk-key").build().unwrap());
+///
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(provider).await;
+/// }
+/// ```
+pub struct MultiEnvConfigCatProvider {
+    metadata: ProviderMetadata,
+    attribute: String,
+    environments: HashMap<String, ConfigCatProvider>,
+    default: Option<ConfigCatProvider>,
+}
+
+impl MultiEnvConfigCatProvider {
+    /// Creates a new [`MultiEnvConfigCatProvider`] that selects the environment by the given
+    /// evaluation context attribute.
+    pub fn new(attribute: &str) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("MultiEnvConfigCatProvider"),
+            attribute: attribute.to_owned(),
+            environments: HashMap::new(),
+            default: None,
+        }
+    }
+
+    /// Adds an environment used when the context attribute has the given value.
+    pub fn environment(mut self, value: &str, provider: ConfigCatProvider) -> Self {
+        self.environments.insert(value.to_owned(), provider);
+        self
+    }
+
+    /// Sets the environment used when the context attribute is missing or has an unknown value.
+    pub fn default_environment(mut self, provider: ConfigCatProvider) -> Self {
+        self.default = Some(provider);
+        self
+    }
+
+    fn route(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<&ConfigCatProvider> {
+        let value = match evaluation_context.custom_fields.get(&self.attribute) {
+            Some(EvaluationContextFieldValue::String(value)) => Some(value),
+            _ => None,
+        };
+        value
+            .and_then(|value| self.environments.get(value))
+            .or(self.default.as_ref())
+            .ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::InvalidContext)
+                    .message(format!(
+                        "No ConfigCat environment is configured for the {} context attribute value '{}'.",
+                        self.attribute,
+                        value.map_or("", String::as_str)
+                    ))
+                    .build()
+            })
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for MultiEnvConfigCatProvider {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        for provider in self.environments.values().chain(self.default.as_ref()) {
+            provider.clone().initialize(context).await;
+        }
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.route(evaluation_context)?
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.route(evaluation_context)?
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.route(evaluation_context)?
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.route(evaluation_context)?
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.route(evaluation_context)?
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+}
@@ -0,0 +1,167 @@
+use crate::bootstrap::{BootstrapPayload, FlagEvaluation};
+use crate::provider::ConfigCatProvider;
+use open_feature::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+/// The path of the OFREP single flag evaluation endpoint, where `{key}` is the flag key.
+pub const OFREP_EVALUATE_FLAG_PATH: &str = "/ofrep/v1/evaluate/flags/{key}";
+
+/// The path of the OFREP bulk evaluation endpoint.
+pub const OFREP_EVALUATE_FLAGS_PATH: &str = "/ofrep/v1/evaluate/flags";
+
+#[derive(Deserialize, Default)]
+struct OfrepRequest {
+    #[serde(default)]
+    context: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The HTTP response of an OFREP endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfrepResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The JSON response body.
+    pub body: serde_json::Value,
+}
+
+/// Handlers implementing the [OpenFeature Remote Evaluation Protocol](https://github.com/open-feature/protocol)
+/// (OFREP) on top of a [`ConfigCatProvider`].
+///
+/// The handlers are framework agnostic: they take the raw request body, and return the status
+/// code and JSON body of the response, so they can be mounted in any HTTP server as POST
+/// endpoints at [`OFREP_EVALUATE_FLAG_PATH`] and [`OFREP_EVALUATE_FLAGS_PATH`]. This lets
+/// polyglot frontends evaluate flags through a thin service without embedding ConfigCat.
+///
+/// The `targetingKey` attribute of the request context becomes the targeting key, the other
+/// attributes become custom attributes (arrays are passed as JSON text). Like
+/// [`ConfigCatProvider::resolve_all`], the evaluations don't invoke the provider level callbacks,
+/// and aren't reported to the sinks and statistics.
+///
+/// The only flag metadata in the responses is the variation ID, the matched targeting rules and
+/// percentage options aren't sent to the clients, as they would reveal the targeting rules.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, OfrepHandler};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///     let ofrep = OfrepHandler::new(provider);
+///
+///     // In the handler of `POST /ofrep/v1/evaluate/flags/{key}`:
+///     let response = ofrep
+///         .evaluate_flag("isAwesomeFeatureEnabled", br#"{"context":{"targetingKey":"user-id"}}"#)
+///         .await;
+///     println!("{} {}", response.status, response.body);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct OfrepHandler {
+    provider: ConfigCatProvider,
+}
+
+impl OfrepHandler {
+    /// Creates a new [`OfrepHandler`] that evaluates the flags with the given provider.
+    pub fn new(provider: ConfigCatProvider) -> Self {
+        Self { provider }
+    }
+
+    /// Handles a single flag evaluation request.
+    ///
+    /// Responds with 200 and the evaluation result, 404 when the flag doesn't exist, or 400 when
+    /// the request is invalid or the evaluation failed.
+    pub async fn evaluate_flag(&self, flag_key: &str, body: &[u8]) -> OfrepResponse {
+        let ctx = match parse_request(body) {
+            Ok(ctx) => ctx,
+            Err(err) => return error_response(Some(flag_key), &err),
+        };
+        match self.provider.resolve_value(flag_key, &ctx).await {
+            Ok(details) => OfrepResponse {
+                status: 200,
+                body: serde_json::to_value(FlagEvaluation::new(flag_key.to_owned(), details))
+                    .unwrap_or_default(),
+            },
+            Err(err) => error_response(Some(flag_key), &err),
+        }
+    }
+
+    /// Handles a bulk evaluation request.
+    ///
+    /// Responds with 200 and the evaluation results of all flags, or 400 when the request is
+    /// invalid.
+    pub async fn evaluate_flags(&self, body: &[u8]) -> OfrepResponse {
+        let ctx = match parse_request(body) {
+            Ok(ctx) => ctx,
+            Err(err) => return error_response(None, &err),
+        };
+        match self.provider.try_resolve_all(&ctx).await {
+            Ok(evaluations) => OfrepResponse {
+                status: 200,
+                body: serde_json::to_value(BootstrapPayload::new(evaluations, None))
+                    .unwrap_or_default(),
+            },
+            Err(err) => error_response(None, &err),
+        }
+    }
+}
+
+fn parse_request(body: &[u8]) -> Result<EvaluationContext, EvaluationError> {
+    let request: OfrepRequest = if body.is_empty() {
+        OfrepRequest::default()
+    } else {
+        serde_json::from_slice(body).map_err(|err| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ParseError)
+                .message(format!("The request body is invalid. ({err})"))
+                .build()
+        })?
+    };
+    let mut ctx = EvaluationContext::default();
+    for (key, value) in request.context {
+        if key == "targetingKey" {
+            ctx.targeting_key = value.as_str().map(ToOwned::to_owned);
+            continue;
+        }
+        let value = match value {
+            serde_json::Value::Null => continue,
+            serde_json::Value::Bool(val) => EvaluationContextFieldValue::Bool(val),
+            serde_json::Value::Number(val) => match val.as_i64() {
+                Some(val) => EvaluationContextFieldValue::Int(val),
+                None => EvaluationContextFieldValue::Float(val.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(val) => EvaluationContextFieldValue::String(val),
+            serde_json::Value::Array(_) => EvaluationContextFieldValue::String(value.to_string()),
+            serde_json::Value::Object(_) => EvaluationContextFieldValue::new_struct(value),
+        };
+        ctx.custom_fields.insert(key, value);
+    }
+    Ok(ctx)
+}
+
+fn error_response(flag_key: Option<&str>, err: &EvaluationError) -> OfrepResponse {
+    let status = match err.code {
+        EvaluationErrorCode::FlagNotFound => 404,
+        _ => 400,
+    };
+    let mut body = json!({
+        "errorCode": error_code(&err.code),
+        "errorDetails": err.message.clone().unwrap_or_default(),
+    });
+    if let Some(key) = flag_key {
+        body["key"] = json!(key);
+    }
+    OfrepResponse { status, body }
+}
+
+/// Maps the error code to one of the error codes defined by OFREP.
+fn error_code(code: &EvaluationErrorCode) -> String {
+    match code {
+        EvaluationErrorCode::General(_) => "GENERAL".to_owned(),
+        code => code.to_string(),
+    }
+}
@@ -0,0 +1,97 @@
+use configcat::ConfigCache;
+use log::warn;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
+
+/// The ETag of the cache entry created from the fallback config JSON.
+const FALLBACK_ETAG: &str = "fallback";
+
+/// A [`ConfigCache`] that keeps the last fetched config JSON, optionally persisted to a local
+/// file, and serves it until the underlying ConfigCat SDK client downloads a newer one.
+///
+/// Initially, it serves the persisted config JSON, or the fallback config JSON when nothing is
+/// persisted. It forwards the calls to the wrapped cache, and falls back to the last known
+/// config JSON when the wrapped cache has no entry.
+pub(crate) struct PersistentCache {
+    path: Option<PathBuf>,
+    inner: Option<Box<dyn ConfigCache>>,
+    last_known: RwLock<Option<String>>,
+}
+
+impl PersistentCache {
+    /// Creates a new [`PersistentCache`] and loads the config JSON persisted to the given file.
+    pub(crate) fn new(
+        path: Option<PathBuf>,
+        fallback: Option<&str>,
+        inner: Option<Box<dyn ConfigCache>>,
+    ) -> Self {
+        let last_known = path
+            .as_deref()
+            .and_then(read_file)
+            .or_else(|| fallback.map(|json| format!("0\n{FALLBACK_ETAG}\n{json}")));
+        Self {
+            path,
+            inner,
+            last_known: RwLock::new(last_known),
+        }
+    }
+}
+
+impl ConfigCache for PersistentCache {
+    fn read(&self, key: &str) -> Option<String> {
+        if let Some(value) = self.inner.as_ref().and_then(|inner| inner.read(key)) {
+            return Some(value);
+        }
+        self.last_known
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn write(&self, key: &str, value: &str) {
+        {
+            let mut last_known = self
+                .last_known
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+            if last_known.as_deref() != Some(value) {
+                if let Some(path) = self.path.as_deref() {
+                    if let Err(err) = write_file(path, value) {
+                        warn!(
+                            "Failed to persist the config JSON to '{}'. ({err})",
+                            path.display()
+                        );
+                    }
+                }
+                *last_known = Some(value.to_owned());
+            }
+        }
+        if let Some(inner) = self.inner.as_ref() {
+            inner.write(key, value);
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(value) => Some(value),
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => {
+            warn!(
+                "Failed to load the persisted config JSON from '{}'. ({err})",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+/// Replaces the content of the file through a temporary file, so a crash can't leave it
+/// partially written.
+fn write_file(path: &Path, value: &str) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, value)?;
+    std::fs::rename(&tmp, path)
+}
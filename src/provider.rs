@@ -1,13 +1,82 @@
+use crate::bootstrap::BootstrapPayload;
+use crate::builder::{
+    AfterFn, BeforeFn, ConfigCatProviderBuilder, ConfigChangedFn, EvaluatedFn, FlagsChangedFn,
+    ProviderOptions, SchemaDriftFn,
+};
+use crate::bulk::{FlagSet, FlagValues};
+use crate::change;
+use crate::debug;
+use crate::format::{self, StructFormat, StructFormats};
+use crate::gate::Gate;
+use crate::refresh::FetchMetrics;
+use crate::runtime;
+use crate::sink::{EvaluationEvent, EvaluationSink};
+use crate::snapshot::ConfigCatSnapshotProvider;
+use crate::source::ConfigSource;
+use crate::stats::{ProviderStats, StatsCollector, UsageReport};
+use crate::tap::ConfigTap;
+use crate::template;
+#[cfg(feature = "testing")]
+use crate::testing::OverrideLayer;
+use crate::tracking::{ExposureLog, TrackingSink};
+use crate::transform::StringTransforms;
+#[cfg(feature = "regex")]
+use crate::typed::RegexCache;
+use crate::value::{from_sdk_value, from_value_details, to_json, to_value_details, FromValue};
+use crate::verify::{FlagSchema, VerificationReport};
 use async_trait::async_trait;
-use configcat::{Client, ClientError, ErrorKind, User, UserValue};
+use configcat::{
+    Client, ClientCacheState, ClientError, ErrorKind, User, UserValue, ValuePrimitive,
+};
+use log::{info, warn};
 use open_feature::provider::{FeatureProvider, ProviderMetadata, ResolutionDetails};
 use open_feature::{
     EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
-    EvaluationReason, EvaluationResult, StructValue, Value,
+    EvaluationReason, EvaluationResult, FlagMetadata, StructValue, Value,
 };
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
+use std::time::Duration;
+use tokio::sync::watch;
 
 const NAME: &str = "ConfigCatProvider";
 
+/// The flag metadata key of the evaluated value's variation ID.
+pub const VARIATION_ID_METADATA_KEY: &str = "variationId";
+
+/// The flag metadata key of the matched targeting rule's human readable description.
+pub const MATCHED_TARGETING_RULE_METADATA_KEY: &str = "matchedTargetingRule";
+
+/// The flag metadata key of the matched percentage option's percentage.
+pub const MATCHED_PERCENTAGE_OPTION_METADATA_KEY: &str = "matchedPercentageOption";
+
+/// The reason of evaluations served from stale config JSON in stale-while-revalidate mode.
+pub const STALE_REASON: &str = "STALE";
+
+/// The evaluation context key of a prebuilt ConfigCat User Object.
+///
+/// When the context holds a [`User`] under this key (as a struct field), the evaluation starts
+/// from that user, so codebases mixing ConfigCat users and OpenFeature contexts don't have to
+/// convert between them. The user's identifier is kept, and the other context attributes are
+/// added to the user, replacing its attributes with the same name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat::User;
+/// use configcat_openfeature_provider::USER_CONTEXT_KEY;
+/// use open_feature::{EvaluationContext, EvaluationContextFieldValue};
+///
+/// let user = User::new("user-id").custom("Roles", ["admin", "billing"]);
+/// let context = EvaluationContext::default()
+///     .with_custom_field(USER_CONTEXT_KEY, EvaluationContextFieldValue::new_struct(user))
+///     .with_custom_field("Tenant", "acme");
+/// ```
+pub const USER_CONTEXT_KEY: &str = "configcat.user";
+
 /// The ConfigCat OpenFeature provider.
 ///
 /// # Examples
@@ -42,9 +111,40 @@ const NAME: &str = "ConfigCatProvider";
 ///         .unwrap_or(false);
 /// }
 /// ```
+///
+/// Cloning the provider is cheap, the clones share the same underlying ConfigCat SDK client
+/// and state. This allows keeping a handle to the provider for runtime operations
+/// (like [`ConfigCatProvider::enable_debug_for`]) after it was registered in OpenFeature.
+#[derive(Clone)]
 pub struct ConfigCatProvider {
-    client: Client,
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    source: Arc<ConfigSource>,
     provider_metadata: ProviderMetadata,
+    sinks: Vec<Arc<dyn EvaluationSink>>,
+    debug_flags: RwLock<HashSet<String>>,
+    stats: StatsCollector,
+    init_timeout: Duration,
+    before: Vec<Box<BeforeFn>>,
+    after: Vec<Box<AfterFn>>,
+    on_evaluated: Vec<Box<EvaluatedFn>>,
+    aliases: HashMap<String, String>,
+    used_aliases: RwLock<HashSet<String>>,
+    key_prefix: String,
+    tracking_sink: Option<Arc<dyn TrackingSink>>,
+    exposures: Option<ExposureLog>,
+    schema: Option<FlagSchema>,
+    schema_report: RwLock<Option<VerificationReport>>,
+    on_schema_drift: Vec<Box<SchemaDriftFn>>,
+    struct_formats: StructFormats,
+    interpolate_strings: bool,
+    string_transforms: StringTransforms,
+    #[cfg(feature = "regex")]
+    regex_cache: RegexCache,
+    #[cfg(feature = "testing")]
+    overrides: OverrideLayer,
 }
 
 impl ConfigCatProvider {
@@ -65,17 +165,941 @@ impl ConfigCatProvider {
     /// let provider = ConfigCatProvider::new(configcat_client);
     /// ```
     pub fn new(client: Client) -> Self {
-        Self {
-            client,
+        Self::with_options(ConfigSource::unmanaged(client), ProviderOptions::default())
+    }
+
+    /// Creates a new [`ConfigCatProviderBuilder`] used to build a [`ConfigCatProvider`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::PollingMode;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(sdk_key: &str) -> ConfigCatProviderBuilder {
+        ConfigCatProviderBuilder::new(sdk_key)
+    }
+
+    pub(crate) fn with_options(source: Arc<ConfigSource>, mut options: ProviderOptions) -> Self {
+        let on_config_changed = std::mem::take(&mut options.on_config_changed);
+        let on_flags_changed = std::mem::take(&mut options.on_flags_changed);
+        let has_callbacks = !on_config_changed.is_empty() || !on_flags_changed.is_empty();
+        if let (true, Some(tap)) = (has_callbacks, source.weak_tap()) {
+            notify_config_changes(
+                tap,
+                options.key_prefix.clone(),
+                on_config_changed,
+                on_flags_changed,
+            );
+        }
+        #[cfg(feature = "regex")]
+        let regex_cache = RegexCache::new(source.tap().map(ConfigTap::subscribe));
+        let inner = Arc::new(Inner {
+            source,
             provider_metadata: ProviderMetadata::new(NAME),
+            sinks: options.sinks,
+            debug_flags: RwLock::new(HashSet::new()),
+            stats: StatsCollector::new(options.count_served_values),
+            init_timeout: options.init_timeout,
+            before: options.before,
+            after: options.after,
+            on_evaluated: options.on_evaluated,
+            aliases: options.aliases,
+            used_aliases: RwLock::new(HashSet::new()),
+            key_prefix: options.key_prefix,
+            tracking_sink: options.tracking_sink,
+            exposures: options.exposure_window.map(ExposureLog::new),
+            schema: options.schema,
+            schema_report: RwLock::new(None),
+            on_schema_drift: options.on_schema_drift,
+            struct_formats: options.struct_formats,
+            interpolate_strings: options.interpolate_strings,
+            string_transforms: options.string_transforms,
+            #[cfg(feature = "regex")]
+            regex_cache,
+            #[cfg(feature = "testing")]
+            overrides: OverrideLayer::default(),
+        });
+        if let (Some(_), Some(tap)) = (inner.schema.as_ref(), inner.source.tap()) {
+            watch_schema(tap.subscribe(), Arc::downgrade(&inner));
+        }
+        Self { inner }
+    }
+
+    /// Initiates a force refresh of the config JSON.
+    ///
+    /// The refreshes can be rate limited with [`ConfigCatProviderBuilder::refresh_rate_limit`].
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the config JSON couldn't be fetched, or the underlying ConfigCat SDK
+    /// client is in offline mode or uses local-only flag overrides.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     provider.refresh().await.unwrap();
+    /// }
+    /// ```
+    pub async fn refresh(&self) -> Result<(), ClientError> {
+        let source = self.inner.source.clone();
+        runtime::run(async move { source.refresher.refresh(&source.client).await }).await
+    }
+
+    /// Returns a snapshot of the config JSON fetch health metrics, like the number of failed
+    /// fetches and the number of seconds elapsed since the last successful one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let metrics = provider.fetch_metrics();
+    ///     if metrics.seconds_since_last_success.is_some_and(|age| age > 300.0) {
+    ///         println!("config JSON is stale, last error: {:?}", metrics.last_failure);
+    ///     }
+    /// }
+    /// ```
+    pub fn fetch_metrics(&self) -> FetchMetrics {
+        self.inner.source.refresher.metrics()
+    }
+
+    /// Returns the result of validating the config JSON against the schema set with
+    /// [`crate::ConfigCatProviderBuilder::schema`] at initialization.
+    ///
+    /// It's `None` when no schema is set, or the provider wasn't initialized with config JSON
+    /// to validate yet.
+    pub fn schema_report(&self) -> Option<VerificationReport> {
+        self.inner
+            .schema_report
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Validates the config JSON against the flag schema, and reports the mismatches when the
+    /// result differs from the previous validation.
+    async fn check_schema(&self) {
+        let Some(schema) = self.inner.schema.as_ref() else {
+            return;
+        };
+        // Without config JSON, the initialization failure is already reported.
+        let Ok(report) = self.validate(schema).await else {
+            return;
+        };
+        let previous = self
+            .inner
+            .schema_report
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(report.clone());
+        if report.is_ok() || previous.as_ref() == Some(&report) {
+            return;
+        }
+        report_lifecycle_error(
+            format!("The config JSON doesn't match the flag schema. {report}").as_str(),
+        );
+        for callback in &self.inner.on_schema_drift {
+            callback(&report);
+        }
+    }
+
+    /// Returns a [`ConfigCatSnapshotProvider`] that evaluates feature flags against the
+    /// currently downloaded config JSON, regardless of later config refreshes.
+    ///
+    /// Creating a snapshot is cheap: snapshots taken while the config JSON doesn't change share
+    /// the same frozen state. When the provider wasn't created with [`ConfigCatProvider::builder`],
+    /// or there's no downloaded config JSON yet, the snapshot evaluates the live config JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::provider::FeatureProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let snapshot = provider.snapshot();
+    ///     let ctx = EvaluationContext::default();
+    ///     let first = snapshot.resolve_bool_value("isAwesomeFeatureEnabled", &ctx).await;
+    ///     // A refresh here doesn't affect the evaluations of the snapshot.
+    ///     let second = snapshot.resolve_bool_value("isAwesomeFeatureEnabled", &ctx).await;
+    /// }
+    /// ```
+    pub fn snapshot(&self) -> ConfigCatSnapshotProvider {
+        ConfigCatSnapshotProvider::new(self.clone(), self.inner.source.frozen_client())
+    }
+
+    /// Returns the config JSON the provider currently evaluates feature flags against.
+    ///
+    /// Useful for incident forensics: the exported config JSON can be stored, and fed into
+    /// [`ConfigCatProviderBuilder::fallback_config_bytes`] or a `configcat::FileDataSource`
+    /// to reproduce evaluations offline. It's `None` when the provider has no downloaded config
+    /// JSON (e.g. when it uses local-only flag overrides), or it wasn't created with
+    /// [`ConfigCatProvider::builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     if let Some(config) = provider.export_config().await {
+    ///         std::fs::write("config.json", config.to_string()).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub async fn export_config(&self) -> Option<serde_json::Value> {
+        let source = &self.inner.source;
+        let tap = source.tap()?;
+        _ = source.prepare().await;
+        // Makes the client load the config JSON from its cache, if it hasn't yet.
+        source.client.get_all_keys().await;
+        serde_json::from_str(tap.latest()?.config_json()).ok()
+    }
+
+    /// Enables evaluation tracing for the given feature flag keys.
+    ///
+    /// For each evaluation of these flags, the provider logs the whole evaluation trace
+    /// (the user, the matched targeting rule and percentage option, and the result) at info
+    /// level, without the need of turning on verbose logging globally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    /// provider.enable_debug_for(&["newCheckout"]);
+    /// ```
+    pub fn enable_debug_for(&self, flag_keys: &[&str]) {
+        let mut debug_flags = write(&self.inner.debug_flags);
+        for key in flag_keys {
+            debug_flags.insert((*key).to_owned());
+        }
+    }
+
+    /// Disables evaluation tracing for the given feature flag keys.
+    pub fn disable_debug_for(&self, flag_keys: &[&str]) {
+        let mut debug_flags = write(&self.inner.debug_flags);
+        for key in flag_keys {
+            debug_flags.remove(*key);
+        }
+    }
+
+    /// Returns a snapshot of the provider's runtime statistics, like the number of
+    /// evaluations and errors, suitable for debug endpoints or logs.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    /// let stats = provider.stats();
+    /// println!("evaluations: {}, errors: {:?}", stats.evaluations, stats.errors_by_code);
+    /// ```
+    pub fn stats(&self) -> ProviderStats {
+        self.inner.stats.snapshot()
+    }
+
+    /// Returns the number of evaluations, the time of the last evaluation and the distribution
+    /// of the served values (when enabled with
+    /// [`ConfigCatProviderBuilder::count_served_values`]) of each flag evaluated since startup,
+    /// to see which flags are hot without external telemetry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key")
+    ///     .count_served_values()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// for (flag_key, usage) in provider.usage_report().flags {
+    ///     println!("{flag_key}: {} evaluations, values: {:?}", usage.evaluations, usage.values);
+    /// }
+    /// ```
+    pub fn usage_report(&self) -> UsageReport {
+        let flags = self
+            .inner
+            .stats
+            .usage()
+            .into_iter()
+            .filter_map(|(flag_key, usage)| Some((self.strip_key_prefix(flag_key)?, usage)))
+            .collect();
+        UsageReport { flags }
+    }
+
+    /// Evaluates all feature flags and settings for the given evaluation context.
+    ///
+    /// Useful for admin UIs and debugging endpoints that show the complete flag state of a user.
+    /// Text settings are returned as strings (they aren't parsed as JSON objects), and the flags
+    /// that failed to evaluate are left out. The result is empty when the evaluation context
+    /// can't be converted to a ConfigCat User Object, or there's no config JSON to work on.
+    ///
+    /// Unlike single flag evaluations, bulk evaluations don't invoke the provider level
+    /// callbacks, and aren't reported to the sinks and statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let ctx = EvaluationContext::default().with_targeting_key("user-id");
+    ///     for (key, details) in provider.resolve_all(&ctx).await {
+    ///         println!("{key}: {:?} ({:?})", details.value, details.reason);
+    ///     }
+    /// }
+    /// ```
+    pub async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> HashMap<String, ResolutionDetails<Value>> {
+        self.try_resolve_all(evaluation_context)
+            .await
+            .unwrap_or_default()
+    }
+
+    /// The same as [`ConfigCatProvider::resolve_all`], but fails when the evaluation context
+    /// can't be converted to a ConfigCat User Object.
+    pub(crate) async fn try_resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let user = to_user(evaluation_context)?;
+        let template_user = self.template_user(user.as_ref());
+        let stale = self.inner.source.prepare().await;
+        Ok(self
+            .inner
+            .source
+            .client
+            .get_all_value_details(user)
+            .await
+            .into_iter()
+            .filter_map(|details| {
+                let key = self.strip_key_prefix(details.key.clone())?;
+                let details = to_value_resolution(details, stale).ok()?;
+                let details = self.process_value(key.as_str(), details, template_user.as_ref());
+                Some((key, details))
+            })
+            .collect())
+    }
+
+    /// Evaluates a feature flag or setting of any type, without invoking the provider level
+    /// callbacks, and reporting to the sinks and statistics.
+    #[cfg(feature = "ofrep")]
+    pub(crate) async fn resolve_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        let resolved_key = self.resolve_key(flag_key);
+        let user = to_user(evaluation_context)?;
+        let template_user = self.template_user(user.as_ref());
+        let stale = self.inner.source.prepare().await;
+        let details = self
+            .inner
+            .source
+            .client
+            .get_flag_details(resolved_key.as_ref(), user)
+            .await;
+        to_value_resolution(details, stale)
+            .map(|details| self.process_value(flag_key, details, template_user.as_ref()))
+    }
+
+    /// Evaluates a [`FlagSet`] for the given evaluation context.
+    ///
+    /// The flags are evaluated with [`ConfigCatProvider::resolve_all`], so all of them see the
+    /// same config JSON. See [`FlagSet`] for an example.
+    pub async fn resolve_set<S: FlagSet>(&self, evaluation_context: &EvaluationContext) -> S {
+        S::from_flags(&FlagValues::new(self.resolve_all(evaluation_context).await))
+    }
+
+    /// Deserializes the settings whose keys start with the given prefix into a struct.
+    ///
+    /// The settings are evaluated for the given evaluation context, and the prefix is removed
+    /// from their keys. The remaining dots in the keys denote nested structs, so with the
+    /// `"payments."` prefix, the `payments.retry.maxAttempts` setting is deserialized into the
+    /// `max_attempts` field of the `retry` field (with `#[serde(rename_all = "camelCase")]`).
+    /// Settings missing from the config JSON can be handled with `#[serde(default)]`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ParseError`] if the settings don't match
+    /// the struct, or with [`EvaluationErrorCode::InvalidContext`] if the evaluation context
+    /// can't be converted to a ConfigCat User Object.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct PaymentSettings {
+    ///     provider: String,
+    ///     timeout_secs: i64,
+    ///     #[serde(default)]
+    ///     sandbox: bool,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let settings: PaymentSettings = provider
+    ///         .load_settings("payments.", &EvaluationContext::default())
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn load_settings<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<T> {
+        let mut settings = serde_json::Map::new();
+        for (key, details) in self.try_resolve_all(evaluation_context).await? {
+            if let Some(path) = key.strip_prefix(prefix) {
+                insert_path(&mut settings, path, to_json(&details.value));
+            }
+        }
+        serde_json::from_value(serde_json::Value::Object(settings)).map_err(|err| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ParseError)
+                .message(format!(
+                    "Failed to deserialize the settings with the '{prefix}' prefix. ({err})"
+                ))
+                .build()
+        })
+    }
+
+    /// Runs a future only when a feature flag is on for the given evaluation context.
+    ///
+    /// The flag is evaluated when the returned [`Gate`] is awaited, and counts as off when
+    /// the evaluation fails. Awaiting the gate returns `Some` with the output of the future
+    /// when the flag is on, and `None` otherwise. Chain [`Gate::or_else`] to run another
+    /// future when the flag is off.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let ctx = EvaluationContext::default().with_targeting_key("user-id");
+    ///     let path = provider
+    ///         .when_enabled("newPath", &ctx, async { "new" })
+    ///         .or_else(async { "old" })
+    ///         .await;
+    /// }
+    /// ```
+    pub fn when_enabled<'a, F>(
+        &'a self,
+        flag_key: &'a str,
+        evaluation_context: &'a EvaluationContext,
+        enabled: F,
+    ) -> Gate<'a, F> {
+        Gate::new(self, flag_key, evaluation_context, enabled)
+    }
+
+    /// Evaluates all (or the allowlisted) flags for the given evaluation context, and returns
+    /// them as a JSON document for hydrating browser and mobile OpenFeature clients.
+    ///
+    /// The document has the shape of an OpenFeature Remote Evaluation Protocol (OFREP) bulk
+    /// evaluation response, so frontends can start evaluating without a network round trip:
+    ///
+    /// ```json
+    /// {"flags": [{"key": "isAwesomeFeatureEnabled", "value": true, "reason": "DEFAULT", "variant": "..."}]}
+    /// ```
+    ///
+    /// The flags are evaluated with [`ConfigCatProvider::resolve_all`]. The only flag metadata
+    /// included is the variation ID, as the matched targeting rules and percentage options
+    /// would reveal the targeting rules to the frontend.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let ctx = EvaluationContext::default().with_targeting_key("user-id");
+    ///     let payload = provider
+    ///         .bootstrap_payload(&ctx, Some(&["newHeader", "bannerText"]))
+    ///         .await;
+    ///     let script = format!("window.__FLAGS__ = {payload};");
+    /// }
+    /// ```
+    pub async fn bootstrap_payload(
+        &self,
+        evaluation_context: &EvaluationContext,
+        allowlist: Option<&[&str]>,
+    ) -> serde_json::Value {
+        let evaluations = self.resolve_all(evaluation_context).await;
+        serde_json::to_value(BootstrapPayload::new(evaluations, allowlist)).unwrap_or_default()
+    }
+
+    /// Returns the keys of all feature flags and settings in alphabetical order.
+    ///
+    /// Useful for tooling like startup validation and admin panels. The result is empty when
+    /// there's no config JSON to work on.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let keys = provider.flag_keys().await;
+    ///     assert!(keys.iter().any(|key| key == "isAwesomeFeatureEnabled"));
+    /// }
+    /// ```
+    pub async fn flag_keys(&self) -> Vec<String> {
+        _ = self.inner.source.prepare().await;
+        let mut keys: Vec<String> = self
+            .inner
+            .source
+            .client
+            .get_all_keys()
+            .await
+            .into_iter()
+            .filter_map(|key| self.strip_key_prefix(key))
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Returns the keys of the feature flags and settings in the config JSON that weren't
+    /// evaluated by this provider since startup, in alphabetical order.
+    ///
+    /// Useful to find dead flags to clean up: a flag that stays unused for the whole lifetime of
+    /// a long-running service is likely not referenced by its code anymore. Evaluations of
+    /// snapshots created from this provider count as well.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     for flag_key in provider.unused_flags().await {
+    ///         println!("'{flag_key}' wasn't evaluated since startup");
+    ///     }
+    /// }
+    /// ```
+    pub async fn unused_flags(&self) -> Vec<String> {
+        _ = self.inner.source.prepare().await;
+        let mut keys: Vec<String> = self
+            .inner
+            .source
+            .client
+            .get_all_keys()
+            .await
+            .into_iter()
+            .filter(|key| !self.inner.stats.is_evaluated(key))
+            .filter_map(|key| self.strip_key_prefix(key))
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    pub(crate) fn struct_format(&self, flag_key: &str) -> StructFormat {
+        self.inner.struct_formats.get(flag_key)
+    }
+
+    #[cfg(feature = "regex")]
+    pub(crate) fn regex_cache(&self) -> &RegexCache {
+        &self.inner.regex_cache
+    }
+
+    pub(crate) fn source(&self) -> &ConfigSource {
+        &self.inner.source
+    }
+
+    pub(crate) fn tracking_sink(&self) -> Option<&dyn TrackingSink> {
+        self.inner.tracking_sink.as_deref()
+    }
+
+    pub(crate) fn exposure_log(&self) -> Option<&ExposureLog> {
+        self.inner.exposures.as_ref()
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn override_layer(&self) -> &OverrideLayer {
+        &self.inner.overrides
+    }
+
+    pub(crate) async fn resolve_bool_on(
+        &self,
+        snapshot: Option<&Client>,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.evaluate(
+            snapshot,
+            flag_key,
+            evaluation_context,
+            None,
+            false,
+            |details, _| to_res_details(details),
+        )
+        .await
+    }
+
+    pub(crate) async fn resolve_int_on(
+        &self,
+        snapshot: Option<&Client>,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.evaluate(
+            snapshot,
+            flag_key,
+            evaluation_context,
+            None,
+            0,
+            |details, _| to_res_details(details),
+        )
+        .await
+    }
+
+    pub(crate) async fn resolve_float_on(
+        &self,
+        snapshot: Option<&Client>,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.evaluate(
+            snapshot,
+            flag_key,
+            evaluation_context,
+            None,
+            0.0,
+            |details, _| to_res_details(details),
+        )
+        .await
+    }
+
+    pub(crate) async fn resolve_string_on(
+        &self,
+        snapshot: Option<&Client>,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.evaluate(
+            snapshot,
+            flag_key,
+            evaluation_context,
+            None,
+            String::default(),
+            |details, user| self.to_string_details(flag_key, details, user),
+        )
+        .await
+    }
+
+    pub(crate) async fn resolve_struct_on(
+        &self,
+        snapshot: Option<&Client>,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let format = self.inner.struct_formats.get(flag_key);
+        self.evaluate(
+            snapshot,
+            flag_key,
+            evaluation_context,
+            None,
+            String::default(),
+            |details, _| to_struct_details(details, format),
+        )
+        .await
+    }
+
+    pub(crate) async fn evaluate<T, R, F>(
+        &self,
+        snapshot: Option<&Client>,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+        explicit_user: Option<&User>,
+        default: T,
+        convert: F,
+    ) -> EvaluationResult<ResolutionDetails<R>>
+    where
+        T: ValuePrimitive + Clone + Default + Display,
+        R: Clone + Into<Value> + FromValue,
+        F: FnOnce(
+            &configcat::EvaluationDetails<T>,
+            Option<&User>,
+        ) -> EvaluationResult<ResolutionDetails<R>>,
+    {
+        let flag_key = self.resolve_key(flag_key);
+        let flag_key = flag_key.as_ref();
+        let rewritten_context;
+        let evaluation_context = if self.inner.before.is_empty() {
+            evaluation_context
+        } else {
+            let mut context = evaluation_context.clone();
+            for before in &self.inner.before {
+                before(flag_key, &mut context);
+            }
+            rewritten_context = context;
+            &rewritten_context
+        };
+        let mut stale = false;
+        let client = if let Some(client) = snapshot {
+            client
+        } else {
+            stale = self.inner.source.prepare().await;
+            &self.inner.source.client
+        };
+        let mut fetch_time = None;
+        let user = match explicit_user {
+            Some(user) => Ok(Some(user.clone())),
+            None => to_user(evaluation_context),
+        };
+        let mut result = match user {
+            Ok(user) => {
+                #[cfg(feature = "testing")]
+                let overridden = self.inner.overrides.details(flag_key);
+                #[cfg(not(feature = "testing"))]
+                let overridden = None;
+                let template_user = self.template_user(user.as_ref());
+                let details = match overridden {
+                    Some(details) => details,
+                    None => client.get_value_details(flag_key, default, user).await,
+                };
+                fetch_time = details.fetch_time;
+                if read(&self.inner.debug_flags).contains(flag_key) {
+                    info!("{}", debug::trace(&details));
+                }
+                let result = convert(&details, template_user.as_ref());
+                if !self.inner.on_evaluated.is_empty() {
+                    let details = to_raw_details(details);
+                    for on_evaluated in &self.inner.on_evaluated {
+                        on_evaluated(flag_key, &details);
+                    }
+                }
+                result
+            }
+            Err(err) => Err(err),
+        };
+        if let (true, Ok(details)) = (stale, result.as_mut()) {
+            details.reason = Some(EvaluationReason::Other(STALE_REASON.to_owned()));
+        }
+        if !self.inner.after.is_empty() {
+            result = self.post_process(flag_key, result);
+        }
+        let value = match (self.inner.stats.counts_values(), result.as_ref()) {
+            (true, Ok(details)) => Some(to_json(&details.value.clone().into()).to_string()),
+            _ => None,
+        };
+        self.inner
+            .stats
+            .record(flag_key, value, result.as_ref().err(), fetch_time);
+        if let (Some(exposures), Some(targeting_key), Ok(details)) = (
+            self.inner.exposures.as_ref(),
+            evaluation_context.targeting_key.as_deref(),
+            result.as_ref(),
+        ) {
+            if let Some(variant) = details.variant.as_deref() {
+                exposures.record(targeting_key, flag_key, variant);
+            }
+        }
+        if !self.inner.sinks.is_empty() {
+            let event = EvaluationEvent::new(flag_key, evaluation_context, &result, fetch_time);
+            for sink in &self.inner.sinks {
+                sink.record(&event);
+            }
+        }
+        result
+    }
+
+    /// Returns the key of the flag to evaluate for a requested flag key, by resolving the
+    /// aliases and prepending the key prefix.
+    pub(crate) fn resolve_key<'a>(&'a self, flag_key: &'a str) -> Cow<'a, str> {
+        let flag_key = match self.inner.aliases.get(flag_key) {
+            Some(target) => {
+                if !read(&self.inner.used_aliases).contains(flag_key)
+                    && write(&self.inner.used_aliases).insert(flag_key.to_owned())
+                {
+                    warn!("The '{flag_key}' flag key is deprecated, use '{target}' instead.");
+                }
+                target.as_str()
+            }
+            None => flag_key,
+        };
+        if self.inner.key_prefix.is_empty() {
+            Cow::Borrowed(flag_key)
+        } else {
+            Cow::Owned(format!("{}{flag_key}", self.inner.key_prefix))
+        }
+    }
+
+    /// Returns the requested flag key for a flag key of the config JSON, or `None` when the
+    /// flag isn't under the key prefix.
+    pub(crate) fn strip_key_prefix(&self, flag_key: String) -> Option<String> {
+        if self.inner.key_prefix.is_empty() {
+            return Some(flag_key);
+        }
+        flag_key
+            .strip_prefix(self.inner.key_prefix.as_str())
+            .map(str::to_owned)
+    }
+
+    /// Converts the details of a text setting, and processes its value with
+    /// [`ConfigCatProvider::process_string`].
+    pub(crate) fn to_string_details(
+        &self,
+        flag_key: &str,
+        details: &configcat::EvaluationDetails<String>,
+        user: Option<&User>,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        let mut result = to_res_details(details);
+        if let Ok(details) = result.as_mut() {
+            details.value = self.process_string(flag_key, std::mem::take(&mut details.value), user);
+        }
+        result
+    }
+
+    /// Applies [`ConfigCatProvider::process_string`] to the value of a bulk evaluation when
+    /// it's a text setting.
+    fn process_value(
+        &self,
+        flag_key: &str,
+        mut details: ResolutionDetails<Value>,
+        user: Option<&User>,
+    ) -> ResolutionDetails<Value> {
+        if let Value::String(value) = &mut details.value {
+            *value = self.process_string(flag_key, std::mem::take(value), user);
+        }
+        details
+    }
+
+    /// Substitutes the placeholders of a text setting's value when enabled with
+    /// [`ConfigCatProviderBuilder::interpolate_strings`], then applies the configured
+    /// transformations.
+    fn process_string(&self, flag_key: &str, value: String, user: Option<&User>) -> String {
+        let value = if self.inner.interpolate_strings {
+            template::interpolate(value.as_str(), user)
+        } else {
+            value
+        };
+        self.inner.string_transforms.apply(flag_key, value)
+    }
+
+    /// Returns a copy of the evaluated user for the placeholder substitution. The user is only
+    /// kept after the evaluation when the text settings are interpolated with its attributes.
+    fn template_user(&self, user: Option<&User>) -> Option<User> {
+        if self.inner.interpolate_strings {
+            user.cloned()
+        } else {
+            None
         }
     }
+
+    fn post_process<R>(
+        &self,
+        flag_key: &str,
+        result: EvaluationResult<ResolutionDetails<R>>,
+    ) -> EvaluationResult<ResolutionDetails<R>>
+    where
+        R: Into<Value> + FromValue,
+    {
+        let mut result = result.map(to_value_details);
+        for after in &self.inner.after {
+            after(flag_key, &mut result);
+        }
+        result.and_then(|details| {
+            from_value_details(details).ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!(
+                        "The post-processed value of '{flag_key}' has a different type than the requested one."
+                    ))
+                    .build()
+            })
+        })
+    }
 }
 
 #[async_trait]
 impl FeatureProvider for ConfigCatProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) {
+        let init_timeout = self.inner.init_timeout;
+        let source = self.inner.source.clone();
+        runtime::run(async move {
+            let prepare = source.refresher.prepare(&source.client);
+            if tokio::time::timeout(init_timeout, prepare).await.is_err() {
+                report_lifecycle_error(
+                    format!("ConfigCat provider initialization timed out after {init_timeout:?}.")
+                        .as_str(),
+                );
+                return;
+            }
+            match source.client.wait_for_ready(init_timeout).await {
+                Ok(ClientCacheState::NoFlagData) => report_lifecycle_error(
+                    "ConfigCat provider initialization finished without flag data.",
+                ),
+                Ok(_) => {}
+                Err(err) => report_lifecycle_error(
+                    format!("ConfigCat provider initialization failed. ({err})").as_str(),
+                ),
+            }
+        })
+        .await;
+        self.check_schema().await;
+    }
+
     fn metadata(&self) -> &ProviderMetadata {
-        &self.provider_metadata
+        &self.inner.provider_metadata
     }
 
     async fn resolve_bool_value(
@@ -83,13 +1107,8 @@ impl FeatureProvider for ConfigCatProvider {
         flag_key: &str,
         evaluation_context: &EvaluationContext,
     ) -> EvaluationResult<ResolutionDetails<bool>> {
-        match to_user(evaluation_context) {
-            Ok(user) => {
-                let details = self.client.get_value_details(flag_key, false, user).await;
-                to_res_details(&details)
-            }
-            Err(err) => Err(err),
-        }
+        self.resolve_bool_on(None, flag_key, evaluation_context)
+            .await
     }
 
     async fn resolve_int_value(
@@ -97,13 +1116,8 @@ impl FeatureProvider for ConfigCatProvider {
         flag_key: &str,
         evaluation_context: &EvaluationContext,
     ) -> EvaluationResult<ResolutionDetails<i64>> {
-        match to_user(evaluation_context) {
-            Ok(user) => {
-                let details = self.client.get_value_details(flag_key, 0, user).await;
-                to_res_details(&details)
-            }
-            Err(err) => Err(err),
-        }
+        self.resolve_int_on(None, flag_key, evaluation_context)
+            .await
     }
 
     async fn resolve_float_value(
@@ -111,13 +1125,8 @@ impl FeatureProvider for ConfigCatProvider {
         flag_key: &str,
         evaluation_context: &EvaluationContext,
     ) -> EvaluationResult<ResolutionDetails<f64>> {
-        match to_user(evaluation_context) {
-            Ok(user) => {
-                let details = self.client.get_value_details(flag_key, 0.0, user).await;
-                to_res_details(&details)
-            }
-            Err(err) => Err(err),
-        }
+        self.resolve_float_on(None, flag_key, evaluation_context)
+            .await
     }
 
     async fn resolve_string_value(
@@ -125,16 +1134,8 @@ impl FeatureProvider for ConfigCatProvider {
         flag_key: &str,
         evaluation_context: &EvaluationContext,
     ) -> EvaluationResult<ResolutionDetails<String>> {
-        match to_user(evaluation_context) {
-            Ok(user) => {
-                let details = self
-                    .client
-                    .get_value_details(flag_key, String::default(), user)
-                    .await;
-                to_res_details(&details)
-            }
-            Err(err) => Err(err),
-        }
+        self.resolve_string_on(None, flag_key, evaluation_context)
+            .await
     }
 
     async fn resolve_struct_value(
@@ -142,30 +1143,93 @@ impl FeatureProvider for ConfigCatProvider {
         flag_key: &str,
         evaluation_context: &EvaluationContext,
     ) -> EvaluationResult<ResolutionDetails<StructValue>> {
-        match to_user(evaluation_context) {
-            Ok(user) => {
-                let details = self
-                    .client
-                    .get_value_details(flag_key, String::default(), user)
-                    .await;
-                to_struct_details(&details)
+        self.resolve_struct_on(None, flag_key, evaluation_context)
+            .await
+    }
+}
+
+/// Validates the config JSON against the flag schema each time it changes, until the provider
+/// is dropped.
+fn watch_schema(mut versions: watch::Receiver<u64>, inner: Weak<Inner>) {
+    runtime::spawn(async move {
+        while versions.changed().await.is_ok() {
+            versions.borrow_and_update();
+            let Some(inner) = inner.upgrade() else {
+                return;
+            };
+            ConfigCatProvider { inner }.check_schema().await;
+        }
+    });
+}
+
+/// Invokes the callbacks each time the config JSON changes after its initial load, until the
+/// config source is dropped.
+fn notify_config_changes(
+    tap: Weak<ConfigTap>,
+    key_prefix: String,
+    callbacks: Vec<Box<ConfigChangedFn>>,
+    flag_callbacks: Vec<(String, Box<FlagsChangedFn>)>,
+) {
+    let Some(mut versions) = tap.upgrade().map(|tap| tap.subscribe()) else {
+        return;
+    };
+    // Checked before spawning, so a change made before the task first runs isn't taken for
+    // the initial load.
+    let mut loaded = *versions.borrow_and_update() > 0;
+    let mut previous = latest_config(&tap);
+    runtime::spawn(async move {
+        while versions.changed().await.is_ok() {
+            versions.borrow_and_update();
+            let current = latest_config(&tap);
+            if loaded {
+                for callback in &callbacks {
+                    callback();
+                }
+                let change = change::diff(&previous, &current, &key_prefix);
+                for (prefix, callback) in &flag_callbacks {
+                    let filtered;
+                    let change = if prefix.is_empty() {
+                        &change
+                    } else {
+                        filtered = change.with_prefix(prefix);
+                        &filtered
+                    };
+                    if !change.is_empty() {
+                        callback(change);
+                    }
+                }
             }
-            Err(err) => Err(err),
+            previous = current;
+            loaded = true;
         }
-    }
+    });
+}
+
+/// Parses the latest config JSON observed by the tap.
+fn latest_config(tap: &Weak<ConfigTap>) -> serde_json::Map<String, serde_json::Value> {
+    tap.upgrade()
+        .and_then(|tap| tap.latest())
+        .map(|latest| change::parse(latest.config_json()))
+        .unwrap_or_default()
+}
+
+fn report_lifecycle_error(message: &str) {
+    warn!("{message}");
+    #[cfg(feature = "sentry")]
+    crate::sentry::capture_lifecycle_error(message);
 }
 
-fn to_user(ctx: &EvaluationContext) -> Result<Option<User>, EvaluationError> {
+pub(crate) fn to_user(ctx: &EvaluationContext) -> Result<Option<User>, EvaluationError> {
     if ctx.targeting_key.is_none() && ctx.custom_fields.is_empty() {
         return Ok(None);
     }
-    let identifier = match ctx.targeting_key.as_ref() {
-        Some(id) => id,
-        None => "",
+    let mut user = match ctx.custom_fields.get(USER_CONTEXT_KEY) {
+        Some(prebuilt) => prebuilt_user(prebuilt)?,
+        None => User::new(ctx.targeting_key.as_deref().unwrap_or_default()),
     };
-    let mut user = User::new(identifier);
     for (key, attr) in &ctx.custom_fields {
         match key.as_str() {
+            USER_CONTEXT_KEY => {}
             User::EMAIL => {
                 if let Some(email) = attr.as_str() {
                     user = user.email(email);
@@ -193,6 +1257,21 @@ fn to_user(ctx: &EvaluationContext) -> Result<Option<User>, EvaluationError> {
     Ok(Some(user))
 }
 
+fn prebuilt_user(value: &EvaluationContextFieldValue) -> Result<User, EvaluationError> {
+    match value {
+        EvaluationContextFieldValue::Struct(value) => value.downcast_ref::<User>().cloned(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        EvaluationError::builder()
+            .code(EvaluationErrorCode::InvalidContext)
+            .message(format!(
+                "{USER_CONTEXT_KEY} context attribute has to hold a ConfigCat User Object."
+            ))
+            .build()
+    })
+}
+
 fn to_user_value(val: &EvaluationContextFieldValue) -> Option<UserValue> {
     match val {
         EvaluationContextFieldValue::Bool(val) => Some(UserValue::String(val.to_string())),
@@ -204,7 +1283,7 @@ fn to_user_value(val: &EvaluationContextFieldValue) -> Option<UserValue> {
     }
 }
 
-fn to_res_details<T: Clone>(
+pub(crate) fn to_res_details<T: Clone>(
     details: &configcat::EvaluationDetails<T>,
 ) -> EvaluationResult<ResolutionDetails<T>> {
     if let Some(err) = &details.error {
@@ -215,44 +1294,106 @@ fn to_res_details<T: Clone>(
         value: details.value.clone(),
         reason: Some(reason),
         variant: details.variation_id.clone(),
-        flag_metadata: None,
+        flag_metadata: to_flag_metadata(details),
     })
 }
 
-fn to_struct_details(
+pub(crate) fn to_struct_details(
     details: &configcat::EvaluationDetails<String>,
+    format: StructFormat,
 ) -> EvaluationResult<ResolutionDetails<StructValue>> {
     if let Some(err) = &details.error {
         return Err(to_res_error(err));
     }
-    let json_val: serde_json::Value = match serde_json::from_str(details.value.as_str()) {
+    let json_val = match format::parse(details.value.as_str(), format) {
         Ok(val) => val,
-        Err(err) => {
+        Err(message) => {
             return Err(EvaluationError::builder()
                 .code(EvaluationErrorCode::ParseError)
-                .message(format!("Failed to parse JSON from evaluated string: {err}"))
+                .message(message)
                 .build())
         }
     };
-    let val: Value = match json_val.try_into() {
-        Ok(val) => val,
-        Err(err) => return Err(err),
-    };
-    return match val.as_struct() {
+    let val: Value = json_val.try_into()?;
+    match val.as_struct() {
         Some(struct_val) => {
             let reason = construct_reason(details);
             Ok(ResolutionDetails {
                 value: struct_val.clone(),
                 reason: Some(reason),
                 variant: details.variation_id.clone(),
-                flag_metadata: None,
+                flag_metadata: to_flag_metadata(details),
             })
         }
         None => Err(EvaluationError::builder()
             .code(EvaluationErrorCode::TypeMismatch)
             .message("Parsed value is not a StructValue")
             .build()),
+    }
+}
+
+fn to_value_resolution(
+    details: configcat::EvaluationDetails<Option<configcat::Value>>,
+    stale: bool,
+) -> EvaluationResult<ResolutionDetails<Value>> {
+    if let Some(err) = &details.error {
+        return Err(to_res_error(err));
+    }
+    let reason = if stale {
+        EvaluationReason::Other(STALE_REASON.to_owned())
+    } else {
+        construct_reason(&details)
     };
+    let flag_metadata = to_flag_metadata(&details);
+    let value = details.value.map(from_sdk_value).ok_or_else(|| {
+        EvaluationError::builder()
+            .code(EvaluationErrorCode::General("Provider error".to_owned()))
+            .message(format!("The value of '{}' is missing.", details.key))
+            .build()
+    })?;
+    Ok(ResolutionDetails {
+        value,
+        reason: Some(reason),
+        variant: details.variation_id,
+        flag_metadata,
+    })
+}
+
+fn to_raw_details<T: Into<configcat::Value>>(
+    details: configcat::EvaluationDetails<T>,
+) -> configcat::EvaluationDetails<configcat::Value> {
+    configcat::EvaluationDetails {
+        value: details.value.into(),
+        key: details.key,
+        is_default_value: details.is_default_value,
+        variation_id: details.variation_id,
+        user: details.user,
+        error: details.error,
+        fetch_time: details.fetch_time,
+        matched_targeting_rule: details.matched_targeting_rule,
+        matched_percentage_option: details.matched_percentage_option,
+    }
+}
+
+fn to_flag_metadata<T>(details: &configcat::EvaluationDetails<T>) -> Option<FlagMetadata> {
+    let mut metadata = FlagMetadata::default();
+    if let Some(variation_id) = details.variation_id.as_ref() {
+        metadata.add_value(VARIATION_ID_METADATA_KEY, variation_id.as_str());
+    }
+    if let Some(rule) = details.matched_targeting_rule.as_ref() {
+        metadata.add_value(
+            MATCHED_TARGETING_RULE_METADATA_KEY,
+            debug::describe_rule(rule),
+        );
+    }
+    if let Some(option) = details.matched_percentage_option.as_ref() {
+        metadata.add_value(MATCHED_PERCENTAGE_OPTION_METADATA_KEY, option.percentage);
+    }
+    if metadata.values.is_empty() {
+        None
+    } else {
+        Some(metadata)
+    }
 }
 
 fn to_res_error(err: &ClientError) -> EvaluationError {
@@ -282,3 +1423,32 @@ fn construct_reason<T>(details: &configcat::EvaluationDetails<T>) -> EvaluationR
     }
     EvaluationReason::Default
 }
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Inserts a value into a JSON object at the given dot separated path.
+fn insert_path(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    value: serde_json::Value,
+) {
+    let Some((head, rest)) = path.split_once('.') else {
+        object.insert(path.to_owned(), value);
+        return;
+    };
+    let child = object
+        .entry(head)
+        .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+    if !child.is_object() {
+        *child = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(child) = child {
+        insert_path(child, rest, value);
+    }
+}
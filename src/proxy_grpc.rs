@@ -0,0 +1,80 @@
+use crate::runtime;
+use crate::source::ConfigSource;
+use log::warn;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+use tonic_prost::ProstCodec;
+
+/// The method of the ConfigCat Proxy's `FlagService` streaming the evaluated values of all flags.
+const EVAL_ALL_FLAGS_STREAM: &str = "/configcat.FlagService/EvalAllFlagsStream";
+
+/// The delay before reconnecting to the ConfigCat Proxy after the stream was closed.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// The `EvalRequest` message of the ConfigCat Proxy's `FlagService`, without user attributes.
+#[derive(Clone, PartialEq, prost::Message)]
+struct EvalRequest {
+    #[prost(string, tag = "1")]
+    sdk_id: String,
+    #[prost(string, tag = "2")]
+    key: String,
+}
+
+/// The `EvalAllResponse` message of the ConfigCat Proxy's `FlagService`. Only its arrival
+/// matters, so its fields are skipped.
+#[derive(Clone, PartialEq, prost::Message)]
+struct EvalAllResponse {}
+
+/// Subscribes to the flag value stream of a ConfigCat Proxy over gRPC, and refreshes the config
+/// JSON of the source on each update, until all providers using the source are dropped.
+pub(crate) fn subscribe(source: &Arc<ConfigSource>, channel: Channel, sdk_id: String) {
+    let source = Arc::downgrade(source);
+    runtime::spawn(async move {
+        loop {
+            let result = stream_updates(&source, channel.clone(), sdk_id.as_str()).await;
+            if source.strong_count() == 0 {
+                return;
+            }
+            let reason = result.err().map_or_else(
+                || "the stream was closed".to_owned(),
+                |status| status.message().to_owned(),
+            );
+            warn!("The ConfigCat Proxy gRPC stream was interrupted, reconnecting in {RECONNECT_DELAY:?}. ({reason})");
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+}
+
+async fn stream_updates(
+    source: &Weak<ConfigSource>,
+    channel: Channel,
+    sdk_id: &str,
+) -> Result<(), Status> {
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready()
+        .await
+        .map_err(|err| Status::unavailable(err.to_string()))?;
+    let request = EvalRequest {
+        sdk_id: sdk_id.to_owned(),
+        key: String::new(),
+    };
+    let mut updates = grpc
+        .server_streaming::<_, EvalAllResponse, _>(
+            Request::new(request),
+            PathAndQuery::from_static(EVAL_ALL_FLAGS_STREAM),
+            ProstCodec::default(),
+        )
+        .await?
+        .into_inner();
+    while updates.message().await?.is_some() {
+        let Some(source) = source.upgrade() else {
+            return Ok(());
+        };
+        // The failures are tracked in the fetch metrics, and the next update retries.
+        _ = source.refresher.refresh(&source.client).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::runtime;
+use crate::source::ConfigSource;
+use log::warn;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+/// The Base64 encoded `{}` request data of the stream, which evaluates the flags without user
+/// attributes.
+const EMPTY_REQUEST: &str = "e30=";
+
+/// The delay before reconnecting to the ConfigCat Proxy after the stream was closed.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Subscribes to the server-sent events stream of a ConfigCat Proxy, and refreshes the config
+/// JSON of the source on each event, until all providers using the source are dropped.
+pub(crate) fn subscribe(source: &Arc<ConfigSource>, http: reqwest::Client, url: String) {
+    let source = Arc::downgrade(source);
+    runtime::spawn(async move {
+        loop {
+            let result = stream_events(&source, &http, url.as_str()).await;
+            if source.strong_count() == 0 {
+                return;
+            }
+            let reason = result
+                .err()
+                .unwrap_or_else(|| "the stream was closed".to_owned());
+            warn!("The ConfigCat Proxy event stream was interrupted, reconnecting in {RECONNECT_DELAY:?}. ({reason})");
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+}
+
+/// Returns the URL of the stream of all flag values evaluated by the ConfigCat Proxy.
+pub(crate) fn stream_url(proxy_url: &str, sdk_id: &str) -> String {
+    format!(
+        "{}/sse/{sdk_id}/eval-all/{EMPTY_REQUEST}",
+        proxy_url.trim_end_matches('/')
+    )
+}
+
+async fn stream_events(
+    source: &Weak<ConfigSource>,
+    http: &reqwest::Client,
+    url: &str,
+) -> Result<(), String> {
+    let mut response = http
+        .get(url)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|err| err.to_string())?;
+    let mut events = EventParser::default();
+    while let Some(chunk) = response.chunk().await.map_err(|err| err.to_string())? {
+        for _ in 0..events.push(chunk.as_ref()) {
+            let Some(source) = source.upgrade() else {
+                return Ok(());
+            };
+            // The failures are tracked in the fetch metrics, and the next event retries.
+            _ = source.refresher.refresh(&source.client).await;
+        }
+    }
+    Ok(())
+}
+
+/// Splits the received bytes into server-sent events.
+#[derive(Default)]
+struct EventParser {
+    line: Vec<u8>,
+    has_data: bool,
+}
+
+impl EventParser {
+    /// Processes the next chunk of the stream, and returns the number of completed events with
+    /// data.
+    fn push(&mut self, chunk: &[u8]) -> usize {
+        let mut events = 0;
+        for &byte in chunk {
+            if byte != b'\n' {
+                self.line.push(byte);
+                continue;
+            }
+            if self.line.last() == Some(&b'\r') {
+                self.line.pop();
+            }
+            if self.line.is_empty() {
+                // An empty line dispatches the event.
+                if std::mem::take(&mut self.has_data) {
+                    events += 1;
+                }
+            } else if self.line.starts_with(b"data:") {
+                self.has_data = true;
+            }
+            self.line.clear();
+        }
+        events
+    }
+}
@@ -0,0 +1,100 @@
+use crate::cache::ProviderCache;
+use async_trait::async_trait;
+use log::warn;
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, RedisError};
+use std::time::Duration;
+
+const DEFAULT_TTL: Duration = Duration::from_hours(24);
+
+/// A [`ProviderCache`] that stores the config JSON in Redis.
+///
+/// Horizontally scaled services sharing the same Redis instance download the config JSON
+/// from the ConfigCat CDN only once per poll interval instead of once per instance. Redis
+/// errors are logged and treated as cache misses, so the provider falls back to fetching.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, RedisCache};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let cache = RedisCache::new("redis://127.0.0.1/").await.unwrap();
+///     let provider = ConfigCatProvider::builder("sdk-key")
+///         .cache(cache)
+///         .build()
+///         .unwrap();
+/// }
+/// ```
+pub struct RedisCache {
+    connection: ConnectionManager,
+    ttl: Duration,
+    key_prefix: String,
+}
+
+impl RedisCache {
+    /// Connects to the Redis server at the given URL.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the URL is invalid or the connection can't be established.
+    pub async fn new(url: &str) -> Result<Self, RedisError> {
+        let client = redis::Client::open(url)?;
+        Ok(Self::with_connection(ConnectionManager::new(client).await?))
+    }
+
+    /// Creates a new [`RedisCache`] that uses an existing connection.
+    pub fn with_connection(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            ttl: DEFAULT_TTL,
+            key_prefix: String::new(),
+        }
+    }
+
+    /// Sets how long the config JSON is kept in Redis after it was written.
+    ///
+    /// It's rounded up to whole seconds. Default is 24 hours.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets a prefix prepended to the keys written to Redis.
+    ///
+    /// Default is no prefix.
+    pub fn key_prefix(mut self, key_prefix: &str) -> Self {
+        key_prefix.clone_into(&mut self.key_prefix);
+        self
+    }
+
+    fn redis_key(&self, key: &str) -> String {
+        format!("{}{key}", self.key_prefix)
+    }
+}
+
+#[async_trait]
+impl ProviderCache for RedisCache {
+    async fn read(&self, key: &str) -> Option<String> {
+        let mut connection = self.connection.clone();
+        match connection.get(self.redis_key(key)).await {
+            Ok(value) => value,
+            Err(err) => {
+                warn!("Failed to read the config JSON from Redis. ({err})");
+                None
+            }
+        }
+    }
+
+    async fn write(&self, key: &str, value: &str) {
+        let mut connection = self.connection.clone();
+        let seconds = self.ttl.as_secs() + u64::from(self.ttl.subsec_nanos() > 0);
+        let result: Result<(), RedisError> = connection
+            .set_ex(self.redis_key(key), value, seconds.max(1))
+            .await;
+        if let Err(err) = result {
+            warn!("Failed to write the config JSON to Redis. ({err})");
+        }
+    }
+}
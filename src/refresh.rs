@@ -0,0 +1,375 @@
+use crate::cache::CacheBridge;
+use crate::download::Downloader;
+use chrono::{DateTime, Utc};
+use configcat::{Client, ClientError, ErrorKind};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// The default limit of how long the evaluations wait for the first fetch in auto polling mode.
+pub(crate) const DEFAULT_MAX_INIT_WAIT: Duration = Duration::from_secs(5);
+
+/// A point-in-time snapshot of the config JSON fetch health of a [`crate::ConfigCatProvider`].
+///
+/// It covers the fetches initiated by the provider: the background polls in auto polling
+/// mode, the fetches triggered by expired config JSON in lazy loading and stale-while-revalidate
+/// mode, the fetch of the first evaluation in serverless mode, the fetches triggered by
+/// ConfigCat Proxy updates, and the explicit [`crate::ConfigCatProvider::refresh`] calls. When a [`crate::ProviderCache`] is configured,
+/// scheduled fetches served by a fresh cache entry count as successful fetches.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FetchMetrics {
+    /// The number of config JSON fetch attempts.
+    pub attempts: u64,
+    /// The number of successful config JSON fetches.
+    pub successes: u64,
+    /// The number of failed config JSON fetches.
+    pub failures: u64,
+    /// The time of the last successful config JSON fetch.
+    pub last_success: Option<DateTime<Utc>>,
+    /// The error message of the last failed config JSON fetch.
+    pub last_failure: Option<String>,
+    /// The number of seconds elapsed since the last successful config JSON fetch.
+    ///
+    /// It's `None` when there wasn't any successful fetch yet. A steadily growing value
+    /// indicates that polling is stuck and the provider evaluates stale flag data.
+    pub seconds_since_last_success: Option<f64>,
+}
+
+/// Determines when the provider fetches the config JSON.
+pub(crate) enum RefreshMode {
+    /// Fetches in the background with the given interval.
+    Poll(Duration),
+    /// Fetches on evaluation when the last successful fetch is older than the given TTL.
+    Lazy(Duration),
+    /// Serves the cached config JSON immediately, and fetches in the background when the last
+    /// successful fetch is older than the given TTL.
+    Revalidate(Duration),
+    /// Fetches only on explicit refresh calls.
+    Manual,
+    /// Fetches on the first evaluation (unless the cached config JSON is younger than the given
+    /// TTL), then only on explicit refresh calls. The fetches and the cache writes are awaited
+    /// with the given timeout, without background tasks.
+    Serverless { ttl: Duration, timeout: Duration },
+}
+
+#[derive(Default)]
+struct FetchState {
+    last_success: Option<(Instant, DateTime<Utc>)>,
+    last_failure: Option<String>,
+}
+
+/// Performs the config JSON fetches of the provider and tracks their outcome.
+pub(crate) struct Refresher {
+    mode: RefreshMode,
+    cache: Option<Arc<CacheBridge>>,
+    fetch_lock: tokio::sync::Mutex<()>,
+    attempts: AtomicU64,
+    successes: AtomicU64,
+    failures: AtomicU64,
+    state: Mutex<FetchState>,
+    ready: watch::Sender<bool>,
+    revalidating: AtomicBool,
+    key_rejected: AtomicBool,
+    refresh_interval: Option<Duration>,
+    last_refresh: Mutex<Option<RefreshOutcome>>,
+    downloader: Option<Downloader>,
+    max_init_wait: Duration,
+}
+
+/// The start time and the result of the last explicit refresh, shared with the refresh calls
+/// coalesced into it.
+struct RefreshOutcome {
+    started: Instant,
+    result: Result<(), (ErrorKind, String)>,
+}
+
+impl Refresher {
+    pub(crate) fn new(mode: RefreshMode, cache: Option<Arc<CacheBridge>>) -> Self {
+        let (ready, _) = watch::channel(!matches!(mode, RefreshMode::Poll(_)));
+        Self {
+            mode,
+            cache,
+            fetch_lock: tokio::sync::Mutex::new(()),
+            attempts: AtomicU64::new(0),
+            successes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            state: Mutex::new(FetchState::default()),
+            ready,
+            revalidating: AtomicBool::new(false),
+            key_rejected: AtomicBool::new(false),
+            refresh_interval: None,
+            last_refresh: Mutex::new(None),
+            downloader: None,
+            max_init_wait: DEFAULT_MAX_INIT_WAIT,
+        }
+    }
+
+    /// Limits the explicit refreshes to one per the given interval. The calls made within the
+    /// interval wait for its end, and are coalesced into one refresh.
+    pub(crate) fn with_refresh_interval(mut self, interval: Option<Duration>) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Limits how long the evaluations wait for the first fetch in auto polling mode. When the
+    /// wait elapses, they are served from the cached config JSON or the default values.
+    pub(crate) fn with_max_init_wait(mut self, wait: Duration) -> Self {
+        self.max_init_wait = wait;
+        self
+    }
+
+    /// Downloads the config JSON with the given [`Downloader`] instead of the SDK client.
+    pub(crate) fn with_downloader(mut self, downloader: Option<Downloader>) -> Self {
+        self.downloader = downloader;
+        self
+    }
+
+    pub(crate) fn poll_interval(&self) -> Option<Duration> {
+        match self.mode {
+            RefreshMode::Poll(interval) => Some(interval),
+            _ => None,
+        }
+    }
+
+    /// Fetches the latest config JSON.
+    pub(crate) async fn refresh(&self, client: &Client) -> Result<(), ClientError> {
+        let Some(interval) = self.refresh_interval else {
+            let _guard = self.fetch_lock.lock().await;
+            return self.force_fetch(client).await;
+        };
+        let requested = Instant::now();
+        let wait = self
+            .last_refresh
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|last| interval.saturating_sub(last.started.elapsed()));
+        if let Some(wait) = wait.filter(|wait| !wait.is_zero()) {
+            tokio::time::sleep(wait).await;
+        }
+        let _guard = self.fetch_lock.lock().await;
+        if let Some(last) = self
+            .last_refresh
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|last| last.started >= requested)
+        {
+            // A refresh started after this call was made, so its result is up-to-date.
+            return last
+                .result
+                .clone()
+                .map_err(|(kind, message)| ClientError { kind, message });
+        }
+        let started = Instant::now();
+        let result = self.force_fetch(client).await;
+        *self
+            .last_refresh
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(RefreshOutcome {
+            started,
+            result: result
+                .as_ref()
+                .map_err(|err| (err.kind, err.message.clone()))
+                .copied(),
+        });
+        result
+    }
+
+    async fn force_fetch(&self, client: &Client) -> Result<(), ClientError> {
+        match self.mode {
+            RefreshMode::Serverless { timeout, .. } => {
+                self.fetch_within(client, Duration::ZERO, timeout).await
+            }
+            _ => self.fetch(client).await,
+        }
+    }
+
+    /// Performs a scheduled background fetch.
+    pub(crate) async fn poll(&self, client: &Client) {
+        if self.is_offline(client) {
+            self.ready.send_replace(true);
+            return;
+        }
+        let _guard = self.fetch_lock.lock().await;
+        if let RefreshMode::Poll(interval) = self.mode {
+            _ = self.fetch_or_load(client, interval).await;
+        }
+    }
+
+    /// Makes sure that the config JSON is available and up-to-date according to the refresh mode
+    /// before an evaluation.
+    ///
+    /// Returns `true` when the evaluation is served from stale config JSON, and the caller has to
+    /// initiate the background revalidation with [`Refresher::revalidate`].
+    pub(crate) async fn prepare(&self, client: &Client) -> bool {
+        match self.mode {
+            RefreshMode::Poll(_) => {
+                let mut ready = self.ready.subscribe();
+                _ = tokio::time::timeout(self.max_init_wait, async {
+                    while !*ready.borrow_and_update() {
+                        if ready.changed().await.is_err() {
+                            break;
+                        }
+                    }
+                })
+                .await;
+                false
+            }
+            RefreshMode::Lazy(ttl) => {
+                if self.is_offline(client) || !self.expired(ttl) {
+                    return false;
+                }
+                let _guard = self.fetch_lock.lock().await;
+                if self.expired(ttl) {
+                    _ = self.fetch_or_load(client, ttl).await;
+                }
+                false
+            }
+            RefreshMode::Revalidate(ttl) => {
+                if self.is_offline(client) || !self.expired(ttl) {
+                    return false;
+                }
+                if self.has_succeeded() {
+                    return self
+                        .revalidating
+                        .compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed)
+                        .is_ok();
+                }
+                // There's nothing to serve yet, so the first fetch blocks the evaluation.
+                let _guard = self.fetch_lock.lock().await;
+                if self.expired(ttl) {
+                    _ = self.fetch_or_load(client, ttl).await;
+                }
+                false
+            }
+            RefreshMode::Serverless { ttl, timeout } => {
+                if self.is_offline(client) || self.attempts.load(Ordering::Relaxed) > 0 {
+                    return false;
+                }
+                let _guard = self.fetch_lock.lock().await;
+                if self.attempts.load(Ordering::Relaxed) == 0 {
+                    _ = self.fetch_within(client, ttl, timeout).await;
+                }
+                false
+            }
+            RefreshMode::Manual => false,
+        }
+    }
+
+    /// Performs the background fetch of a stale config JSON requested by [`Refresher::prepare`].
+    pub(crate) async fn revalidate(&self, client: &Client) {
+        if let RefreshMode::Revalidate(ttl) = self.mode {
+            let _guard = self.fetch_lock.lock().await;
+            if self.expired(ttl) {
+                _ = self.fetch_or_load(client, ttl).await;
+            }
+        }
+        self.revalidating.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn metrics(&self) -> FetchMetrics {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        FetchMetrics {
+            attempts: self.attempts.load(Ordering::Relaxed),
+            successes: self.successes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            last_success: state.last_success.map(|(_, time)| time),
+            last_failure: state.last_failure.clone(),
+            seconds_since_last_success: state
+                .last_success
+                .map(|(instant, _)| instant.elapsed().as_secs_f64()),
+        }
+    }
+
+    /// Returns whether the last config JSON fetch was rejected because of an invalid SDK key.
+    pub(crate) fn key_rejected(&self) -> bool {
+        self.key_rejected.load(Ordering::Relaxed)
+    }
+
+    fn is_offline(&self, client: &Client) -> bool {
+        self.downloader
+            .as_ref()
+            .map_or_else(|| client.is_offline(), Downloader::is_offline)
+    }
+
+    fn has_succeeded(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.last_success.is_some()
+    }
+
+    fn expired(&self, ttl: Duration) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state
+            .last_success
+            .is_none_or(|(instant, _)| instant.elapsed() >= ttl)
+    }
+
+    /// Uses the config JSON of the custom cache when it's younger than the given age, otherwise
+    /// fetches the latest one.
+    async fn fetch_or_load(&self, client: &Client, max_age: Duration) -> Result<(), ClientError> {
+        let Some(cache) = self.cache.as_ref() else {
+            return self.fetch(client).await;
+        };
+        if !cache.load(max_age).await {
+            return self.fetch(client).await;
+        }
+        self.record(Ok(()))
+    }
+
+    /// Uses the cached config JSON when it's younger than the given age, otherwise fetches the
+    /// latest one and writes it into the cache, failing when it takes longer than the timeout.
+    async fn fetch_within(
+        &self,
+        client: &Client,
+        max_age: Duration,
+        timeout: Duration,
+    ) -> Result<(), ClientError> {
+        let fetch = async {
+            self.fetch_or_load(client, max_age).await?;
+            if let Some(cache) = self.cache.as_ref() {
+                cache.flush().await;
+            }
+            Ok(())
+        };
+        match tokio::time::timeout(timeout, fetch).await {
+            Ok(result) => result,
+            Err(_) => self.record(Err(ClientError {
+                kind: ErrorKind::HttpRequestTimeout,
+                message: format!("Config JSON fetch timed out after {timeout:?}."),
+            })),
+        }
+    }
+
+    async fn fetch(&self, client: &Client) -> Result<(), ClientError> {
+        let result = match self.downloader.as_ref() {
+            Some(downloader) => downloader.fetch().await,
+            None => client.refresh().await,
+        };
+        self.record(result)
+    }
+
+    fn record(&self, result: Result<(), ClientError>) -> Result<(), ClientError> {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            match &result {
+                Ok(()) => {
+                    self.successes.fetch_add(1, Ordering::Relaxed);
+                    self.key_rejected.store(false, Ordering::Relaxed);
+                    state.last_success = Some((Instant::now(), Utc::now()));
+                }
+                Err(err) => {
+                    self.failures.fetch_add(1, Ordering::Relaxed);
+                    if err.kind == ErrorKind::InvalidSdkKey {
+                        self.key_rejected.store(true, Ordering::Relaxed);
+                    }
+                    state.last_failure = Some(err.message.clone());
+                }
+            }
+        }
+        self.ready.send_replace(true);
+        result
+    }
+}
@@ -0,0 +1,163 @@
+use crate::provider::ConfigCatProvider;
+use async_trait::async_trait;
+use log::warn;
+use open_feature::provider::{FeatureProvider, ProviderMetadata, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationResult, StructValue};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+type SwitchFn = dyn Fn(&str) + Send + Sync;
+
+/// An OpenFeature provider that evaluates with a provider created for the primary SDK key,
+/// and switches to a provider created for the secondary SDK key once the primary one gets
+/// rejected by the ConfigCat CDN (e.g. because it was revoked).
+///
+/// Both providers keep their config JSON up-to-date, so the secondary one is ready to serve
+/// evaluations at the moment of the switch. The switch happens at the first evaluation after a
+/// config JSON fetch of the primary provider fails with an invalid SDK key error, and it's
+/// final: the primary provider isn't used anymore, even if its SDK key gets accepted again.
+/// The switch is logged as a warning, and reported to the callbacks added with
+/// [`KeyRotationProvider::on_switch`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, KeyRotationProvider};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let primary = ConfigCatProvider::builder("old-sdk-key").build().unwrap();
+///     let secondary = ConfigCatProvider::builder("new-sdk-key").build().unwrap();
+///
+///     let provider = KeyRotationProvider::new(primary, secondary).on_switch(|reason| {
+///         println!("switched to the secondary SDK key: {reason}");
+///     });
+///
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(provider).await;
+/// }
+/// ```
+pub struct KeyRotationProvider {
+    metadata: ProviderMetadata,
+    primary: ConfigCatProvider,
+    secondary: ConfigCatProvider,
+    switched: AtomicBool,
+    on_switch: Vec<Box<SwitchFn>>,
+}
+
+impl KeyRotationProvider {
+    /// Creates a new [`KeyRotationProvider`] with the providers of the primary and the secondary
+    /// SDK keys.
+    pub fn new(primary: ConfigCatProvider, secondary: ConfigCatProvider) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("KeyRotationProvider"),
+            primary,
+            secondary,
+            switched: AtomicBool::new(false),
+            on_switch: Vec::new(),
+        }
+    }
+
+    /// Adds a callback invoked with the error message of the rejected config JSON fetch when
+    /// the provider switches to the secondary SDK key.
+    pub fn on_switch<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_switch.push(Box::new(callback));
+        self
+    }
+
+    /// Returns whether the provider switched to the secondary SDK key.
+    pub fn is_switched(&self) -> bool {
+        self.switched.load(Ordering::Acquire)
+    }
+
+    /// Returns the provider serving the evaluations, switching to the secondary one when the
+    /// primary SDK key got rejected.
+    fn active(&self) -> &ConfigCatProvider {
+        if !self.is_switched()
+            && self.primary.source().refresher.key_rejected()
+            && !self.switched.swap(true, Ordering::AcqRel)
+        {
+            let reason = self
+                .primary
+                .fetch_metrics()
+                .last_failure
+                .unwrap_or_default();
+            warn!(
+                "The primary SDK key was rejected, switching to the secondary SDK key. ({reason})"
+            );
+            for callback in &self.on_switch {
+                callback(reason.as_str());
+            }
+        }
+        if self.is_switched() {
+            &self.secondary
+        } else {
+            &self.primary
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for KeyRotationProvider {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        self.primary.initialize(context).await;
+        self.secondary.initialize(context).await;
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.active()
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.active()
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.active()
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.active()
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.active()
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+}
@@ -0,0 +1,61 @@
+use std::future::Future;
+use tokio::task::JoinHandle;
+
+/// Spawns a background task of the provider.
+///
+/// With the `runtime-agnostic` feature, the task runs on the runtime owned by the provider,
+/// otherwise on the Tokio runtime of the caller.
+pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(feature = "runtime-agnostic")]
+    return owned::handle().spawn(future);
+    #[cfg(not(feature = "runtime-agnostic"))]
+    tokio::spawn(future)
+}
+
+/// Runs a future that needs a Tokio runtime, e.g. for timers or the HTTP requests of the
+/// ConfigCat SDK.
+///
+/// With the `runtime-agnostic` feature, the future runs on the runtime owned by the provider,
+/// so it can be awaited from any async runtime. Otherwise, it's simply awaited.
+pub(crate) async fn run<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(feature = "runtime-agnostic")]
+    return match owned::handle().spawn(future).await {
+        Ok(output) => output,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    };
+    #[cfg(not(feature = "runtime-agnostic"))]
+    future.await
+}
+
+#[cfg(feature = "runtime-agnostic")]
+mod owned {
+    use std::sync::LazyLock;
+    use tokio::runtime::Handle;
+
+    /// The runtime shared by the providers, driven by a background thread for the lifetime of
+    /// the process.
+    static HANDLE: LazyLock<Handle> = LazyLock::new(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start the runtime of the ConfigCat provider.");
+        let handle = runtime.handle().clone();
+        std::thread::Builder::new()
+            .name("configcat-runtime".to_owned())
+            .spawn(move || runtime.block_on(std::future::pending::<()>()))
+            .expect("Failed to start the runtime thread of the ConfigCat provider.");
+        handle
+    });
+
+    pub(super) fn handle() -> &'static Handle {
+        &HANDLE
+    }
+}
@@ -0,0 +1,119 @@
+use crate::sink::{EvaluationEvent, EvaluationSink};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+const MIN_RATE_WINDOW: Duration = Duration::from_millis(1);
+
+struct RateWindow {
+    started: Instant,
+    count: u64,
+}
+
+/// An [`EvaluationSink`] wrapper that applies sampling and rate limiting before passing
+/// events to the wrapped sink.
+///
+/// Sampling is applied per flag key: with `every(n)`, only every n-th evaluation of each flag
+/// is forwarded. The rate limit caps the number of forwarded events per second across all flags.
+/// Both can be combined; the rate limit is applied to the sampled events.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use configcat_openfeature_provider::{AuditLog, ConfigCatProvider, SampledSink};
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .sink(
+///         SampledSink::new(AuditLog::new(std::io::stdout()))
+///             .every(100)
+///             .rate_limit(50, Duration::from_secs(1)),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+pub struct SampledSink<S: EvaluationSink> {
+    inner: S,
+    every: u64,
+    limit: Option<(u64, Duration)>,
+    counters: Mutex<HashMap<String, u64>>,
+    window: Mutex<RateWindow>,
+    suppressed: AtomicU64,
+}
+
+impl<S: EvaluationSink> SampledSink<S> {
+    /// Creates a new [`SampledSink`] that forwards every event to the given sink until
+    /// sampling or rate limiting is configured.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            every: 1,
+            limit: None,
+            counters: Mutex::new(HashMap::new()),
+            window: Mutex::new(RateWindow {
+                started: Instant::now(),
+                count: 0,
+            }),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Forwards only every n-th evaluation event of each flag key.
+    ///
+    /// The first evaluation of each flag is always forwarded.
+    pub fn every(mut self, n: u64) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Forwards at most `max_events` events in each time window of the given length.
+    ///
+    /// Windows shorter than 1 millisecond are treated as 1 millisecond.
+    pub fn rate_limit(mut self, max_events: u64, per: Duration) -> Self {
+        self.limit = Some((max_events, per.max(MIN_RATE_WINDOW)));
+        self
+    }
+
+    /// Returns the number of events that were not forwarded due to sampling or rate limiting.
+    pub fn suppressed_events(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    fn sampled(&self, flag_key: &str) -> bool {
+        if self.every == 1 {
+            return true;
+        }
+        let mut counters = self.counters.lock().unwrap_or_else(PoisonError::into_inner);
+        let counter = counters.entry(flag_key.to_owned()).or_insert(0);
+        let sampled = counter.is_multiple_of(self.every);
+        *counter += 1;
+        sampled
+    }
+
+    fn within_limit(&self) -> bool {
+        let Some((max_events, per)) = self.limit else {
+            return true;
+        };
+        let mut window = self.window.lock().unwrap_or_else(PoisonError::into_inner);
+        if window.started.elapsed() >= per {
+            window.started = Instant::now();
+            window.count = 0;
+        }
+        if window.count >= max_events {
+            return false;
+        }
+        window.count += 1;
+        true
+    }
+}
+
+impl<S: EvaluationSink> EvaluationSink for SampledSink<S> {
+    fn record(&self, event: &EvaluationEvent) {
+        if self.sampled(event.flag_key.as_str()) && self.within_limit() {
+            self.inner.record(event);
+        } else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use configcat::{ClientError, ErrorKind};
+
+const SDK_KEY_PREFIX: &str = "configcat-sdk-1";
+const PROXY_PREFIX: &str = "configcat-proxy/";
+const SECTION_LENGTH: usize = 22;
+
+/// Checks the format of an SDK key, and describes the likely mistake when it's invalid.
+///
+/// Accepts the same keys as the ConfigCat SDK: `configcat-sdk-1/{22 chars}/{22 chars}`, the
+/// legacy `{22 chars}/{22 chars}` format, and `configcat-proxy/{id}` with a custom base URL.
+pub(crate) fn validate(sdk_key: &str, custom_base_url: bool) -> Result<(), ClientError> {
+    describe(sdk_key, custom_base_url).map_or(Ok(()), |problem| {
+        Err(ClientError {
+            kind: ErrorKind::InvalidSdkKey,
+            message: format!(
+                "SDK Key '{sdk_key}' is invalid: {problem} Copy the SDK key of the config and environment from the ConfigCat Dashboard."
+            ),
+        })
+    })
+}
+
+fn describe(sdk_key: &str, custom_base_url: bool) -> Option<String> {
+    if sdk_key.is_empty() {
+        return Some("it's empty.".to_owned());
+    }
+    if sdk_key.trim() != sdk_key || sdk_key.contains(char::is_whitespace) {
+        return Some(
+            "it contains whitespace, it was probably copied with surrounding spaces or a line break."
+                .to_owned(),
+        );
+    }
+    if sdk_key.contains("://") {
+        return Some("it's a URL, only the SDK key part of it is needed.".to_owned());
+    }
+    if let Some(id) = sdk_key.strip_prefix(PROXY_PREFIX) {
+        return match (id.is_empty(), custom_base_url) {
+            (true, _) => Some(format!("the SDK identifier is missing after '{PROXY_PREFIX}'.")),
+            (false, false) => Some(format!(
+                "'{PROXY_PREFIX}' keys require the URL of the ConfigCat Proxy to be set with `base_url`."
+            )),
+            (false, true) => None,
+        };
+    }
+    let sections: Vec<&str> = sdk_key.split('/').collect();
+    let (prefix, ids) = match sections.as_slice() {
+        [prefix, config, environment] => (Some(*prefix), [*config, *environment]),
+        [config, environment] => (None, [*config, *environment]),
+        _ => {
+            return Some(format!(
+                "it should have the '{SDK_KEY_PREFIX}/{{config id}}/{{environment id}}' format, but it has {} '/'-separated parts.",
+                sections.len()
+            ))
+        }
+    };
+    if let Some(prefix) = prefix.filter(|prefix| *prefix != SDK_KEY_PREFIX) {
+        return Some(format!(
+            "it should start with '{SDK_KEY_PREFIX}/', but it starts with '{prefix}/'."
+        ));
+    }
+    for (name, id) in ["config", "environment"].into_iter().zip(ids) {
+        match id.len() {
+            SECTION_LENGTH => {}
+            len if len < SECTION_LENGTH => {
+                return Some(format!(
+                    "its {name} part is {len} characters long instead of {SECTION_LENGTH}, it was probably truncated."
+                ))
+            }
+            len => {
+                return Some(format!(
+                    "its {name} part is {len} characters long instead of {SECTION_LENGTH}, it probably has extra characters."
+                ))
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,95 @@
+use crate::sink::{EvaluationEvent, EvaluationSink};
+use sentry_core::protocol::{Breadcrumb, Event, Level, Map};
+
+const CATEGORY: &str = "configcat";
+
+/// An [`EvaluationSink`] that records failed flag evaluations in Sentry.
+///
+/// Each failed evaluation is added as a breadcrumb to the current scope, so it shows up in the
+/// context of subsequent Sentry events. With [`SentrySink::capture_events`], failed evaluations
+/// are also captured as standalone Sentry events tagged with the flag key and the error code.
+///
+/// Provider lifecycle errors (like an initialization failure) are captured as Sentry events
+/// automatically when the `sentry` feature is enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, SentrySink};
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .sink(SentrySink::new().capture_events(true))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct SentrySink {
+    capture_events: bool,
+}
+
+impl SentrySink {
+    /// Creates a new [`SentrySink`] that records failed evaluations as breadcrumbs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indicates whether failed evaluations should also be captured as Sentry events.
+    ///
+    /// Default is `false`.
+    pub fn capture_events(mut self, capture_events: bool) -> Self {
+        self.capture_events = capture_events;
+        self
+    }
+}
+
+impl EvaluationSink for SentrySink {
+    fn record(&self, event: &EvaluationEvent) {
+        let Some(error_code) = event.error_code.as_ref() else {
+            return;
+        };
+        let message = format!(
+            "Failed to evaluate '{}': {}",
+            event.flag_key,
+            event.error_message.as_deref().unwrap_or(error_code)
+        );
+        let mut data = Map::new();
+        data.insert("flag_key".to_owned(), event.flag_key.as_str().into());
+        data.insert("error_code".to_owned(), error_code.as_str().into());
+        sentry_core::add_breadcrumb(Breadcrumb {
+            category: Some(CATEGORY.to_owned()),
+            level: Level::Warning,
+            message: Some(message.clone()),
+            data,
+            ..Breadcrumb::default()
+        });
+        if self.capture_events {
+            let mut sentry_event = Event {
+                level: Level::Warning,
+                message: Some(message),
+                logger: Some(CATEGORY.to_owned()),
+                ..Event::default()
+            };
+            sentry_event
+                .tags
+                .insert("flag_key".to_owned(), event.flag_key.clone());
+            sentry_event
+                .tags
+                .insert("error_code".to_owned(), error_code.clone());
+            sentry_core::capture_event(sentry_event);
+        }
+    }
+}
+
+/// Captures a provider lifecycle error as a Sentry event.
+pub(crate) fn capture_lifecycle_error(message: &str) {
+    let mut event = Event {
+        level: Level::Error,
+        message: Some(message.to_owned()),
+        logger: Some(CATEGORY.to_owned()),
+        ..Event::default()
+    };
+    event
+        .tags
+        .insert("error_code".to_owned(), "PROVIDER_NOT_READY".to_owned());
+    sentry_core::capture_event(event);
+}
@@ -0,0 +1,245 @@
+use async_trait::async_trait;
+use open_feature::provider::{
+    FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+use open_feature::{EvaluationContext, EvaluationResult, StructValue, Value};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+type MismatchFn = dyn Fn(&ShadowMismatch) + Send + Sync;
+
+/// A difference between the results of the primary and the shadow provider of a
+/// [`ShadowProvider`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShadowMismatch {
+    /// The key of the evaluated flag.
+    pub flag_key: String,
+    /// The value served by the primary provider, or `None` when its evaluation failed.
+    pub primary: Option<Value>,
+    /// The value returned by the shadow provider, or `None` when its evaluation failed.
+    pub shadow: Option<Value>,
+}
+
+/// An OpenFeature provider that evaluates each flag with both a primary and a shadow
+/// provider, serves the primary result, and reports when the two differ.
+///
+/// It's meant for verifying a migration between ConfigCat environments or SDK keys (for
+/// example, staging vs production config) before switching over: the shadow evaluations run in
+/// the background on the Tokio runtime, so they never delay or affect the served values. The
+/// mismatching values are reported to the callbacks added with
+/// [`ShadowProvider::on_mismatch`], and counted by [`ShadowProvider::mismatches`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, ShadowProvider};
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let primary = ConfigCatProvider::builder("old-sdk-key").build().unwrap();
+///     let shadow = ConfigCatProvider::builder("new-sdk-key").build().unwrap();
+///
+///     let provider = ShadowProvider::new(primary, shadow).on_mismatch(|mismatch| {
+///         println!(
+///             "'{}' differs: {:?} vs {:?}",
+///             mismatch.flag_key, mismatch.primary, mismatch.shadow
+///         );
+///     });
+///
+///     let mut api = OpenFeature::singleton_mut().await;
+///     api.set_provider(provider).await;
+/// }
+/// ```
+pub struct ShadowProvider<P, S> {
+    primary: P,
+    shadow: Arc<S>,
+    on_mismatch: Arc<[Arc<MismatchFn>]>,
+    mismatches: Arc<AtomicU64>,
+}
+
+impl<P: FeatureProvider, S: FeatureProvider> ShadowProvider<P, S> {
+    /// Creates a new [`ShadowProvider`] with the given primary and shadow providers.
+    pub fn new(primary: P, shadow: S) -> Self {
+        Self {
+            primary,
+            shadow: Arc::new(shadow),
+            on_mismatch: Arc::new([]),
+            mismatches: Arc::default(),
+        }
+    }
+
+    /// Adds a callback invoked each time the primary and the shadow provider return different
+    /// values for a flag.
+    pub fn on_mismatch<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ShadowMismatch) + Send + Sync + 'static,
+    {
+        let mut on_mismatch = self.on_mismatch.to_vec();
+        on_mismatch.push(Arc::new(callback));
+        self.on_mismatch = on_mismatch.into();
+        self
+    }
+
+    /// Returns the number of evaluations where the primary and the shadow provider returned
+    /// different values.
+    ///
+    /// The evaluations still being compared in the background are not counted yet.
+    pub fn mismatches(&self) -> u64 {
+        self.mismatches.load(Ordering::Relaxed)
+    }
+
+    /// Spawns the shadow evaluation, and compares its result to the primary one once it's done.
+    fn compare<T, F>(
+        &self,
+        flag_key: &str,
+        primary: &EvaluationResult<ResolutionDetails<T>>,
+        shadow: F,
+    ) where
+        T: Clone + PartialEq + Into<Value> + Send + 'static,
+        F: Future<Output = EvaluationResult<ResolutionDetails<T>>> + Send + 'static,
+    {
+        let primary_value = primary.as_ref().ok().map(|details| details.value.clone());
+        let flag_key = flag_key.to_owned();
+        let on_mismatch = self.on_mismatch.clone();
+        let mismatches = self.mismatches.clone();
+        tokio::spawn(async move {
+            let shadow = shadow.await;
+            let shadow_value = shadow.ok().map(|details| details.value);
+            if primary_value != shadow_value {
+                mismatches.fetch_add(1, Ordering::Relaxed);
+                let mismatch = ShadowMismatch {
+                    flag_key,
+                    primary: primary_value.map(Into::into),
+                    shadow: shadow_value.map(Into::into),
+                };
+                for callback in on_mismatch.iter() {
+                    callback(&mismatch);
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider, S: FeatureProvider> FeatureProvider for ShadowProvider<P, S> {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        // The shadow provider is only shared with the background evaluations, which can't be
+        // running before the provider is initialized.
+        let shadow = async {
+            if let Some(shadow) = Arc::get_mut(&mut self.shadow) {
+                shadow.initialize(context).await;
+            }
+        };
+        tokio::join!(self.primary.initialize(context), shadow);
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.primary.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.primary.metadata()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        let primary = self
+            .primary
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await;
+        let (shadow, key, context) = (
+            self.shadow.clone(),
+            flag_key.to_owned(),
+            evaluation_context.clone(),
+        );
+        self.compare(flag_key, &primary, async move {
+            shadow.resolve_bool_value(&key, &context).await
+        });
+        primary
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        let primary = self
+            .primary
+            .resolve_int_value(flag_key, evaluation_context)
+            .await;
+        let (shadow, key, context) = (
+            self.shadow.clone(),
+            flag_key.to_owned(),
+            evaluation_context.clone(),
+        );
+        self.compare(flag_key, &primary, async move {
+            shadow.resolve_int_value(&key, &context).await
+        });
+        primary
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        let primary = self
+            .primary
+            .resolve_float_value(flag_key, evaluation_context)
+            .await;
+        let (shadow, key, context) = (
+            self.shadow.clone(),
+            flag_key.to_owned(),
+            evaluation_context.clone(),
+        );
+        self.compare(flag_key, &primary, async move {
+            shadow.resolve_float_value(&key, &context).await
+        });
+        primary
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        let primary = self
+            .primary
+            .resolve_string_value(flag_key, evaluation_context)
+            .await;
+        let (shadow, key, context) = (
+            self.shadow.clone(),
+            flag_key.to_owned(),
+            evaluation_context.clone(),
+        );
+        self.compare(flag_key, &primary, async move {
+            shadow.resolve_string_value(&key, &context).await
+        });
+        primary
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let primary = self
+            .primary
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await;
+        let (shadow, key, context) = (
+            self.shadow.clone(),
+            flag_key.to_owned(),
+            evaluation_context.clone(),
+        );
+        self.compare(flag_key, &primary, async move {
+            shadow.resolve_struct_value(&key, &context).await
+        });
+        primary
+    }
+}
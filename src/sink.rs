@@ -0,0 +1,113 @@
+use crate::trace_context;
+use crate::value::to_json;
+use chrono::{DateTime, Utc};
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationReason, EvaluationResult, Value};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Describes a single flag evaluation performed by the [`crate::ConfigCatProvider`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EvaluationEvent {
+    /// The key of the evaluated feature flag or setting.
+    pub flag_key: String,
+    /// The evaluated value. `None` when the evaluation failed.
+    pub value: Option<serde_json::Value>,
+    /// The variation ID of the evaluated value.
+    pub variant: Option<String>,
+    /// The reason of the evaluation result.
+    pub reason: Option<String>,
+    /// The error code when the evaluation failed.
+    pub error_code: Option<String>,
+    /// The error message when the evaluation failed.
+    pub error_message: Option<String>,
+    /// The targeting key of the evaluation context.
+    pub targeting_key: Option<String>,
+    /// The time when the evaluation happened.
+    pub timestamp: DateTime<Utc>,
+    /// The fetch time of the config JSON the evaluation was based on.
+    pub config_fetch_time: Option<DateTime<Utc>>,
+    /// The ID of the trace the evaluation happened in.
+    ///
+    /// Only populated with the `otel` feature, from the current OpenTelemetry context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// The ID of the span the evaluation happened in.
+    ///
+    /// Populated with the `otel` feature from the current OpenTelemetry context, or with the
+    /// `tracing` feature from the current `tracing` span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
+}
+
+impl EvaluationEvent {
+    pub(crate) fn new<T: Clone + Into<Value>>(
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+        result: &EvaluationResult<ResolutionDetails<T>>,
+        config_fetch_time: Option<DateTime<Utc>>,
+    ) -> Self {
+        let (trace_id, span_id) = trace_context::current();
+        let mut event = Self {
+            flag_key: flag_key.to_owned(),
+            value: None,
+            variant: None,
+            reason: None,
+            error_code: None,
+            error_message: None,
+            targeting_key: evaluation_context.targeting_key.clone(),
+            timestamp: Utc::now(),
+            config_fetch_time,
+            trace_id,
+            span_id,
+        };
+        match result {
+            Ok(details) => {
+                event.value = Some(to_json(&details.value.clone().into()));
+                event.variant.clone_from(&details.variant);
+                event.reason = details.reason.as_ref().map(ToString::to_string);
+            }
+            Err(err) => {
+                event.reason = Some(EvaluationReason::Error.to_string());
+                event.error_code = Some(err.code.to_string());
+                event.error_message.clone_from(&err.message);
+            }
+        }
+        event
+    }
+}
+
+/// A destination of [`EvaluationEvent`]s emitted by the [`crate::ConfigCatProvider`].
+///
+/// Sinks are called synchronously on the evaluation path, so implementations should
+/// hand the event over to a background worker (like [`crate::BatchExporter`] does)
+/// rather than performing blocking I/O.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, EvaluationEvent, EvaluationSink};
+///
+/// struct PrintSink;
+///
+/// impl EvaluationSink for PrintSink {
+///     fn record(&self, event: &EvaluationEvent) {
+///         println!("{}: {:?}", event.flag_key, event.value);
+///     }
+/// }
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .sink(PrintSink)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait EvaluationSink: Send + Sync {
+    /// Records an evaluation event.
+    fn record(&self, event: &EvaluationEvent);
+}
+
+impl<S: EvaluationSink + ?Sized> EvaluationSink for Arc<S> {
+    fn record(&self, event: &EvaluationEvent) {
+        (**self).record(event);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::provider::ConfigCatProvider;
+use async_trait::async_trait;
+use configcat::{
+    Client, ClientError, ConfigCache, OverrideBehavior, OverrideDataSource, PollingMode, Setting,
+    User,
+};
+use open_feature::provider::{FeatureProvider, ProviderMetadata, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationResult, StructValue};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const SNAPSHOT_ETAG: &str = "snapshot";
+
+/// An OpenFeature provider that evaluates feature flags against a frozen config JSON.
+///
+/// Created by [`ConfigCatProvider::snapshot`]. All evaluations of a snapshot see the same
+/// config JSON, even if the [`ConfigCatProvider`] downloads a newer one in the meantime,
+/// so it's suitable for serving a single request with consistent flag values.
+///
+/// Evaluations are still reported to the sinks, callbacks and statistics of the originating
+/// [`ConfigCatProvider`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::ConfigCatProvider;
+/// use open_feature::OpenFeature;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     // At the beginning of each request:
+///     let mut api = OpenFeature::default();
+///     api.set_provider(provider.snapshot()).await;
+///     let client = api.create_client();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ConfigCatSnapshotProvider {
+    provider: ConfigCatProvider,
+    client: Option<Arc<Client>>,
+}
+
+impl ConfigCatSnapshotProvider {
+    pub(crate) fn new(provider: ConfigCatProvider, client: Option<Arc<Client>>) -> Self {
+        Self { provider, client }
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for ConfigCatSnapshotProvider {
+    fn metadata(&self) -> &ProviderMetadata {
+        self.provider.metadata()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.provider
+            .resolve_bool_on(self.client.as_deref(), flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.provider
+            .resolve_int_on(self.client.as_deref(), flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.provider
+            .resolve_float_on(self.client.as_deref(), flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.provider
+            .resolve_string_on(self.client.as_deref(), flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.provider
+            .resolve_struct_on(self.client.as_deref(), flag_key, evaluation_context)
+            .await
+    }
+}
+
+/// The configuration needed to create ConfigCat SDK clients that evaluate a frozen config JSON
+/// the same way as the provider's own client.
+pub(crate) struct ClientTemplate {
+    pub(crate) sdk_key: String,
+    pub(crate) default_user: Option<User>,
+    pub(crate) overrides: Option<(Arc<dyn OverrideDataSource>, OverrideBehavior)>,
+}
+
+impl ClientTemplate {
+    /// Creates an offline client that evaluates the given SDK cache entry.
+    pub(crate) fn build(&self, cache_str: &str) -> Result<Client, ClientError> {
+        let mut builder = Client::builder(self.sdk_key.as_str())
+            .polling_mode(PollingMode::Manual)
+            .offline(true)
+            .cache(Box::new(FrozenCache(with_etag(cache_str))));
+        if let Some(user) = self.default_user.as_ref() {
+            builder = builder.default_user(user.clone());
+        }
+        if let Some((source, behavior)) = self.overrides.as_ref() {
+            builder = builder.overrides(
+                Box::new(SharedSource(source.clone())),
+                copy_behavior(behavior),
+            );
+        }
+        builder.build()
+    }
+}
+
+/// Makes sure that the cache entry has an ETag, as the SDK ignores cache entries without one
+/// when it has no config JSON loaded yet.
+fn with_etag(cache_str: &str) -> String {
+    let mut parts = cache_str.splitn(3, '\n');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(fetch_time), Some(""), Some(config_json)) => {
+            format!("{fetch_time}\n{SNAPSHOT_ETAG}\n{config_json}")
+        }
+        _ => cache_str.to_owned(),
+    }
+}
+
+/// Serves the same cache entry forever, and ignores the writes.
+struct FrozenCache(String);
+
+impl ConfigCache for FrozenCache {
+    fn read(&self, _: &str) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn write(&self, _: &str, _: &str) {}
+}
+
+/// Shares a flag override data source between multiple clients.
+pub(crate) struct SharedSource(pub(crate) Arc<dyn OverrideDataSource>);
+
+impl OverrideDataSource for SharedSource {
+    fn settings(&self) -> &HashMap<String, Setting> {
+        self.0.settings()
+    }
+}
+
+pub(crate) fn copy_behavior(behavior: &OverrideBehavior) -> OverrideBehavior {
+    match behavior {
+        OverrideBehavior::LocalOnly => OverrideBehavior::LocalOnly,
+        OverrideBehavior::LocalOverRemote => OverrideBehavior::LocalOverRemote,
+        OverrideBehavior::RemoteOverLocal => OverrideBehavior::RemoteOverLocal,
+    }
+}
@@ -0,0 +1,149 @@
+use crate::refresh::{RefreshMode, Refresher};
+use crate::runtime;
+use crate::snapshot::ClientTemplate;
+use crate::tap::ConfigTap;
+use configcat::{Client, ClientError};
+use log::warn;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError, Weak};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// A shared config source, along with a description of the options it was built with.
+type SharedEntry = (Weak<ConfigSource>, String);
+
+/// The config sources shared between providers, keyed by SDK key.
+static REGISTRY: LazyLock<Mutex<HashMap<String, SharedEntry>>> = LazyLock::new(Mutex::default);
+
+/// The underlying ConfigCat SDK client of a provider, along with the state needed to keep its
+/// config JSON up-to-date. It can be shared between providers built for the same SDK key.
+pub(crate) struct ConfigSource {
+    pub(crate) client: Client,
+    pub(crate) refresher: Refresher,
+    tap: Option<Arc<ConfigTap>>,
+    template: Option<ClientTemplate>,
+    snapshot_client: Mutex<Option<(u64, Arc<Client>)>>,
+}
+
+impl ConfigSource {
+    /// Creates a new [`ConfigSource`], and starts polling when the refresh mode requires it.
+    pub(crate) fn new(
+        client: Client,
+        refresher: Refresher,
+        tap: Option<Arc<ConfigTap>>,
+        template: Option<ClientTemplate>,
+    ) -> Arc<Self> {
+        let source = Arc::new(Self {
+            client,
+            refresher,
+            tap,
+            template,
+            snapshot_client: Mutex::new(None),
+        });
+        if let Some(interval) = source.refresher.poll_interval() {
+            spawn_poller(Arc::downgrade(&source), interval);
+        }
+        source
+    }
+
+    /// Creates a [`ConfigSource`] for a client that's kept up-to-date by itself.
+    pub(crate) fn unmanaged(client: Client) -> Arc<Self> {
+        Self::new(
+            client,
+            Refresher::new(RefreshMode::Manual, None),
+            None,
+            None,
+        )
+    }
+
+    /// Returns the [`ConfigSource`] registered for the given SDK key, or registers the one
+    /// created by `create` when there's none alive.
+    ///
+    /// Logs a warning when the registered source was built with different `options`, as they
+    /// are ignored.
+    pub(crate) fn shared(
+        sdk_key: &str,
+        options: String,
+        create: impl FnOnce() -> Result<Arc<Self>, ClientError>,
+    ) -> Result<Arc<Self>, ClientError> {
+        let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+        registry.retain(|_, (source, _)| source.strong_count() > 0);
+        if let Some((source, shared_options)) = registry.get(sdk_key) {
+            if let Some(source) = source.upgrade() {
+                if *shared_options != options {
+                    warn!("A provider is already sharing the config JSON of this SDK key with different SDK client or polling options. The options of the first built provider apply.");
+                }
+                return Ok(source);
+            }
+        }
+        let source = create()?;
+        registry.insert(sdk_key.to_owned(), (Arc::downgrade(&source), options));
+        Ok(source)
+    }
+
+    /// Prepares the config JSON for an evaluation according to the refresh mode, and starts
+    /// the background revalidation when the evaluation is served from stale config JSON.
+    ///
+    /// Returns `true` when the config JSON is stale.
+    pub(crate) async fn prepare(self: &Arc<Self>) -> bool {
+        let source = self.clone();
+        let stale =
+            runtime::run(async move { source.refresher.prepare(&source.client).await }).await;
+        if stale {
+            let source = self.clone();
+            runtime::spawn(async move { source.refresher.revalidate(&source.client).await });
+        }
+        stale
+    }
+
+    pub(crate) fn tap(&self) -> Option<&ConfigTap> {
+        self.tap.as_deref()
+    }
+
+    /// Returns a reference to the tap that doesn't keep it alive, for background tasks that
+    /// stop once the source is dropped.
+    pub(crate) fn weak_tap(&self) -> Option<Weak<ConfigTap>> {
+        self.tap.as_ref().map(Arc::downgrade)
+    }
+
+    /// Returns an offline client that evaluates the currently downloaded config JSON.
+    pub(crate) fn frozen_client(&self) -> Option<Arc<Client>> {
+        let latest = self.tap.as_ref()?.latest()?;
+        let template = self.template.as_ref()?;
+        let mut frozen = self
+            .snapshot_client
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some((version, client)) = frozen.as_ref() {
+            if *version == latest.version {
+                return Some(client.clone());
+            }
+        }
+        match template.build(&latest.cache_str) {
+            Ok(client) => {
+                let client = Arc::new(client);
+                *frozen = Some((latest.version, client.clone()));
+                Some(client)
+            }
+            Err(err) => {
+                warn!("Failed to create a config snapshot, the live config JSON is used instead. ({err})");
+                None
+            }
+        }
+    }
+}
+
+fn spawn_poller(source: Weak<ConfigSource>, interval: Duration) {
+    runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            // Stop polling once all the providers using the source were dropped.
+            let Some(source) = source.upgrade() else {
+                return;
+            };
+            source.refresher.poll(&source.client).await;
+        }
+    });
+}
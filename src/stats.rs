@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use open_feature::EvaluationError;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// The key of [`FlagUsage::values`] counting the evaluations that served a value beyond the
+/// first [`MAX_COUNTED_VALUES`] distinct values of a flag.
+pub const OTHER_VALUES_KEY: &str = "other";
+
+/// The maximum number of distinct values counted separately in [`FlagUsage::values`].
+pub const MAX_COUNTED_VALUES: usize = 100;
+
+/// A point-in-time snapshot of the [`crate::ConfigCatProvider`]'s runtime statistics.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProviderStats {
+    /// The number of flag evaluations performed by the provider.
+    pub evaluations: u64,
+    /// The number of failed evaluations grouped by error code.
+    pub errors_by_code: HashMap<String, u64>,
+    /// The number of evaluations served from the already downloaded config JSON,
+    /// without fetching a newer one.
+    pub cache_hits: u64,
+    /// The fetch time of the most recent config JSON used for evaluation.
+    pub last_fetch_time: Option<DateTime<Utc>>,
+    /// The message of the most recent evaluation error.
+    pub last_error: Option<String>,
+}
+
+/// The evaluations of a feature flag since startup, part of a [`UsageReport`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlagUsage {
+    /// The number of evaluations of the flag.
+    pub evaluations: u64,
+    /// The number of failed evaluations of the flag.
+    pub errors: u64,
+    /// The time of the last evaluation of the flag.
+    pub last_evaluated: Option<DateTime<Utc>>,
+    /// The number of successful evaluations by the JSON representation of the served value
+    /// (e.g. `true`, `5` or `"dark"`), when enabled with
+    /// [`crate::ConfigCatProviderBuilder::count_served_values`].
+    ///
+    /// At most [`MAX_COUNTED_VALUES`] distinct values are counted separately, the rest are
+    /// counted under [`OTHER_VALUES_KEY`].
+    pub values: HashMap<String, u64>,
+}
+
+/// A point-in-time report of the flag evaluations performed by the
+/// [`crate::ConfigCatProvider`] since startup, returned by
+/// [`crate::ConfigCatProvider::usage_report`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UsageReport {
+    /// The usage of each evaluated flag by flag key.
+    pub flags: BTreeMap<String, FlagUsage>,
+}
+
+#[derive(Default)]
+struct MutableStats {
+    errors_by_code: HashMap<String, u64>,
+    last_fetch_time: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    usage: HashMap<String, FlagUsage>,
+}
+
+#[derive(Default)]
+pub(crate) struct StatsCollector {
+    count_values: bool,
+    evaluations: AtomicU64,
+    cache_hits: AtomicU64,
+    state: Mutex<MutableStats>,
+}
+
+impl StatsCollector {
+    pub(crate) fn new(count_values: bool) -> Self {
+        Self {
+            count_values,
+            ..Self::default()
+        }
+    }
+
+    /// Returns whether the served values are counted, so they have to be passed to
+    /// [`StatsCollector::record`].
+    pub(crate) fn counts_values(&self) -> bool {
+        self.count_values
+    }
+
+    pub(crate) fn record(
+        &self,
+        flag_key: &str,
+        value: Option<String>,
+        error: Option<&EvaluationError>,
+        fetch_time: Option<DateTime<Utc>>,
+    ) {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if !state.usage.contains_key(flag_key) {
+            state
+                .usage
+                .insert(flag_key.to_owned(), FlagUsage::default());
+        }
+        if let Some(usage) = state.usage.get_mut(flag_key) {
+            usage.evaluations += 1;
+            usage.last_evaluated = Some(Utc::now());
+            if error.is_some() {
+                usage.errors += 1;
+            }
+            if let Some(value) = value {
+                let key = if usage.values.len() < MAX_COUNTED_VALUES
+                    || usage.values.contains_key(&value)
+                {
+                    value
+                } else {
+                    OTHER_VALUES_KEY.to_owned()
+                };
+                *usage.values.entry(key).or_insert(0) += 1;
+            }
+        }
+        if fetch_time.is_some() {
+            if fetch_time == state.last_fetch_time {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+            } else {
+                state.last_fetch_time = fetch_time;
+            }
+        }
+        if let Some(err) = error {
+            *state
+                .errors_by_code
+                .entry(err.code.to_string())
+                .or_insert(0) += 1;
+            state.last_error = Some(match err.message.as_ref() {
+                Some(message) => message.clone(),
+                None => err.code.to_string(),
+            });
+        }
+    }
+
+    /// Returns whether the flag with the given config JSON key was evaluated since startup.
+    pub(crate) fn is_evaluated(&self, flag_key: &str) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.usage.contains_key(flag_key)
+    }
+
+    /// Returns the usage of each evaluated flag by config JSON key.
+    pub(crate) fn usage(&self) -> HashMap<String, FlagUsage> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.usage.clone()
+    }
+
+    pub(crate) fn snapshot(&self) -> ProviderStats {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        ProviderStats {
+            evaluations: self.evaluations.load(Ordering::Relaxed),
+            errors_by_code: state.errors_by_code.clone(),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            last_fetch_time: state.last_fetch_time,
+            last_error: state.last_error.clone(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::provider::ConfigCatProvider;
+use crate::runtime;
+use chrono::Utc;
+use log::info;
+use std::fmt::Write;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// The number of the most evaluated flags listed in the summary.
+const TOP_FLAGS: usize = 5;
+
+impl ConfigCatProvider {
+    /// Returns a one-line summary of the evaluations performed since startup: the number of
+    /// evaluations and errors, the age of the config JSON, and the most evaluated flags.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    /// // 120 evaluations, 2 errors (1.7%), config JSON age: 35s, top flags: 'isNewCheckout' (80), 'theme' (40).
+    /// println!("{}", provider.evaluation_summary());
+    /// ```
+    pub fn evaluation_summary(&self) -> String {
+        let stats = self.stats();
+        let errors: u64 = stats.errors_by_code.values().sum();
+        let mut summary = format!("{} evaluations, {errors} errors", stats.evaluations);
+        if let Some(permille) = (errors * 1000).checked_div(stats.evaluations) {
+            _ = write!(summary, " ({}.{}%)", permille / 10, permille % 10);
+        }
+        match stats.last_fetch_time {
+            Some(fetch_time) => {
+                let age = (Utc::now() - fetch_time).num_seconds().max(0);
+                _ = write!(summary, ", config JSON age: {age}s");
+            }
+            None => summary.push_str(", no config JSON"),
+        }
+        let mut flags: Vec<_> = self.usage_report().flags.into_iter().collect();
+        flags.sort_by(|(a_key, a), (b_key, b)| {
+            b.evaluations.cmp(&a.evaluations).then(a_key.cmp(b_key))
+        });
+        if !flags.is_empty() {
+            let top: Vec<_> = flags
+                .iter()
+                .take(TOP_FLAGS)
+                .map(|(flag_key, usage)| format!("'{flag_key}' ({})", usage.evaluations))
+                .collect();
+            _ = write!(summary, ", top flags: {}", top.join(", "));
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Starts a background task that logs the [`ConfigCatProvider::evaluation_summary`] at info
+    /// level with the given interval, giving lightweight observability without a metrics stack.
+    ///
+    /// The returned task runs until it's aborted.
+    ///
+    /// # Panics
+    ///
+    /// This method panics when called outside of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     provider.log_summary(Duration::from_secs(300));
+    /// }
+    /// ```
+    pub fn log_summary(&self, interval: Duration) -> JoinHandle<()> {
+        let provider = self.clone();
+        runtime::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                info!(
+                    "ConfigCat provider summary: {}",
+                    provider.evaluation_summary()
+                );
+            }
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use configcat::ConfigCache;
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::watch;
+
+/// The latest config JSON cache entry observed by a [`ConfigTap`].
+#[derive(Clone)]
+pub(crate) struct TappedConfig {
+    /// The SDK's cache entry in `{fetch_time}\n{etag}\n{config_json}` format.
+    pub(crate) cache_str: Arc<str>,
+    /// Incremented each time the config JSON content changes.
+    pub(crate) version: u64,
+}
+
+impl TappedConfig {
+    /// Returns the config JSON part of the cache entry.
+    pub(crate) fn config_json(&self) -> &str {
+        config_json(&self.cache_str)
+    }
+}
+
+/// Keeps track of the config JSON downloaded by the underlying ConfigCat SDK client.
+pub(crate) struct ConfigTap {
+    latest: RwLock<Option<TappedConfig>>,
+    changes: watch::Sender<u64>,
+}
+
+impl Default for ConfigTap {
+    fn default() -> Self {
+        Self {
+            latest: RwLock::default(),
+            changes: watch::Sender::new(0),
+        }
+    }
+}
+
+impl ConfigTap {
+    /// Returns a receiver notified with the new version each time the config JSON content changes.
+    pub(crate) fn subscribe(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
+    }
+
+    pub(crate) fn latest(&self) -> Option<TappedConfig> {
+        self.latest
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn store(&self, cache_str: &str) {
+        let mut latest = self.latest.write().unwrap_or_else(PoisonError::into_inner);
+        let version = match latest.as_ref() {
+            Some(prev) if prev.config_json() == config_json(cache_str) => prev.version,
+            Some(prev) => prev.version + 1,
+            None => 1,
+        };
+        let changed = latest.as_ref().is_none_or(|prev| prev.version != version);
+        *latest = Some(TappedConfig {
+            cache_str: cache_str.into(),
+            version,
+        });
+        drop(latest);
+        if changed {
+            self.changes.send_replace(version);
+        }
+    }
+
+    fn is_latest(&self, cache_str: &str) -> bool {
+        self.latest
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(|latest| &*latest.cache_str == cache_str)
+    }
+}
+
+/// A [`ConfigCache`] installed into the underlying ConfigCat SDK client to observe the
+/// downloaded config JSON. It forwards the calls to the wrapped cache, if any.
+pub(crate) struct TapCache {
+    tap: Arc<ConfigTap>,
+    inner: Option<Box<dyn ConfigCache>>,
+}
+
+impl TapCache {
+    pub(crate) fn new(tap: Arc<ConfigTap>, inner: Option<Box<dyn ConfigCache>>) -> Self {
+        Self { tap, inner }
+    }
+}
+
+impl ConfigCache for TapCache {
+    fn read(&self, key: &str) -> Option<String> {
+        let value = self.inner.as_ref()?.read(key)?;
+        if !self.tap.is_latest(value.as_str()) {
+            self.tap.store(value.as_str());
+        }
+        Some(value)
+    }
+
+    fn write(&self, key: &str, value: &str) {
+        self.tap.store(value);
+        if let Some(inner) = self.inner.as_ref() {
+            inner.write(key, value);
+        }
+    }
+}
+
+fn config_json(cache_str: &str) -> &str {
+    cache_str.splitn(3, '\n').nth(2).unwrap_or_default()
+}
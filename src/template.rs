@@ -0,0 +1,27 @@
+use configcat::User;
+
+/// The prefix of the placeholders referencing custom user attributes.
+const CUSTOM_PREFIX: &str = "custom.";
+
+/// Substitutes the `{{Attribute}}` and `{{custom.Attribute}}` placeholders of the text with the
+/// attributes of the user, keeping the placeholders of missing attributes.
+pub(crate) fn interpolate(text: &str, user: Option<&User>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + len + 4;
+        let name = rest[start + 2..end - 2].trim();
+        let name = name.strip_prefix(CUSTOM_PREFIX).unwrap_or(name);
+        result.push_str(&rest[..start]);
+        match user.and_then(|user| user.get(name)) {
+            Some(value) => result.push_str(value.to_string().as_str()),
+            None => result.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use open_feature::provider::{FeatureProvider, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationErrorCode, EvaluationResult};
+use std::fmt::{Debug, Write};
+
+/// Asserts that a feature flag is on for an evaluation context (or an empty one when
+/// omitted), by evaluating it with a [`FeatureProvider`]. Must be awaited in an async context.
+///
+/// On failure, the panic message contains the reason, the variant and the error of the
+/// evaluation.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::assert_flag;
+/// use configcat_openfeature_provider::testing::TestFlags;
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///
+///     assert_flag!(provider, "isNewCheckout", ctx);
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_flag {
+    ($provider:expr, $flag_key:expr $(,)?) => {
+        $crate::assert_flag_eq!($provider, $flag_key, true)
+    };
+    ($provider:expr, $flag_key:expr, $ctx:expr $(,)?) => {
+        $crate::assert_flag_eq!($provider, $flag_key, true, $ctx)
+    };
+}
+
+/// Asserts that a feature flag or setting evaluates to the expected value for an evaluation
+/// context (or an empty one when omitted), by evaluating it with a [`FeatureProvider`]. Must
+/// be awaited in an async context.
+///
+/// The type the flag is requested as follows the expected value: `bool`, integers, `f64` and
+/// strings are supported. On failure, the panic message contains the reason, the variant and
+/// the error of the evaluation.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::assert_flag_eq;
+/// use configcat_openfeature_provider::testing::TestFlags;
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = TestFlags::new()
+///         .int("maxItems", 5)
+///         .string("theme", "dark")
+///         .into_provider();
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///
+///     assert_flag_eq!(provider, "maxItems", 5, ctx);
+///     assert_flag_eq!(provider, "theme", "dark");
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_flag_eq {
+    ($provider:expr, $flag_key:expr, $expected:expr $(,)?) => {
+        $crate::assert_flag_eq!(
+            $provider,
+            $flag_key,
+            $expected,
+            $crate::open_feature::EvaluationContext::default()
+        )
+    };
+    ($provider:expr, $flag_key:expr, $expected:expr, $ctx:expr $(,)?) => {
+        if let Err(message) =
+            $crate::testing::check_flag_eq(&$provider, $flag_key, $expected, &$ctx).await
+        {
+            panic!("{}", message);
+        }
+    };
+}
+
+/// Asserts that the evaluation of a feature flag or setting fails with the expected
+/// [`EvaluationErrorCode`] for an evaluation context (or an empty one when omitted). The flag
+/// is requested as the type of the `default` argument. Must be awaited in an async context.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::assert_flag_err;
+/// use configcat_openfeature_provider::testing::TestFlags;
+/// use open_feature::EvaluationErrorCode;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = TestFlags::new().int("maxItems", 5).into_provider();
+///
+///     assert_flag_err!(provider, "missing", false, EvaluationErrorCode::FlagNotFound);
+///     assert_flag_err!(provider, "maxItems", "", EvaluationErrorCode::TypeMismatch);
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_flag_err {
+    ($provider:expr, $flag_key:expr, $default:expr, $code:expr $(,)?) => {
+        $crate::assert_flag_err!(
+            $provider,
+            $flag_key,
+            $default,
+            $code,
+            $crate::open_feature::EvaluationContext::default()
+        )
+    };
+    ($provider:expr, $flag_key:expr, $default:expr, $code:expr, $ctx:expr $(,)?) => {
+        if let Err(message) =
+            $crate::testing::check_flag_err(&$provider, $flag_key, $default, $code, &$ctx).await
+        {
+            panic!("{}", message);
+        }
+    };
+}
+
+/// A value a flag can be compared with in the assertion macros, which determines the type the
+/// flag is requested as.
+#[doc(hidden)]
+#[async_trait]
+pub trait AssertedValue: Send {
+    type Value: PartialEq + Debug + Send;
+
+    fn into_value(self) -> Self::Value;
+
+    async fn resolve<P: FeatureProvider>(
+        provider: &P,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self::Value>>;
+}
+
+macro_rules! asserted_value {
+    ($($ty:ty => $value:ty, $resolve:ident;)*) => {
+        $(
+            #[async_trait]
+            impl AssertedValue for $ty {
+                type Value = $value;
+
+                fn into_value(self) -> Self::Value {
+                    self.into()
+                }
+
+                async fn resolve<P: FeatureProvider>(
+                    provider: &P,
+                    flag_key: &str,
+                    evaluation_context: &EvaluationContext,
+                ) -> EvaluationResult<ResolutionDetails<Self::Value>> {
+                    provider.$resolve(flag_key, evaluation_context).await
+                }
+            }
+        )*
+    };
+}
+
+asserted_value! {
+    bool => bool, resolve_bool_value;
+    i32 => i64, resolve_int_value;
+    i64 => i64, resolve_int_value;
+    f64 => f64, resolve_float_value;
+    &str => String, resolve_string_value;
+    String => String, resolve_string_value;
+}
+
+#[doc(hidden)]
+pub async fn check_flag_eq<P: FeatureProvider, E: AssertedValue>(
+    provider: &P,
+    flag_key: &str,
+    expected: E,
+    evaluation_context: &EvaluationContext,
+) -> Result<(), String> {
+    let expected = expected.into_value();
+    match E::resolve(provider, flag_key, evaluation_context).await {
+        Ok(details) if details.value == expected => Ok(()),
+        Ok(details) => {
+            let mut message = format!(
+                "assertion failed for flag '{flag_key}'\n  expected: {expected:?}\n    actual: {:?}",
+                details.value
+            );
+            describe(&mut message, &details, evaluation_context);
+            Err(message)
+        }
+        Err(err) => Err(format!(
+            "assertion failed for flag '{flag_key}'\n  expected: {expected:?}\n     error: {}{}\n{}",
+            err.code,
+            err.message
+                .map(|message| format!(" ({message})"))
+                .unwrap_or_default(),
+            describe_context(evaluation_context)
+        )),
+    }
+}
+
+#[doc(hidden)]
+pub async fn check_flag_err<P: FeatureProvider, E: AssertedValue>(
+    provider: &P,
+    flag_key: &str,
+    _default: E,
+    code: EvaluationErrorCode,
+    evaluation_context: &EvaluationContext,
+) -> Result<(), String> {
+    match E::resolve(provider, flag_key, evaluation_context).await {
+        Err(err) if err.code == code => Ok(()),
+        Err(err) => Err(format!(
+            "assertion failed for flag '{flag_key}'\n  expected error: {code}\n    actual error: {}{}\n{}",
+            err.code,
+            err.message
+                .map(|message| format!(" ({message})"))
+                .unwrap_or_default(),
+            describe_context(evaluation_context)
+        )),
+        Ok(details) => {
+            let mut message = format!(
+                "assertion failed for flag '{flag_key}'\n  expected error: {code}\n    actual value: {:?}",
+                details.value
+            );
+            describe(&mut message, &details, evaluation_context);
+            Err(message)
+        }
+    }
+}
+
+fn describe<T>(
+    message: &mut String,
+    details: &ResolutionDetails<T>,
+    evaluation_context: &EvaluationContext,
+) {
+    if let Some(reason) = &details.reason {
+        _ = write!(message, "\n    reason: {reason}");
+    }
+    if let Some(variant) = &details.variant {
+        _ = write!(message, "\n   variant: {variant}");
+    }
+    _ = write!(message, "\n{}", describe_context(evaluation_context));
+}
+
+fn describe_context(evaluation_context: &EvaluationContext) -> String {
+    let mut fields: Vec<_> = evaluation_context
+        .custom_fields
+        .iter()
+        .map(|(key, value)| format!("{key}={value:?}"))
+        .collect();
+    fields.sort();
+    format!(
+        "   context: targeting_key={:?} {{{}}}",
+        evaluation_context.targeting_key,
+        fields.join(", ")
+    )
+}
@@ -0,0 +1,115 @@
+use crate::builder::ConfigCatProviderBuilder;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::sync::Arc;
+
+/// The maximum number of candidate targeting keys tried for a bucket.
+const MAX_ATTEMPTS: u32 = 10_000;
+
+/// Decides which percentage bucket (in the `0..100` range) an evaluation falls into, in place
+/// of the ConfigCat SDK's hash of the targeting key.
+///
+/// Percentage options are selected in the order they're defined: with a 50/50 split, buckets
+/// `0..50` are served the first option, and buckets `50..100` the second one.
+pub trait BucketStrategy: Send + Sync {
+    /// Returns the bucket of the flag's evaluation for the given targeting key. Values above
+    /// 99 are treated as 99.
+    fn bucket(&self, flag_key: &str, targeting_key: Option<&str>) -> u8;
+}
+
+impl<F> BucketStrategy for F
+where
+    F: Fn(&str, Option<&str>) -> u8 + Send + Sync,
+{
+    fn bucket(&self, flag_key: &str, targeting_key: Option<&str>) -> u8 {
+        self(flag_key, targeting_key)
+    }
+}
+
+/// A [`BucketStrategy`] putting every evaluation into the same bucket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedBucket(pub u8);
+
+impl BucketStrategy for FixedBucket {
+    fn bucket(&self, _flag_key: &str, _targeting_key: Option<&str>) -> u8 {
+        self.0
+    }
+}
+
+/// A [`BucketStrategy`] spreading the evaluations across the buckets by a hash of a seed, the
+/// flag key and the targeting key, so a different seed produces a different, but equally
+/// reproducible, assignment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeededBuckets(pub u64);
+
+impl BucketStrategy for SeededBuckets {
+    fn bucket(&self, flag_key: &str, targeting_key: Option<&str>) -> u8 {
+        let mut hasher = Sha256::new();
+        hasher.update(self.0.to_be_bytes());
+        hasher.update(flag_key.as_bytes());
+        hasher.update(targeting_key.unwrap_or_default().as_bytes());
+        let hash = hasher.finalize();
+        let bucket = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) % 100;
+        u8::try_from(bucket).unwrap_or_default()
+    }
+}
+
+impl ConfigCatProviderBuilder {
+    /// Makes the percentage options select their values by the given [`BucketStrategy`], so
+    /// the outcomes of percentage rollouts are reproducible in tests.
+    ///
+    /// The targeting key passed to the ConfigCat SDK is replaced with a synthetic one that
+    /// hashes into the chosen bucket. Consequently, targeting rules on the `Identifier`
+    /// attribute don't match it, and the evaluation events contain the synthetic key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use configcat_openfeature_provider::testing::{ConfigFixture, FixedBucket, FlagFixture};
+    /// use open_feature::provider::FeatureProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let fixture = ConfigFixture::new().flag(
+    ///         "isNewCheckout",
+    ///         FlagFixture::new(false)
+    ///             .percentage(50, true, "v-on")
+    ///             .percentage(50, false, "v-off"),
+    ///     );
+    ///     let provider = fixture.builder().bucketing(FixedBucket(10)).build().unwrap();
+    ///
+    ///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+    ///     assert!(provider.resolve_bool_value("isNewCheckout", &ctx).await.unwrap().value);
+    /// }
+    /// ```
+    pub fn bucketing(self, strategy: impl BucketStrategy + 'static) -> Self {
+        let strategy = Arc::new(strategy);
+        self.before(move |flag_key, ctx| {
+            let bucket = strategy
+                .bucket(flag_key, ctx.targeting_key.as_deref())
+                .min(99);
+            ctx.targeting_key = Some(bucket_key(flag_key, bucket));
+        })
+    }
+}
+
+/// Returns a targeting key that the ConfigCat SDK puts into the given percentage bucket of the
+/// flag, by trying candidates against the SDK's hash. Each candidate hits the bucket with a
+/// 1% chance, so the attempts practically never run out.
+fn bucket_key(flag_key: &str, bucket: u8) -> String {
+    (0..MAX_ATTEMPTS)
+        .map(|attempt| format!("bucket-{bucket}-{attempt}"))
+        .find(|candidate| sdk_bucket(flag_key, candidate) == u64::from(bucket))
+        .unwrap_or_default()
+}
+
+/// Produces the same percentage bucket the ConfigCat SDK computes for a flag key and a user
+/// attribute value.
+fn sdk_bucket(flag_key: &str, value: &str) -> u64 {
+    let hash = format!(
+        "{:x}",
+        Sha1::digest(format!("{flag_key}{value}").as_bytes())
+    );
+    u64::from_str_radix(&hash[..7], 16).unwrap_or_default() % 100
+}
@@ -0,0 +1,197 @@
+use crate::testing::random::Random;
+use async_trait::async_trait;
+use open_feature::provider::{
+    FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+use open_feature::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult, StructValue,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// A fault injected into evaluations by a [`ChaosProvider`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fault {
+    /// Fails the evaluation with the error code.
+    Error(EvaluationErrorCode),
+    /// Waits for the duration, then fails the evaluation as timed out.
+    Timeout(Duration),
+}
+
+/// An OpenFeature provider that fails a percentage of the evaluations of another provider
+/// with chosen faults, so the fallback paths of an application can be verified.
+///
+/// Each evaluation picks at most one fault: with faults added at 10% and 20%, 10% of the
+/// evaluations get the first, 20% the second, and the remaining 70% are evaluated with the
+/// wrapped provider.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::testing::{ChaosProvider, Fault, TestFlags};
+/// use open_feature::provider::FeatureProvider;
+/// use open_feature::{EvaluationContext, EvaluationErrorCode};
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+///     let chaos = ChaosProvider::new(provider)
+///         .fault(20, Fault::Error(EvaluationErrorCode::ProviderNotReady))
+///         .fault(10, Fault::Timeout(Duration::from_millis(50)))
+///         .seed(42);
+///
+///     let enabled = chaos
+///         .resolve_bool_value("isNewCheckout", &EvaluationContext::default())
+///         .await
+///         .map_or(false, |details| details.value);
+/// }
+/// ```
+pub struct ChaosProvider<P> {
+    provider: P,
+    faults: Vec<(u8, Fault)>,
+    random: Random,
+    injected: AtomicU64,
+}
+
+impl<P: FeatureProvider> ChaosProvider<P> {
+    /// Creates a new [`ChaosProvider`] wrapping the given provider, without faults.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            faults: Vec::new(),
+            random: Random::new(),
+            injected: AtomicU64::new(0),
+        }
+    }
+
+    /// Adds a fault injected into the given percentage of the evaluations. Percentages beyond
+    /// a total of 100 are ignored.
+    pub fn fault(mut self, percentage: u8, fault: Fault) -> Self {
+        self.faults.push((percentage, fault));
+        self
+    }
+
+    /// Seeds the selection of the faulty evaluations, so the same sequence of evaluations gets
+    /// the same faults in each run.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.random = Random::seeded(seed);
+        self
+    }
+
+    /// Returns the number of evaluations a fault was injected into.
+    pub fn injected(&self) -> u64 {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    /// Picks the fault of an evaluation, and returns its error, if any.
+    async fn inject(&self, flag_key: &str) -> Option<EvaluationError> {
+        if self.faults.is_empty() {
+            return None;
+        }
+        let roll = self.random.percent();
+        let mut bucket = 0u8;
+        let fault = self.faults.iter().find_map(|(percentage, fault)| {
+            bucket = bucket.saturating_add(*percentage);
+            (roll < bucket).then_some(fault)
+        })?;
+        self.injected.fetch_add(1, Ordering::Relaxed);
+        let err = match fault {
+            Fault::Error(code) => EvaluationError::builder()
+                .code(code.clone())
+                .message(format!("Injected {code} error for '{flag_key}'."))
+                .build(),
+            Fault::Timeout(after) => {
+                tokio::time::sleep(*after).await;
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::General("Timeout".to_owned()))
+                    .message(format!(
+                        "Injected timeout for '{flag_key}' after {}ms.",
+                        after.as_millis()
+                    ))
+                    .build()
+            }
+        };
+        Some(err)
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for ChaosProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        self.provider.initialize(context).await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.provider.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.provider.metadata()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        if let Some(err) = self.inject(flag_key).await {
+            return Err(err);
+        }
+        self.provider
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        if let Some(err) = self.inject(flag_key).await {
+            return Err(err);
+        }
+        self.provider
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        if let Some(err) = self.inject(flag_key).await {
+            return Err(err);
+        }
+        self.provider
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        if let Some(err) = self.inject(flag_key).await {
+            return Err(err);
+        }
+        self.provider
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        if let Some(err) = self.inject(flag_key).await {
+            return Err(err);
+        }
+        self.provider
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+}
@@ -0,0 +1,286 @@
+use crate::verify::FlagType;
+use open_feature::provider::{FeatureProvider, ProviderStatus, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationErrorCode, EvaluationReason, EvaluationResult};
+use std::fmt::{Display, Formatter};
+
+/// The key of the flag that's expected to be missing, unless overridden with
+/// [`ProviderContract::missing_flag`].
+const MISSING_FLAG: &str = "__openfeature_contract_missing_flag__";
+
+/// A requirement of the OpenFeature provider specification a provider doesn't meet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractViolation {
+    /// The number of the requirement in the specification, like `2.2.5`.
+    pub requirement: &'static str,
+    /// Describes how the requirement is violated.
+    pub message: String,
+}
+
+/// The result of [`ProviderContract::verify`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractReport {
+    /// The violated requirements.
+    pub violations: Vec<ContractViolation>,
+}
+
+impl ContractReport {
+    /// Returns whether the provider meets all the verified requirements.
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn violation(&mut self, requirement: &'static str, message: String) {
+        self.violations.push(ContractViolation {
+            requirement,
+            message,
+        });
+    }
+}
+
+impl Display for ContractReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return f.write_str("The provider meets the verified requirements.");
+        }
+        let violations: Vec<_> = self
+            .violations
+            .iter()
+            .map(|violation| format!("[{}] {}", violation.requirement, violation.message))
+            .collect();
+        f.write_str(&violations.join(" "))
+    }
+}
+
+/// Verifies a provider against the requirements of the
+/// [OpenFeature provider specification](https://openfeature.dev/specification/sections/providers)
+/// that can be checked from the outside: the metadata, the lifecycle, and the reasons, variants
+/// and error codes of the flag resolutions.
+///
+/// The declared flags are expected to exist with the declared types, and to resolve without
+/// errors for the given evaluation context.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::testing::{ProviderContract, TestFlags};
+/// use configcat_openfeature_provider::FlagType;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut provider = TestFlags::new()
+///         .bool("isNewCheckout", true)
+///         .string("theme", "dark")
+///         .into_provider();
+///
+///     let report = ProviderContract::new()
+///         .flag("isNewCheckout", FlagType::Bool)
+///         .flag("theme", FlagType::String)
+///         .verify(&mut provider)
+///         .await;
+///     assert!(report.is_ok(), "{report}");
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProviderContract {
+    flags: Vec<(String, FlagType)>,
+    missing_flag: String,
+    evaluation_context: EvaluationContext,
+}
+
+impl Default for ProviderContract {
+    fn default() -> Self {
+        Self {
+            flags: Vec::new(),
+            missing_flag: MISSING_FLAG.to_owned(),
+            evaluation_context: EvaluationContext::default(),
+        }
+    }
+}
+
+impl ProviderContract {
+    /// Creates a new [`ProviderContract`] without declared flags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a flag the provider serves with the given type.
+    pub fn flag(mut self, flag_key: &str, flag_type: FlagType) -> Self {
+        self.flags.push((flag_key.to_owned(), flag_type));
+        self
+    }
+
+    /// Sets the key of a flag the provider doesn't serve.
+    pub fn missing_flag(mut self, flag_key: &str) -> Self {
+        flag_key.clone_into(&mut self.missing_flag);
+        self
+    }
+
+    /// Sets the evaluation context the provider is initialized with and the flags are resolved
+    /// for.
+    pub fn evaluation_context(mut self, evaluation_context: EvaluationContext) -> Self {
+        self.evaluation_context = evaluation_context;
+        self
+    }
+
+    /// Initializes the provider, and verifies it against the requirements.
+    pub async fn verify<P: FeatureProvider>(&self, provider: &mut P) -> ContractReport {
+        let mut report = ContractReport::default();
+        if provider.metadata().name.is_empty() {
+            report.violation("2.1.1", "The provider metadata has no name.".to_owned());
+        }
+        provider.initialize(&self.evaluation_context).await;
+        let status = provider.status();
+        if status != ProviderStatus::Ready {
+            report.violation(
+                "2.4.1",
+                format!("The status of the initialized provider is {status:?}, not Ready."),
+            );
+        }
+        for (flag_key, flag_type) in &self.flags {
+            self.verify_flag(&*provider, flag_key, *flag_type, &mut report)
+                .await;
+        }
+        let result = resolve(
+            &*provider,
+            &self.missing_flag,
+            FlagType::Bool,
+            &self.evaluation_context,
+        )
+        .await;
+        expect_error(
+            &mut report,
+            &self.missing_flag,
+            FlagType::Bool,
+            &result,
+            &EvaluationErrorCode::FlagNotFound,
+        );
+        report
+    }
+
+    async fn verify_flag<P: FeatureProvider>(
+        &self,
+        provider: &P,
+        flag_key: &str,
+        flag_type: FlagType,
+        report: &mut ContractReport,
+    ) {
+        match resolve(provider, flag_key, flag_type, &self.evaluation_context).await {
+            Ok(resolved) => {
+                if resolved.reason.is_none() {
+                    report.violation(
+                        "2.2.5",
+                        format!("The {flag_type} resolution of '{flag_key}' has no reason."),
+                    );
+                }
+                if resolved.reason == Some(EvaluationReason::Error) {
+                    report.violation(
+                        "2.2.5",
+                        format!(
+                            "The successful {flag_type} resolution of '{flag_key}' has the ERROR reason."
+                        ),
+                    );
+                }
+                if resolved.variant.is_none() {
+                    report.violation(
+                        "2.2.4",
+                        format!("The {flag_type} resolution of '{flag_key}' has no variant."),
+                    );
+                }
+            }
+            Err(code) => report.violation(
+                "2.2.3",
+                format!("The {flag_type} resolution of '{flag_key}' failed with {code}."),
+            ),
+        }
+        let other_type = if flag_type == FlagType::Bool {
+            FlagType::String
+        } else {
+            FlagType::Bool
+        };
+        let result = resolve(provider, flag_key, other_type, &self.evaluation_context).await;
+        expect_error(
+            report,
+            flag_key,
+            other_type,
+            &result,
+            &EvaluationErrorCode::TypeMismatch,
+        );
+    }
+}
+
+/// The parts of a resolution the requirements are verified on, independently of the type.
+struct Resolved {
+    variant: Option<String>,
+    reason: Option<EvaluationReason>,
+}
+
+impl<T> From<ResolutionDetails<T>> for Resolved {
+    fn from(details: ResolutionDetails<T>) -> Self {
+        Self {
+            variant: details.variant,
+            reason: details.reason,
+        }
+    }
+}
+
+async fn resolve<P: FeatureProvider>(
+    provider: &P,
+    flag_key: &str,
+    flag_type: FlagType,
+    evaluation_context: &EvaluationContext,
+) -> Result<Resolved, EvaluationErrorCode> {
+    fn simplify<T>(
+        result: EvaluationResult<ResolutionDetails<T>>,
+    ) -> Result<Resolved, EvaluationErrorCode> {
+        result.map(Resolved::from).map_err(|err| err.code)
+    }
+    match flag_type {
+        FlagType::Bool => simplify(
+            provider
+                .resolve_bool_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::String => simplify(
+            provider
+                .resolve_string_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::Int => simplify(
+            provider
+                .resolve_int_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::Float => simplify(
+            provider
+                .resolve_float_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::Struct => simplify(
+            provider
+                .resolve_struct_value(flag_key, evaluation_context)
+                .await,
+        ),
+    }
+}
+
+fn expect_error(
+    report: &mut ContractReport,
+    flag_key: &str,
+    flag_type: FlagType,
+    result: &Result<Resolved, EvaluationErrorCode>,
+    expected: &EvaluationErrorCode,
+) {
+    match result {
+        Err(code) if code == expected => {}
+        Err(code) => report.violation(
+            "2.2.7",
+            format!(
+                "The {flag_type} resolution of '{flag_key}' failed with {code} instead of {expected}."
+            ),
+        ),
+        Ok(_) => report.violation(
+            "2.2.7",
+            format!("The {flag_type} resolution of '{flag_key}' didn't fail with {expected}."),
+        ),
+    }
+}
@@ -0,0 +1,317 @@
+use crate::builder::ConfigCatProviderBuilder;
+use crate::provider::ConfigCatProvider;
+use configcat::{OverrideBehavior, OverrideDataSource, Setting};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// A value served by a [`FlagFixture`], convertible from `bool`, `i64`, `f64` and strings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixtureValue {
+    setting_type: u8,
+    value: Value,
+}
+
+impl From<bool> for FixtureValue {
+    fn from(value: bool) -> Self {
+        Self {
+            setting_type: 0,
+            value: json!({ "b": value }),
+        }
+    }
+}
+
+impl From<&str> for FixtureValue {
+    fn from(value: &str) -> Self {
+        Self {
+            setting_type: 1,
+            value: json!({ "s": value }),
+        }
+    }
+}
+
+impl From<String> for FixtureValue {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<i64> for FixtureValue {
+    fn from(value: i64) -> Self {
+        Self {
+            setting_type: 2,
+            value: json!({ "i": value }),
+        }
+    }
+}
+
+impl From<f64> for FixtureValue {
+    fn from(value: f64) -> Self {
+        Self {
+            setting_type: 3,
+            value: json!({ "d": value }),
+        }
+    }
+}
+
+/// Builds a ConfigCat config JSON in memory, so tests can express targeting scenarios in Rust
+/// instead of maintaining config JSON files.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::testing::{ConfigFixture, FlagFixture, RuleFixture};
+/// use open_feature::provider::FeatureProvider;
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigFixture::new()
+///         .flag(
+///             "isNewCheckout",
+///             FlagFixture::new(false).variation_id("v-off").rule(
+///                 RuleFixture::new()
+///                     .contains("Email", &["@example.com"])
+///                     .then(true)
+///                     .variation_id("v-on"),
+///             ),
+///         )
+///         .into_provider();
+///
+///     let ctx = EvaluationContext::default()
+///         .with_targeting_key("user-1")
+///         .with_custom_field("Email", "jane@example.com");
+///     let details = provider.resolve_bool_value("isNewCheckout", &ctx).await.unwrap();
+///     assert!(details.value);
+///     assert_eq!(Some("v-on".to_owned()), details.variant);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConfigFixture {
+    settings: Map<String, Value>,
+}
+
+impl ConfigFixture {
+    /// Creates a [`ConfigFixture`] without settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a setting to the config JSON.
+    pub fn flag(mut self, flag_key: &str, flag: FlagFixture) -> Self {
+        self.settings.insert(flag_key.to_owned(), flag.into_json());
+        self
+    }
+
+    /// Returns the config JSON, e.g. to serve it from a mock HTTP server.
+    pub fn to_json(&self) -> Value {
+        json!({ "f": self.settings })
+    }
+
+    /// Returns a [`ConfigCatProviderBuilder`] serving the config JSON through local-only flag
+    /// overrides, for further configuration of the provider.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the built config JSON is invalid.
+    pub fn builder(self) -> ConfigCatProviderBuilder {
+        let settings = serde_json::from_value(Value::Object(self.settings))
+            .expect("the fixture should build a valid config JSON");
+        ConfigCatProvider::builder("local").overrides(
+            Box::new(FixtureSource { settings }),
+            OverrideBehavior::LocalOnly,
+        )
+    }
+
+    /// Creates a [`ConfigCatProvider`] serving the config JSON.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the built config JSON is invalid, or the provider can't be created.
+    pub fn into_provider(self) -> ConfigCatProvider {
+        self.builder()
+            .build()
+            .expect("the test provider should be created")
+    }
+}
+
+/// A setting of a [`ConfigFixture`].
+#[derive(Clone, Debug)]
+pub struct FlagFixture {
+    default: FixtureValue,
+    variation_id: Option<String>,
+    rules: Vec<Value>,
+    percentage_options: Vec<Value>,
+    percentage_attribute: Option<String>,
+}
+
+impl FlagFixture {
+    /// Creates a setting serving the given value when no targeting rule or percentage option
+    /// matches. The type of the setting is determined by the type of the value.
+    pub fn new(default: impl Into<FixtureValue>) -> Self {
+        Self {
+            default: default.into(),
+            variation_id: None,
+            rules: Vec::new(),
+            percentage_options: Vec::new(),
+            percentage_attribute: None,
+        }
+    }
+
+    /// Sets the variation ID of the default value.
+    pub fn variation_id(mut self, variation_id: &str) -> Self {
+        self.variation_id = Some(variation_id.to_owned());
+        self
+    }
+
+    /// Adds a targeting rule. Targeting rules are evaluated in the order they were added.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the rule doesn't serve a value, or serves a value with a type
+    /// different from the setting's type.
+    pub fn rule(mut self, rule: RuleFixture) -> Self {
+        let served = rule
+            .served
+            .expect("the targeting rule should serve a value");
+        assert_eq!(
+            self.default.setting_type, served.setting_type,
+            "the targeting rule should serve a value of the setting's type"
+        );
+        let mut served_value = json!({ "v": served.value });
+        if let Some(variation_id) = rule.variation_id {
+            served_value["i"] = Value::String(variation_id);
+        }
+        self.rules
+            .push(json!({ "c": rule.conditions, "s": served_value }));
+        self
+    }
+
+    /// Adds a percentage option serving the value to the given percentage of the users.
+    /// The percentages of the options should add up to 100.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the value's type differs from the setting's type.
+    pub fn percentage(
+        mut self,
+        percentage: i64,
+        value: impl Into<FixtureValue>,
+        variation_id: &str,
+    ) -> Self {
+        let value = value.into();
+        assert_eq!(
+            self.default.setting_type, value.setting_type,
+            "the percentage option should serve a value of the setting's type"
+        );
+        self.percentage_options
+            .push(json!({ "p": percentage, "v": value.value, "i": variation_id }));
+        self
+    }
+
+    /// Sets the user attribute the percentage options are evaluated on, instead of the
+    /// identifier (the targeting key).
+    pub fn percentage_attribute(mut self, attribute: &str) -> Self {
+        self.percentage_attribute = Some(attribute.to_owned());
+        self
+    }
+
+    fn into_json(self) -> Value {
+        let mut setting = json!({
+            "t": self.default.setting_type,
+            "v": self.default.value,
+        });
+        if let Some(variation_id) = self.variation_id {
+            setting["i"] = Value::String(variation_id);
+        }
+        if !self.rules.is_empty() {
+            setting["r"] = Value::Array(self.rules);
+        }
+        if !self.percentage_options.is_empty() {
+            setting["p"] = Value::Array(self.percentage_options);
+        }
+        if let Some(attribute) = self.percentage_attribute {
+            setting["a"] = Value::String(attribute);
+        }
+        setting
+    }
+}
+
+/// A targeting rule of a [`FlagFixture`]. Its conditions are combined with a logical AND.
+#[derive(Clone, Debug, Default)]
+pub struct RuleFixture {
+    conditions: Vec<Value>,
+    served: Option<FixtureValue>,
+    variation_id: Option<String>,
+}
+
+impl RuleFixture {
+    /// Creates a targeting rule without conditions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a condition matching when the attribute equals any of the values.
+    pub fn is_one_of(self, attribute: &str, values: &[&str]) -> Self {
+        self.condition(attribute, 0, "l", json!(values))
+    }
+
+    /// Adds a condition matching when the attribute equals none of the values.
+    pub fn is_not_one_of(self, attribute: &str, values: &[&str]) -> Self {
+        self.condition(attribute, 1, "l", json!(values))
+    }
+
+    /// Adds a condition matching when the attribute contains any of the values.
+    pub fn contains(self, attribute: &str, values: &[&str]) -> Self {
+        self.condition(attribute, 2, "l", json!(values))
+    }
+
+    /// Adds a condition matching when the attribute contains none of the values.
+    pub fn not_contains(self, attribute: &str, values: &[&str]) -> Self {
+        self.condition(attribute, 3, "l", json!(values))
+    }
+
+    /// Adds a condition matching when the attribute, as a number, equals the value.
+    pub fn number_equals(self, attribute: &str, value: f64) -> Self {
+        self.condition(attribute, 10, "d", json!(value))
+    }
+
+    /// Adds a condition matching when the attribute, as a number, is less than the value.
+    pub fn number_less_than(self, attribute: &str, value: f64) -> Self {
+        self.condition(attribute, 12, "d", json!(value))
+    }
+
+    /// Adds a condition matching when the attribute, as a number, is greater than the value.
+    pub fn number_greater_than(self, attribute: &str, value: f64) -> Self {
+        self.condition(attribute, 14, "d", json!(value))
+    }
+
+    /// Sets the value served when all conditions match.
+    pub fn then(mut self, value: impl Into<FixtureValue>) -> Self {
+        self.served = Some(value.into());
+        self
+    }
+
+    /// Sets the variation ID of the served value.
+    pub fn variation_id(mut self, variation_id: &str) -> Self {
+        self.variation_id = Some(variation_id.to_owned());
+        self
+    }
+
+    fn condition(mut self, attribute: &str, comparator: u8, value_key: &str, value: Value) -> Self {
+        let mut condition = json!({ "a": attribute, "c": comparator });
+        condition[value_key] = value;
+        self.conditions.push(json!({ "u": condition }));
+        self
+    }
+}
+
+/// Serves the settings of a [`ConfigFixture`] as flag overrides.
+struct FixtureSource {
+    settings: HashMap<String, Setting>,
+}
+
+impl OverrideDataSource for FixtureSource {
+    fn settings(&self) -> &HashMap<String, Setting> {
+        &self.settings
+    }
+}
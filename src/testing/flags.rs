@@ -0,0 +1,93 @@
+use crate::builder::ConfigCatProviderBuilder;
+use crate::provider::ConfigCatProvider;
+use configcat::{MapDataSource, OverrideBehavior, Value};
+use std::collections::HashMap;
+
+/// An in-memory set of flag values to create a [`ConfigCatProvider`] for tests from, without
+/// a config JSON file.
+///
+/// The created provider serves the given values through local-only flag overrides, so it
+/// never makes network requests.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::testing::TestFlags;
+/// use open_feature::provider::FeatureProvider;
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = TestFlags::new()
+///         .bool("enabledFeature", true)
+///         .string("theme", "dark")
+///         .into_provider();
+///
+///     let theme = provider
+///         .resolve_string_value("theme", &EvaluationContext::default())
+///         .await
+///         .unwrap();
+///     assert_eq!("dark", theme.value);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TestFlags {
+    values: HashMap<String, Value>,
+}
+
+impl TestFlags {
+    /// Creates an empty [`TestFlags`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of a feature flag.
+    pub fn bool(self, flag_key: &str, value: bool) -> Self {
+        self.value(flag_key, Value::Bool(value))
+    }
+
+    /// Sets the value of a whole number setting.
+    pub fn int(self, flag_key: &str, value: i64) -> Self {
+        self.value(flag_key, Value::Int(value))
+    }
+
+    /// Sets the value of a decimal number setting.
+    pub fn float(self, flag_key: &str, value: f64) -> Self {
+        self.value(flag_key, Value::Float(value))
+    }
+
+    /// Sets the value of a text setting.
+    pub fn string(self, flag_key: &str, value: &str) -> Self {
+        self.value(flag_key, Value::String(value.to_owned()))
+    }
+
+    /// Sets the value of a text setting holding a JSON object, resolvable as a struct.
+    pub fn object(self, flag_key: &str, value: &serde_json::Value) -> Self {
+        self.value(flag_key, Value::String(value.to_string()))
+    }
+
+    /// Returns a [`ConfigCatProviderBuilder`] serving the flag values, for further
+    /// configuration of the provider.
+    pub fn builder(self) -> ConfigCatProviderBuilder {
+        ConfigCatProvider::builder("local").overrides(
+            Box::new(MapDataSource::from(self.values)),
+            OverrideBehavior::LocalOnly,
+        )
+    }
+
+    /// Creates a [`ConfigCatProvider`] serving the flag values.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the provider can't be created.
+    pub fn into_provider(self) -> ConfigCatProvider {
+        self.builder()
+            .build()
+            .expect("the test provider should be created")
+    }
+
+    fn value(mut self, flag_key: &str, value: Value) -> Self {
+        self.values.insert(flag_key.to_owned(), value);
+        self
+    }
+}
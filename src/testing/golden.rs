@@ -0,0 +1,266 @@
+use crate::value::to_json;
+use crate::verify::FlagType;
+use open_feature::provider::{FeatureProvider, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationResult, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+/// The environment variable that makes [`EvaluationMatrix::assert_snapshot`] overwrite the
+/// snapshot files instead of comparing with them.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// The result of evaluating a flag for a context of an [`EvaluationMatrix`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MatrixCell {
+    /// The evaluated value. `None` when the evaluation failed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<serde_json::Value>,
+    /// The variation ID of the evaluated value.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub variant: Option<String>,
+    /// The reason of the evaluation result.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub reason: Option<String>,
+    /// The error code when the evaluation failed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error_code: Option<String>,
+}
+
+impl MatrixCell {
+    fn new<T: Into<Value>>(result: EvaluationResult<ResolutionDetails<T>>) -> Self {
+        match result {
+            Ok(details) => Self {
+                value: Some(to_json(&details.value.into())),
+                variant: details.variant,
+                reason: details.reason.as_ref().map(ToString::to_string),
+                error_code: None,
+            },
+            // The error messages are left out, as they aren't part of the targeting behavior
+            // and may be reworded between SDK versions.
+            Err(err) => Self {
+                value: None,
+                variant: None,
+                reason: None,
+                error_code: Some(err.code.to_string()),
+            },
+        }
+    }
+}
+
+/// The evaluation results of an [`EvaluationMatrix`], keyed by context name, then by flag key.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MatrixSnapshot {
+    /// The evaluation results keyed by context name, then by flag key.
+    pub results: BTreeMap<String, BTreeMap<String, MatrixCell>>,
+}
+
+impl MatrixSnapshot {
+    /// Loads a snapshot saved with [`MatrixSnapshot::save`].
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the file couldn't be read or isn't a valid snapshot.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Saves the snapshot to a pretty-printed JSON file, which is meant to be committed.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the file couldn't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)
+    }
+
+    /// Describes the differences from an expected snapshot, one line per flag and context.
+    pub fn diff(&self, expected: &MatrixSnapshot) -> Vec<String> {
+        let empty = BTreeMap::new();
+        let mut differences = Vec::new();
+        let contexts: BTreeSet<_> = expected.results.keys().chain(self.results.keys()).collect();
+        for context in contexts {
+            let expected = expected.results.get(context).unwrap_or(&empty);
+            let actual = self.results.get(context).unwrap_or(&empty);
+            let flag_keys: BTreeSet<_> = expected.keys().chain(actual.keys()).collect();
+            for flag_key in flag_keys {
+                let (expected, actual) = (expected.get(flag_key), actual.get(flag_key));
+                if expected != actual {
+                    differences.push(format!(
+                        "'{flag_key}' for '{context}': expected {}, actual {}",
+                        describe(expected),
+                        describe(actual)
+                    ));
+                }
+            }
+        }
+        differences
+    }
+}
+
+/// Evaluates a declared list of flags against a matrix of named evaluation contexts, and
+/// compares the results with a committed snapshot file, so changes in the targeting behavior
+/// become visible when the config or the SDK is upgraded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::testing::EvaluationMatrix;
+/// use configcat_openfeature_provider::{ConfigCatProvider, FlagType};
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///
+///     EvaluationMatrix::new()
+///         .flag("isNewCheckout", FlagType::Bool)
+///         .flag("theme", FlagType::String)
+///         .context("anonymous", EvaluationContext::default())
+///         .context(
+///             "beta-user",
+///             EvaluationContext::default()
+///                 .with_targeting_key("user-1")
+///                 .with_custom_field("Email", "john@example.com"),
+///         )
+///         .assert_snapshot(&provider, "tests/snapshots/flags.json")
+///         .await;
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EvaluationMatrix {
+    flags: Vec<(String, FlagType)>,
+    contexts: Vec<(String, EvaluationContext)>,
+}
+
+impl EvaluationMatrix {
+    /// Creates a new, empty [`EvaluationMatrix`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a flag evaluated as the given type.
+    pub fn flag(mut self, flag_key: &str, flag_type: FlagType) -> Self {
+        self.flags.push((flag_key.to_owned(), flag_type));
+        self
+    }
+
+    /// Adds an evaluation context, identified by the given name in the snapshot.
+    pub fn context(mut self, name: &str, evaluation_context: EvaluationContext) -> Self {
+        self.contexts.push((name.to_owned(), evaluation_context));
+        self
+    }
+
+    /// Evaluates each flag for each context with the given provider.
+    pub async fn evaluate<P: FeatureProvider>(&self, provider: &P) -> MatrixSnapshot {
+        let mut snapshot = MatrixSnapshot::default();
+        for (name, evaluation_context) in &self.contexts {
+            let mut results = BTreeMap::new();
+            for (flag_key, flag_type) in &self.flags {
+                let cell = evaluate(provider, flag_key, *flag_type, evaluation_context).await;
+                results.insert(flag_key.clone(), cell);
+            }
+            snapshot.results.insert(name.clone(), results);
+        }
+        snapshot
+    }
+
+    /// Evaluates the matrix with the given provider, and compares the results with the
+    /// snapshot file.
+    ///
+    /// The snapshot file is written instead when it doesn't exist yet, or when the
+    /// `UPDATE_SNAPSHOTS` environment variable is set, so the intended changes can be
+    /// accepted by re-running the tests with `UPDATE_SNAPSHOTS=1` and reviewing the diff of
+    /// the file.
+    ///
+    /// # Panics
+    ///
+    /// This method panics listing the differences when the results differ from the snapshot,
+    /// or when the snapshot file can't be read or written.
+    pub async fn assert_snapshot<P: FeatureProvider>(&self, provider: &P, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.evaluate(provider).await;
+        if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() || !path.exists() {
+            if let Err(err) = actual.save(path) {
+                panic!("failed to write the snapshot '{}': {err}", path.display());
+            }
+            return;
+        }
+        let expected = match MatrixSnapshot::load(path) {
+            Ok(expected) => expected,
+            Err(err) => panic!("failed to read the snapshot '{}': {err}", path.display()),
+        };
+        let differences = actual.diff(&expected);
+        assert!(
+            differences.is_empty(),
+            "the evaluations differ from the snapshot '{}'\n  {}\nRe-run with {UPDATE_SNAPSHOTS_VAR}=1 to accept the changes.",
+            path.display(),
+            differences.join("\n  ")
+        );
+    }
+}
+
+async fn evaluate<P: FeatureProvider>(
+    provider: &P,
+    flag_key: &str,
+    flag_type: FlagType,
+    evaluation_context: &EvaluationContext,
+) -> MatrixCell {
+    match flag_type {
+        FlagType::Bool => MatrixCell::new(
+            provider
+                .resolve_bool_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::String => MatrixCell::new(
+            provider
+                .resolve_string_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::Int => MatrixCell::new(
+            provider
+                .resolve_int_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::Float => MatrixCell::new(
+            provider
+                .resolve_float_value(flag_key, evaluation_context)
+                .await,
+        ),
+        FlagType::Struct => MatrixCell::new(
+            provider
+                .resolve_struct_value(flag_key, evaluation_context)
+                .await,
+        ),
+    }
+}
+
+fn describe(cell: Option<&MatrixCell>) -> String {
+    let Some(cell) = cell else {
+        return "nothing".to_owned();
+    };
+    if let Some(code) = &cell.error_code {
+        return format!("error {code}");
+    }
+    let mut description = cell
+        .value
+        .as_ref()
+        .map_or_else(|| "no value".to_owned(), ToString::to_string);
+    if let Some(variant) = cell
+        .variant
+        .as_deref()
+        .filter(|variant| !variant.is_empty())
+    {
+        _ = write!(description, " (variant {variant})");
+    }
+    if let Some(reason) = &cell.reason {
+        _ = write!(description, " [{reason}]");
+    }
+    description
+}
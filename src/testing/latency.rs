@@ -0,0 +1,170 @@
+use crate::testing::random::Random;
+use async_trait::async_trait;
+use open_feature::provider::{
+    FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+use open_feature::{EvaluationContext, EvaluationResult, StructValue};
+use std::time::Duration;
+
+/// An OpenFeature provider that delays the evaluations of another provider, so timeouts and
+/// caching can be verified under slow-provider conditions.
+///
+/// Each evaluation waits for the base delay, plus a random part of the jitter, plus the
+/// extra delay of the slow evaluations, before it's evaluated with the wrapped provider.
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::testing::{LatencyProvider, TestFlags};
+/// use open_feature::provider::FeatureProvider;
+/// use open_feature::EvaluationContext;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+///     let slow = LatencyProvider::new(provider)
+///         .delay(Duration::from_millis(5))
+///         .jitter(Duration::from_millis(10))
+///         .slow(5, Duration::from_millis(200));
+///
+///     let result = tokio::time::timeout(
+///         Duration::from_millis(100),
+///         slow.resolve_bool_value("isNewCheckout", &EvaluationContext::default()),
+///     )
+///     .await;
+/// }
+/// ```
+pub struct LatencyProvider<P> {
+    provider: P,
+    delay: Duration,
+    jitter: Duration,
+    slow: Option<(u8, Duration)>,
+    random: Random,
+}
+
+impl<P: FeatureProvider> LatencyProvider<P> {
+    /// Creates a new [`LatencyProvider`] wrapping the given provider, without delays.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            delay: Duration::ZERO,
+            jitter: Duration::ZERO,
+            slow: None,
+            random: Random::new(),
+        }
+    }
+
+    /// Sets the delay of every evaluation.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the maximum random delay added to every evaluation.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Adds an extra delay to the given percentage of the evaluations, to simulate the slow
+    /// tail of the latency distribution.
+    pub fn slow(mut self, percentage: u8, delay: Duration) -> Self {
+        self.slow = Some((percentage, delay));
+        self
+    }
+
+    /// Seeds the random parts of the delays, so the same sequence of evaluations gets the same
+    /// delays in each run.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.random = Random::seeded(seed);
+        self
+    }
+
+    /// Waits for the delay of an evaluation.
+    async fn wait(&self) {
+        let mut delay = self.delay;
+        if !self.jitter.is_zero() {
+            let nanos = u64::try_from(self.jitter.as_nanos()).unwrap_or(u64::MAX);
+            delay += Duration::from_nanos(self.random.next_u64() % nanos);
+        }
+        if let Some((percentage, extra)) = self.slow {
+            if self.random.percent() < percentage {
+                delay += extra;
+            }
+        }
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for LatencyProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        self.provider.initialize(context).await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.provider.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.provider.metadata()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.wait().await;
+        self.provider
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.wait().await;
+        self.provider
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.wait().await;
+        self.provider
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.wait().await;
+        self.provider
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.wait().await;
+        self.provider
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+}
@@ -0,0 +1,38 @@
+mod flags;
+pub use flags::TestFlags;
+
+mod fixture;
+pub use fixture::{ConfigFixture, FixtureValue, FlagFixture, RuleFixture};
+
+mod overrides;
+pub use overrides::FlagOverrideGuard;
+pub(crate) use overrides::OverrideLayer;
+
+mod replay;
+pub use replay::{RecordedEvaluation, RecordingProvider, ReplayProvider};
+
+mod bucketing;
+pub use bucketing::{BucketStrategy, FixedBucket, SeededBuckets};
+
+mod chaos;
+pub use chaos::{ChaosProvider, Fault};
+
+mod latency;
+pub use latency::LatencyProvider;
+
+mod golden;
+pub use golden::{EvaluationMatrix, MatrixCell, MatrixSnapshot, UPDATE_SNAPSHOTS_VAR};
+
+mod contract;
+pub use contract::{ContractReport, ContractViolation, ProviderContract};
+
+#[cfg(feature = "proptest")]
+mod strategies;
+#[cfg(feature = "proptest")]
+pub use strategies::{attribute_name, check_evaluation, context_field_value, evaluation_context};
+
+mod random;
+
+mod assert;
+#[doc(hidden)]
+pub use assert::{check_flag_eq, check_flag_err, AssertedValue};
@@ -0,0 +1,117 @@
+use crate::provider::ConfigCatProvider;
+use configcat::{ClientError, ErrorKind, EvaluationDetails, Value, ValuePrimitive};
+use std::any::type_name;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Restores the previous behavior of a flag overridden with
+/// [`ConfigCatProvider::override_flag`] when dropped.
+#[must_use = "the override is removed when the guard is dropped"]
+pub struct FlagOverrideGuard {
+    provider: ConfigCatProvider,
+    flag_key: String,
+    id: u64,
+}
+
+impl Drop for FlagOverrideGuard {
+    fn drop(&mut self) {
+        self.provider
+            .override_layer()
+            .remove(&self.flag_key, self.id);
+    }
+}
+
+impl ConfigCatProvider {
+    /// Overrides the value of a flag until the returned guard is dropped, so a test can
+    /// change a flag without rebuilding the provider.
+    ///
+    /// The override takes precedence over the config JSON and the flag overrides of the
+    /// underlying ConfigCat SDK client. Nested overrides of the same flag are stacked: the
+    /// latest one is served, and dropping it restores the previous one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use configcat_openfeature_provider::testing::TestFlags;
+    /// use open_feature::provider::FeatureProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = TestFlags::new().bool("isNewCheckout", false).into_provider();
+    ///     let ctx = EvaluationContext::default();
+    ///     {
+    ///         let _guard = provider.override_flag("isNewCheckout", true);
+    ///         assert!(provider.resolve_bool_value("isNewCheckout", &ctx).await.unwrap().value);
+    ///     }
+    ///     assert!(!provider.resolve_bool_value("isNewCheckout", &ctx).await.unwrap().value);
+    /// }
+    /// ```
+    pub fn override_flag(&self, flag_key: &str, value: impl Into<Value>) -> FlagOverrideGuard {
+        let flag_key = self.resolve_key(flag_key).into_owned();
+        let id = self.override_layer().push(&flag_key, value.into());
+        FlagOverrideGuard {
+            provider: self.clone(),
+            flag_key,
+            id,
+        }
+    }
+}
+
+/// The flag values overridden with [`ConfigCatProvider::override_flag`], layered over the
+/// evaluations of the ConfigCat SDK client.
+#[derive(Default)]
+pub(crate) struct OverrideLayer {
+    next_id: AtomicU64,
+    values: Mutex<HashMap<String, Vec<(u64, Value)>>>,
+}
+
+impl OverrideLayer {
+    /// Returns the evaluation details of an overridden flag, or `None` when the flag isn't
+    /// overridden.
+    pub(crate) fn details<T>(&self, flag_key: &str) -> Option<EvaluationDetails<T>>
+    where
+        T: ValuePrimitive + Default,
+    {
+        let values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+        let (_, value) = values.get(flag_key)?.last()?;
+        let mut details = EvaluationDetails {
+            key: flag_key.to_owned(),
+            ..EvaluationDetails::default()
+        };
+        if let Some(value) = T::from_value(value) {
+            details.value = value;
+        } else {
+            details.is_default_value = true;
+            details.error = Some(ClientError {
+                kind: ErrorKind::SettingValueTypeMismatch,
+                message: format!(
+                    "The overridden value of '{flag_key}' doesn't match the requested type '{}'.",
+                    type_name::<T>()
+                ),
+            });
+        }
+        Some(details)
+    }
+
+    fn push(&self, flag_key: &str, value: Value) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+        values
+            .entry(flag_key.to_owned())
+            .or_default()
+            .push((id, value));
+        id
+    }
+
+    fn remove(&self, flag_key: &str, id: u64) {
+        let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(stack) = values.get_mut(flag_key) {
+            stack.retain(|(entry_id, _)| *entry_id != id);
+            if stack.is_empty() {
+                values.remove(flag_key);
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A lock-free SplitMix64 generator for the injected faults, reproducible when seeded.
+pub(crate) struct Random {
+    state: AtomicU64,
+}
+
+impl Random {
+    pub(crate) fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        Self::seeded(u64::try_from(nanos).unwrap_or_default())
+    }
+
+    pub(crate) fn seeded(seed: u64) -> Self {
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    pub(crate) fn next_u64(&self) -> u64 {
+        let mut z = self
+            .state
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in the `0..100` range.
+    pub(crate) fn percent(&self) -> u8 {
+        u8::try_from(self.next_u64() % 100).unwrap_or_default()
+    }
+}
@@ -0,0 +1,436 @@
+use crate::tracking::context_fields;
+use crate::value::{to_json, FromValue};
+use crate::verify::FlagType;
+use async_trait::async_trait;
+use open_feature::provider::{
+    FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+use open_feature::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    StructValue, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+/// A flag evaluation captured by a [`RecordingProvider`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvaluation {
+    /// The key of the evaluated flag.
+    pub flag_key: String,
+    /// The type the flag was requested as.
+    pub flag_type: FlagType,
+    /// The targeting key of the evaluation context.
+    pub targeting_key: Option<String>,
+    /// The custom fields of the evaluation context. Struct fields are left out.
+    pub context: serde_json::Map<String, serde_json::Value>,
+    /// The evaluated value. `None` when the evaluation failed.
+    pub value: Option<serde_json::Value>,
+    /// The variation ID of the evaluated value.
+    pub variant: Option<String>,
+    /// The reason of the evaluation result.
+    pub reason: Option<String>,
+    /// The error code when the evaluation failed.
+    pub error_code: Option<String>,
+    /// The error message when the evaluation failed.
+    pub error_message: Option<String>,
+}
+
+impl RecordedEvaluation {
+    fn new<T: Clone + Into<Value>>(
+        flag_key: &str,
+        flag_type: FlagType,
+        evaluation_context: &EvaluationContext,
+        result: &EvaluationResult<ResolutionDetails<T>>,
+    ) -> Self {
+        let mut recorded = Self {
+            flag_key: flag_key.to_owned(),
+            flag_type,
+            targeting_key: evaluation_context.targeting_key.clone(),
+            context: context_fields(evaluation_context),
+            value: None,
+            variant: None,
+            reason: None,
+            error_code: None,
+            error_message: None,
+        };
+        match result {
+            Ok(details) => {
+                recorded.value = Some(to_json(&details.value.clone().into()));
+                recorded.variant.clone_from(&details.variant);
+                recorded.reason = details.reason.as_ref().map(ToString::to_string);
+            }
+            Err(err) => {
+                recorded.error_code = Some(err.code.to_string());
+                recorded.error_message.clone_from(&err.message);
+            }
+        }
+        recorded
+    }
+
+    fn replay_key(&self) -> ReplayKey {
+        ReplayKey::new(
+            &self.flag_key,
+            self.flag_type,
+            self.targeting_key.clone(),
+            &self.context,
+        )
+    }
+
+    fn to_result<T: FromValue>(&self) -> EvaluationResult<ResolutionDetails<T>> {
+        if let Some(code) = &self.error_code {
+            let mut err = EvaluationError::builder()
+                .code(parse_error_code(code))
+                .build();
+            err.message.clone_from(&self.error_message);
+            return Err(err);
+        }
+        let value = self
+            .value
+            .as_ref()
+            .and_then(|value| Value::try_from(value).ok())
+            .and_then(T::from_value)
+            .ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::ParseError)
+                    .message(format!(
+                        "The recorded value of '{}' is invalid.",
+                        self.flag_key
+                    ))
+                    .build()
+            })?;
+        Ok(ResolutionDetails {
+            value,
+            variant: self.variant.clone(),
+            reason: self.reason.as_deref().map(parse_reason),
+            flag_metadata: None,
+        })
+    }
+}
+
+/// An OpenFeature provider that records the evaluations of another provider, so they can be
+/// saved to a file and served later by a [`ReplayProvider`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::testing::RecordingProvider;
+/// use configcat_openfeature_provider::ConfigCatProvider;
+/// use open_feature::provider::FeatureProvider;
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+///     let recorder = RecordingProvider::new(provider);
+///
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///     recorder.resolve_bool_value("isNewCheckout", &ctx).await.unwrap();
+///
+///     recorder.save("tests/data/recorded.json").unwrap();
+/// }
+/// ```
+pub struct RecordingProvider<P> {
+    provider: P,
+    recorded: Mutex<Vec<RecordedEvaluation>>,
+}
+
+impl<P: FeatureProvider> RecordingProvider<P> {
+    /// Creates a new [`RecordingProvider`] recording the evaluations of the given provider.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            recorded: Mutex::default(),
+        }
+    }
+
+    /// Returns the recorded evaluations. For repeated evaluations of a flag with the same
+    /// evaluation context, only the latest one is kept.
+    pub fn recorded(&self) -> Vec<RecordedEvaluation> {
+        self.recorded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Saves the recorded evaluations to a JSON file, which can be loaded with
+    /// [`ReplayProvider::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the file couldn't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.recorded())?;
+        std::fs::write(path, json)
+    }
+
+    fn record<T: Clone + Into<Value>>(
+        &self,
+        flag_key: &str,
+        flag_type: FlagType,
+        evaluation_context: &EvaluationContext,
+        result: &EvaluationResult<ResolutionDetails<T>>,
+    ) {
+        let recorded = RecordedEvaluation::new(flag_key, flag_type, evaluation_context, result);
+        let key = recorded.replay_key();
+        let mut all = self.recorded.lock().unwrap_or_else(PoisonError::into_inner);
+        all.retain(|existing| existing.replay_key() != key);
+        all.push(recorded);
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for RecordingProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) {
+        self.provider.initialize(context).await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.provider.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.provider.metadata()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        let result = self
+            .provider
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, FlagType::Bool, evaluation_context, &result);
+        result
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        let result = self
+            .provider
+            .resolve_int_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, FlagType::Int, evaluation_context, &result);
+        result
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        let result = self
+            .provider
+            .resolve_float_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, FlagType::Float, evaluation_context, &result);
+        result
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        let result = self
+            .provider
+            .resolve_string_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, FlagType::String, evaluation_context, &result);
+        result
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let result = self
+            .provider
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, FlagType::Struct, evaluation_context, &result);
+        result
+    }
+}
+
+/// An OpenFeature provider serving the evaluations recorded by a [`RecordingProvider`],
+/// deterministically and without network access.
+///
+/// A flag is served only when it was recorded with the same type and an equal evaluation
+/// context (targeting key and custom fields, struct fields left out). Other evaluations fail
+/// with [`EvaluationErrorCode::FlagNotFound`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::testing::ReplayProvider;
+/// use open_feature::provider::FeatureProvider;
+/// use open_feature::EvaluationContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let provider = ReplayProvider::from_file("tests/data/recorded.json").unwrap();
+///
+///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+///     let enabled = provider.resolve_bool_value("isNewCheckout", &ctx).await.unwrap();
+/// }
+/// ```
+pub struct ReplayProvider {
+    metadata: ProviderMetadata,
+    recorded: HashMap<ReplayKey, RecordedEvaluation>,
+}
+
+impl ReplayProvider {
+    /// Creates a new [`ReplayProvider`] serving the given evaluations.
+    pub fn new(recorded: Vec<RecordedEvaluation>) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("ReplayProvider"),
+            recorded: recorded
+                .into_iter()
+                .map(|recorded| (recorded.replay_key(), recorded))
+                .collect(),
+        }
+    }
+
+    /// Creates a new [`ReplayProvider`] serving the evaluations saved with
+    /// [`RecordingProvider::save`].
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the file couldn't be read or isn't a valid recording.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(Self::new(serde_json::from_str(&json)?))
+    }
+
+    fn replay<T: FromValue>(
+        &self,
+        flag_key: &str,
+        flag_type: FlagType,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let key = ReplayKey::new(
+            flag_key,
+            flag_type,
+            evaluation_context.targeting_key.clone(),
+            &context_fields(evaluation_context),
+        );
+        match self.recorded.get(&key) {
+            Some(recorded) => recorded.to_result(),
+            None => Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!(
+                    "There's no recorded {flag_type} evaluation of '{flag_key}' for the evaluation context."
+                ))
+                .build()),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for ReplayProvider {
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.replay(flag_key, FlagType::Bool, evaluation_context)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.replay(flag_key, FlagType::Int, evaluation_context)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.replay(flag_key, FlagType::Float, evaluation_context)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.replay(flag_key, FlagType::String, evaluation_context)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.replay(flag_key, FlagType::Struct, evaluation_context)
+    }
+}
+
+/// Identifies a recorded evaluation by the flag, the requested type and the evaluation
+/// context, whose custom fields are serialized in key order.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ReplayKey {
+    flag_key: String,
+    flag_type: FlagType,
+    targeting_key: Option<String>,
+    context: String,
+}
+
+impl ReplayKey {
+    fn new(
+        flag_key: &str,
+        flag_type: FlagType,
+        targeting_key: Option<String>,
+        context: &serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        let sorted: BTreeMap<_, _> = context.iter().collect();
+        Self {
+            flag_key: flag_key.to_owned(),
+            flag_type,
+            targeting_key,
+            context: serde_json::to_string(&sorted).unwrap_or_default(),
+        }
+    }
+}
+
+fn parse_error_code(code: &str) -> EvaluationErrorCode {
+    match code {
+        "PROVIDER_NOT_READY" => EvaluationErrorCode::ProviderNotReady,
+        "FLAG_NOT_FOUND" => EvaluationErrorCode::FlagNotFound,
+        "PARSE_ERROR" => EvaluationErrorCode::ParseError,
+        "TYPE_MISMATCH" => EvaluationErrorCode::TypeMismatch,
+        "TARGETING_KEY_MISSING" => EvaluationErrorCode::TargetingKeyMissing,
+        "INVALID_CONTEXT" => EvaluationErrorCode::InvalidContext,
+        _ => EvaluationErrorCode::General(code.to_owned()),
+    }
+}
+
+fn parse_reason(reason: &str) -> EvaluationReason {
+    match reason {
+        "STATIC" => EvaluationReason::Static,
+        "DEFAULT" => EvaluationReason::Default,
+        "TARGETING_MATCH" => EvaluationReason::TargetingMatch,
+        "SPLIT" => EvaluationReason::Split,
+        "CACHED" => EvaluationReason::Cached,
+        "DISABLED" => EvaluationReason::Disabled,
+        "UNKNOWN" => EvaluationReason::Unknown,
+        "ERROR" => EvaluationReason::Error,
+        _ => EvaluationReason::Other(reason.to_owned()),
+    }
+}
@@ -0,0 +1,135 @@
+use open_feature::provider::FeatureProvider;
+use open_feature::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
+};
+use proptest::collection::hash_map;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use std::fmt::Debug;
+use time::OffsetDateTime;
+
+/// The user attribute names with a special meaning in the ConfigCat SDK.
+const WELL_KNOWN_ATTRIBUTES: [&str; 3] = ["Identifier", "Email", "Country"];
+
+/// The error codes the provider documents for flag evaluations.
+const DOCUMENTED_ERROR_CODES: [EvaluationErrorCode; 6] = [
+    EvaluationErrorCode::ProviderNotReady,
+    EvaluationErrorCode::FlagNotFound,
+    EvaluationErrorCode::ParseError,
+    EvaluationErrorCode::TypeMismatch,
+    EvaluationErrorCode::TargetingKeyMissing,
+    EvaluationErrorCode::InvalidContext,
+];
+
+/// Generates attribute names for evaluation contexts: the well-known user attributes of the
+/// ConfigCat SDK, and arbitrary strings.
+pub fn attribute_name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        proptest::sample::select(&WELL_KNOWN_ATTRIBUTES[..]).prop_map(str::to_owned),
+        any::<String>(),
+    ]
+}
+
+/// Generates evaluation context field values of every type, including non-finite numbers, the
+/// whole range of valid dates and struct values.
+pub fn context_field_value() -> impl Strategy<Value = EvaluationContextFieldValue> {
+    let min = OffsetDateTime::new_utc(time::Date::MIN, time::Time::MIDNIGHT).unix_timestamp();
+    let max = OffsetDateTime::new_utc(time::Date::MAX, time::Time::MIDNIGHT).unix_timestamp();
+    prop_oneof![
+        any::<bool>().prop_map(EvaluationContextFieldValue::Bool),
+        any::<i64>().prop_map(EvaluationContextFieldValue::Int),
+        any::<f64>().prop_map(EvaluationContextFieldValue::Float),
+        any::<String>().prop_map(EvaluationContextFieldValue::String),
+        (min..=max).prop_map(|timestamp| {
+            EvaluationContextFieldValue::DateTime(
+                OffsetDateTime::from_unix_timestamp(timestamp)
+                    .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            )
+        }),
+        any::<Vec<String>>().prop_map(EvaluationContextFieldValue::new_struct),
+    ]
+}
+
+/// Generates evaluation contexts with an optional, arbitrary targeting key and up to
+/// `max_fields` custom fields generated with [`attribute_name`] and [`context_field_value`].
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::testing::{check_evaluation, evaluation_context, TestFlags};
+/// use proptest::test_runner::TestRunner;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+///
+/// TestRunner::default()
+///     .run(&evaluation_context(16), |ctx| {
+///         runtime.block_on(check_evaluation(&provider, "isNewCheckout", &ctx))
+///     })
+///     .unwrap();
+/// ```
+pub fn evaluation_context(max_fields: usize) -> impl Strategy<Value = EvaluationContext> {
+    (
+        option::of(any::<String>()),
+        hash_map(attribute_name(), context_field_value(), 0..=max_fields),
+    )
+        .prop_map(|(targeting_key, custom_fields)| EvaluationContext {
+            targeting_key,
+            custom_fields,
+        })
+}
+
+/// Evaluates a flag as each type for the given evaluation context, and fails the test case
+/// when an evaluation fails with an error code the provider doesn't document, like a
+/// `GENERAL` error. Panics of the provider are left to be caught by `proptest`.
+///
+/// # Errors
+///
+/// Returns the failure of the test case, describing the undocumented error.
+pub async fn check_evaluation<P: FeatureProvider>(
+    provider: &P,
+    flag_key: &str,
+    evaluation_context: &EvaluationContext,
+) -> Result<(), TestCaseError> {
+    check(
+        provider
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await,
+    )?;
+    check(
+        provider
+            .resolve_int_value(flag_key, evaluation_context)
+            .await,
+    )?;
+    check(
+        provider
+            .resolve_float_value(flag_key, evaluation_context)
+            .await,
+    )?;
+    check(
+        provider
+            .resolve_string_value(flag_key, evaluation_context)
+            .await,
+    )?;
+    check(
+        provider
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await,
+    )
+}
+
+fn check<T: Debug>(result: Result<T, EvaluationError>) -> Result<(), TestCaseError> {
+    match result {
+        Err(err) if !DOCUMENTED_ERROR_CODES.contains(&err.code) => {
+            Err(TestCaseError::fail(format!(
+                "the evaluation failed with an undocumented error: {}{}",
+                err.code,
+                err.message
+                    .map(|message| format!(" ({message})"))
+                    .unwrap_or_default()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,23 @@
+/// Returns the ID of the current trace and span, when the `otel` or `tracing` feature is enabled.
+///
+/// The OpenTelemetry context takes precedence over the `tracing` span.
+pub(crate) fn current() -> (Option<String>, Option<String>) {
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TraceContextExt;
+
+        let context = opentelemetry::Context::current();
+        let span_context = context.span().span_context().clone();
+        if span_context.is_valid() {
+            return (
+                Some(span_context.trace_id().to_string()),
+                Some(span_context.span_id().to_string()),
+            );
+        }
+    }
+    #[cfg(feature = "tracing")]
+    if let Some(id) = tracing::Span::current().id() {
+        return (None, Some(format!("{:016x}", id.into_u64())));
+    }
+    (None, None)
+}
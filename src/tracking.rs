@@ -0,0 +1,251 @@
+use crate::provider::ConfigCatProvider;
+use crate::trace_context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use open_feature::{EvaluationContext, EvaluationContextFieldValue};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// The maximum number of targeting keys the exposures are kept for.
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// The number of targeting keys evicted at once when [`MAX_TRACKED_KEYS`] is reached, so the
+/// eviction scan runs once per this many new targeting keys at most.
+const EVICTION_BATCH: usize = MAX_TRACKED_KEYS / 10;
+
+/// A variant recently served to the targeting key of a [`TrackingEvent`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FlagExposure {
+    /// The key of the evaluated feature flag or setting.
+    pub flag_key: String,
+    /// The variation ID of the served value.
+    pub variant: String,
+    /// The time of the latest evaluation that served the variant.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Describes a user action tracked with [`ConfigCatProvider::track`], like a conversion.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TrackingEvent {
+    /// The name of the tracked event.
+    pub event_name: String,
+    /// The targeting key of the evaluation context.
+    pub targeting_key: Option<String>,
+    /// The numeric value associated with the event (e.g. the amount of a purchase).
+    pub value: Option<f64>,
+    /// The custom fields of the evaluation context. Struct fields are left out.
+    pub context: serde_json::Map<String, serde_json::Value>,
+    /// The time when the event was tracked.
+    pub timestamp: DateTime<Utc>,
+    /// The ID of the trace the event was tracked in.
+    ///
+    /// Only populated with the `otel` feature, from the current OpenTelemetry context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// The ID of the span the event was tracked in.
+    ///
+    /// Populated with the `otel` feature from the current OpenTelemetry context, or with the
+    /// `tracing` feature from the current `tracing` span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
+    /// The variants recently served to the targeting key, one per flag.
+    ///
+    /// Only populated when exposure correlation is enabled with
+    /// [`crate::ConfigCatProviderBuilder::correlate_exposures`].
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exposures: Vec<FlagExposure>,
+}
+
+impl TrackingEvent {
+    fn new(
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        value: Option<f64>,
+        exposures: Vec<FlagExposure>,
+    ) -> Self {
+        let (trace_id, span_id) = trace_context::current();
+        Self {
+            event_name: event_name.to_owned(),
+            targeting_key: evaluation_context.targeting_key.clone(),
+            value,
+            context: context_fields(evaluation_context),
+            timestamp: Utc::now(),
+            trace_id,
+            span_id,
+            exposures,
+        }
+    }
+}
+
+/// A destination of the [`TrackingEvent`]s tracked with [`ConfigCatProvider::track`].
+///
+/// Unlike [`crate::EvaluationSink`]s, tracking sinks are awaited, so they can deliver the
+/// events directly, e.g. to the same analytics pipeline that receives the evaluation events,
+/// where conversions can be joined with the served variants.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use configcat_openfeature_provider::{ConfigCatProvider, TrackingEvent, TrackingSink};
+///
+/// struct PrintSink;
+///
+/// #[async_trait]
+/// impl TrackingSink for PrintSink {
+///     async fn track(&self, event: &TrackingEvent) {
+///         println!("{}: {:?}", event.event_name, event.value);
+///     }
+/// }
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .tracking_sink(PrintSink)
+///     .build()
+///     .unwrap();
+/// ```
+#[async_trait]
+pub trait TrackingSink: Send + Sync {
+    /// Records a tracking event.
+    async fn track(&self, event: &TrackingEvent);
+}
+
+#[async_trait]
+impl<S: TrackingSink + ?Sized> TrackingSink for Arc<S> {
+    async fn track(&self, event: &TrackingEvent) {
+        (**self).track(event).await;
+    }
+}
+
+impl ConfigCatProvider {
+    /// Tracks a user action, like a conversion, by forwarding it to the [`TrackingSink`]
+    /// configured with [`crate::ConfigCatProviderBuilder::tracking_sink`].
+    ///
+    /// It does nothing when no tracking sink is configured.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///     let ctx = EvaluationContext::default().with_targeting_key("user-1");
+    ///
+    ///     provider.track("checkout-completed", &ctx, Some(99.9)).await;
+    /// }
+    /// ```
+    pub async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        value: Option<f64>,
+    ) {
+        if let Some(sink) = self.tracking_sink() {
+            let exposures = self
+                .exposure_log()
+                .zip(evaluation_context.targeting_key.as_deref())
+                .map(|(log, targeting_key)| log.recent(targeting_key))
+                .unwrap_or_default();
+            let event = TrackingEvent::new(event_name, evaluation_context, value, exposures);
+            sink.track(&event).await;
+        }
+    }
+}
+
+/// Keeps the variants recently served to each targeting key, so they can be attached to the
+/// tracking events.
+pub(crate) struct ExposureLog {
+    max_age: Duration,
+    by_key: Mutex<HashMap<String, Vec<(FlagExposure, Instant)>>>,
+}
+
+impl ExposureLog {
+    pub(crate) fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            by_key: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn record(&self, targeting_key: &str, flag_key: &str, variant: &str) {
+        let now = Instant::now();
+        let mut by_key = self.by_key.lock().unwrap_or_else(PoisonError::into_inner);
+        if !by_key.contains_key(targeting_key) && by_key.len() >= MAX_TRACKED_KEYS {
+            self.evict(&mut by_key, now);
+        }
+        let exposures = by_key.entry(targeting_key.to_owned()).or_default();
+        exposures.retain(|(exposure, _)| exposure.flag_key != flag_key);
+        exposures.push((
+            FlagExposure {
+                flag_key: flag_key.to_owned(),
+                variant: variant.to_owned(),
+                timestamp: Utc::now(),
+            },
+            now,
+        ));
+    }
+
+    pub(crate) fn recent(&self, targeting_key: &str) -> Vec<FlagExposure> {
+        let by_key = self.by_key.lock().unwrap_or_else(PoisonError::into_inner);
+        by_key
+            .get(targeting_key)
+            .into_iter()
+            .flatten()
+            .filter(|(_, at)| at.elapsed() <= self.max_age)
+            .map(|(exposure, _)| exposure.clone())
+            .collect()
+    }
+
+    /// Drops the expired exposures, and when there's still not enough room, the targeting keys
+    /// with the oldest latest exposure, until [`EVICTION_BATCH`] new targeting keys fit.
+    fn evict(&self, by_key: &mut HashMap<String, Vec<(FlagExposure, Instant)>>, now: Instant) {
+        by_key.retain(|_, exposures| {
+            exposures.retain(|(_, at)| now.duration_since(*at) <= self.max_age);
+            !exposures.is_empty()
+        });
+        let limit = MAX_TRACKED_KEYS - EVICTION_BATCH;
+        if by_key.len() <= limit {
+            return;
+        }
+        let mut latest: Vec<(Instant, String)> = by_key
+            .iter()
+            .filter_map(|(key, exposures)| {
+                let at = exposures.iter().map(|(_, at)| *at).max()?;
+                Some((at, key.clone()))
+            })
+            .collect();
+        let excess = by_key.len() - limit;
+        latest.select_nth_unstable_by_key(excess - 1, |(at, _)| *at);
+        for (_, key) in latest.drain(..excess) {
+            by_key.remove(&key);
+        }
+    }
+}
+
+/// Converts the custom fields of an evaluation context to JSON, leaving out the struct fields.
+pub(crate) fn context_fields(
+    evaluation_context: &EvaluationContext,
+) -> serde_json::Map<String, serde_json::Value> {
+    evaluation_context
+        .custom_fields
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), to_json(value)?)))
+        .collect()
+}
+
+fn to_json(value: &EvaluationContextFieldValue) -> Option<serde_json::Value> {
+    match value {
+        EvaluationContextFieldValue::Bool(val) => Some(serde_json::Value::Bool(*val)),
+        EvaluationContextFieldValue::Int(val) => Some(serde_json::Value::from(*val)),
+        EvaluationContextFieldValue::Float(val) => Some(serde_json::Value::from(*val)),
+        EvaluationContextFieldValue::String(val) => Some(serde_json::Value::String(val.clone())),
+        EvaluationContextFieldValue::DateTime(val) => {
+            Some(serde_json::Value::String(val.to_string()))
+        }
+        EvaluationContextFieldValue::Struct(_) => None,
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A transformation applied to the values of text settings before they're returned.
+///
+/// The transformations are registered with [`crate::ConfigCatProviderBuilder::transform_strings`]
+/// for all text settings, or with [`crate::ConfigCatProviderBuilder::transform_string`] for a
+/// given flag key, and applied in the order of registration.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{ConfigCatProvider, StringTransform};
+///
+/// let provider = ConfigCatProvider::builder("sdk-key")
+///     .transform_strings(StringTransform::Trim)
+///     .transform_string("serviceUrl", StringTransform::ExpandEnv(&["SERVICE_HOST"]))
+///     .transform_string(
+///         "serviceUrl",
+///         StringTransform::custom(|url| url.trim_end_matches('/').to_owned()),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum StringTransform {
+    /// Removes the leading and trailing whitespace.
+    Trim,
+    /// Converts the value to lowercase.
+    Lowercase,
+    /// Substitutes the `${NAME}` references with the value of the `NAME` environment variable,
+    /// for the listed variable names only.
+    ///
+    /// The flag values are edited on the ConfigCat Dashboard, so expanding any variable would
+    /// let the editors read the secrets of the process (like `${DATABASE_URL}`) through the
+    /// flag values. References of unlisted or unset variables are kept as is.
+    ExpandEnv(&'static [&'static str]),
+    /// Applies a custom function.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl StringTransform {
+    /// Creates a [`StringTransform::Custom`] transformation applying the given function.
+    pub fn custom(transform: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(transform))
+    }
+
+    fn apply(&self, value: &str) -> String {
+        match self {
+            Self::Trim => value.trim().to_owned(),
+            Self::Lowercase => value.to_lowercase(),
+            Self::ExpandEnv(allowed) => expand_env(value, allowed),
+            Self::Custom(transform) => transform(value),
+        }
+    }
+}
+
+/// The text setting transformations configured for a provider.
+#[derive(Clone, Default)]
+pub(crate) struct StringTransforms {
+    pub(crate) all: Vec<StringTransform>,
+    pub(crate) per_key: HashMap<String, Vec<StringTransform>>,
+}
+
+impl StringTransforms {
+    /// Applies the transformations of all text settings, then the ones of the given flag key.
+    pub(crate) fn apply(&self, flag_key: &str, value: String) -> String {
+        self.all
+            .iter()
+            .chain(self.per_key.get(flag_key).into_iter().flatten())
+            .fold(value, |value, transform| transform.apply(value.as_str()))
+    }
+}
+
+fn expand_env(value: &str, allowed: &[&str]) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let end = start + len + 3;
+        result.push_str(&rest[..start]);
+        let name = &rest[start + 2..end - 1];
+        match std::env::var(name) {
+            Ok(var) if allowed.contains(&name) => result.push_str(var.as_str()),
+            _ => result.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
@@ -0,0 +1,352 @@
+use crate::provider::ConfigCatProvider;
+use open_feature::{EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult};
+#[cfg(feature = "regex")]
+use regex::Regex;
+#[cfg(feature = "regex")]
+use std::collections::HashMap;
+#[cfg(feature = "regex")]
+use std::sync::{PoisonError, RwLock};
+use std::time::Duration;
+#[cfg(feature = "regex")]
+use tokio::sync::watch;
+use url::Url;
+
+/// The accepted duration formats, listed in the parse errors.
+const DURATION_FORMATS: &str =
+    "a number followed by 'ms', 's', 'm', 'h' or 'd' (e.g. '500ms', '30s', '1.5h'), or a sequence of them (e.g. '1h30m')";
+
+/// The accepted byte size formats, listed in the parse errors.
+const BYTES_FORMATS: &str =
+    "a number optionally followed by 'B', a decimal unit ('KB', 'MB', 'GB', 'TB') or a binary unit ('KiB', 'MiB', 'GiB', 'TiB') (e.g. '512KiB', '2MB')";
+
+impl ConfigCatProvider {
+    /// Resolves a text setting holding a humanized duration, like `"30s"` or `"5m"`.
+    ///
+    /// The value is a number followed by a unit (`ms`, `s`, `m`, `h` or `d`), or a sequence of
+    /// them like `"1h30m"`. The number can have a fractional part, like in `"1.5h"`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ParseError`] if the value isn't a duration
+    /// in the accepted formats, and in the same cases as
+    /// [`open_feature::provider::FeatureProvider::resolve_string_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let timeout = provider
+    ///         .resolve_duration("requestTimeout", &EvaluationContext::default())
+    ///         .await
+    ///         .unwrap_or(Duration::from_secs(30));
+    /// }
+    /// ```
+    pub async fn resolve_duration(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<Duration> {
+        let details = self
+            .resolve_string_on(None, flag_key, evaluation_context)
+            .await?;
+        parse_duration(details.value.as_str()).ok_or_else(|| {
+            parse_error(format!(
+                "The value of '{flag_key}' ('{}') is not a duration. Accepted formats: {DURATION_FORMATS}.",
+                details.value
+            ))
+        })
+    }
+
+    /// Resolves a text setting holding a byte size, like `"512KiB"` or `"2MB"`, as a number of
+    /// bytes.
+    ///
+    /// The value is a number followed by an optional unit, either decimal (`KB`, `MB`, `GB`,
+    /// `TB`, multiples of 1000) or binary (`KiB`, `MiB`, `GiB`, `TiB`, multiples of 1024). The
+    /// units are case-insensitive, and the number can have a fractional part, like in
+    /// `"1.5GiB"`, as long as the size is a whole number of bytes.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ParseError`] if the value isn't a byte
+    /// size in the accepted formats, and in the same cases as
+    /// [`open_feature::provider::FeatureProvider::resolve_string_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let max_upload_size = provider
+    ///         .resolve_bytes("maxUploadSize", &EvaluationContext::default())
+    ///         .await
+    ///         .unwrap_or(10 * 1024 * 1024);
+    /// }
+    /// ```
+    pub async fn resolve_bytes(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<u64> {
+        let details = self
+            .resolve_string_on(None, flag_key, evaluation_context)
+            .await?;
+        parse_bytes(details.value.as_str()).ok_or_else(|| {
+            parse_error(format!(
+                "The value of '{flag_key}' ('{}') is not a byte size. Accepted formats: {BYTES_FORMATS}.",
+                details.value
+            ))
+        })
+    }
+
+    /// Resolves a text setting holding an absolute URL, like `"https://api.example.com/v2"`.
+    ///
+    /// Besides being a valid URL, the value has to have a hierarchical structure with a path,
+    /// so values like `"mailto:ops@example.com"` or a host without a scheme (like
+    /// `"localhost:8080"`, which would be parsed as a URL with the `localhost` scheme) are
+    /// rejected.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ParseError`] if the value isn't a valid
+    /// URL, and in the same cases as
+    /// [`open_feature::provider::FeatureProvider::resolve_string_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let endpoint = provider
+    ///         .resolve_url("paymentsEndpoint", &EvaluationContext::default())
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn resolve_url(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<Url> {
+        let details = self
+            .resolve_string_on(None, flag_key, evaluation_context)
+            .await?;
+        let reason = match Url::parse(details.value.as_str()) {
+            Ok(url) if !url.cannot_be_a_base() => return Ok(url),
+            Ok(url) => format!("it has no path after the '{}:' scheme", url.scheme()),
+            Err(err) => err.to_string(),
+        };
+        Err(parse_error(format!(
+            "The value of '{flag_key}' ('{}') is not a valid URL: {reason}.",
+            details.value
+        )))
+    }
+
+    /// Resolves a text setting holding a regular expression, and returns it compiled.
+    ///
+    /// The compiled expressions are cached by their pattern until the config JSON changes, so
+    /// the matching rules controlled by a flag aren't recompiled on each evaluation. At most
+    /// 256 expressions are cached, the cache is cleared when it's full.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ParseError`] if the value isn't a valid
+    /// regular expression, and in the same cases as
+    /// [`open_feature::provider::FeatureProvider::resolve_string_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let blocked_agents = provider
+    ///         .resolve_regex("blockedUserAgents", &EvaluationContext::default())
+    ///         .await
+    ///         .unwrap();
+    ///     let blocked = blocked_agents.is_match("curl/8.5.0");
+    /// }
+    /// ```
+    #[cfg(feature = "regex")]
+    pub async fn resolve_regex(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<Regex> {
+        let details = self
+            .resolve_string_on(None, flag_key, evaluation_context)
+            .await?;
+        self.regex_cache()
+            .get(details.value.as_str())
+            .map_err(|err| {
+                parse_error(format!(
+                    "The value of '{flag_key}' ('{}') is not a valid regular expression: {err}",
+                    details.value
+                ))
+            })
+    }
+
+    /// Returns the number of regular expressions compiled by
+    /// [`ConfigCatProvider::resolve_regex`] that are cached for the current config JSON.
+    #[cfg(feature = "regex")]
+    pub fn cached_regexes(&self) -> usize {
+        self.regex_cache().len()
+    }
+}
+
+/// The maximum number of compiled regular expressions cached by a provider.
+#[cfg(feature = "regex")]
+const MAX_CACHED_REGEXES: usize = 256;
+
+/// The regular expressions compiled from the values of text settings.
+///
+/// The cache is cleared when the config JSON changes, and when it's full, so patterns varying
+/// by user (e.g. with interpolated placeholders) or clients without a config JSON version
+/// (created with [`ConfigCatProvider::new`]) can't grow it without limit.
+#[cfg(feature = "regex")]
+pub(crate) struct RegexCache {
+    versions: Option<watch::Receiver<u64>>,
+    compiled: RwLock<(u64, HashMap<String, Regex>)>,
+}
+
+#[cfg(feature = "regex")]
+impl RegexCache {
+    /// Creates a cache cleared each time a new version is sent to the given receiver.
+    pub(crate) fn new(versions: Option<watch::Receiver<u64>>) -> Self {
+        Self {
+            versions,
+            compiled: RwLock::default(),
+        }
+    }
+
+    fn version(&self) -> u64 {
+        self.versions
+            .as_ref()
+            .map_or(0, |versions| *versions.borrow())
+    }
+
+    /// Returns the number of expressions compiled for the current config JSON.
+    fn len(&self) -> usize {
+        let compiled = self.compiled.read().unwrap_or_else(PoisonError::into_inner);
+        if compiled.0 == self.version() {
+            compiled.1.len()
+        } else {
+            0
+        }
+    }
+
+    fn get(&self, pattern: &str) -> Result<Regex, regex::Error> {
+        let version = self.version();
+        {
+            let compiled = self.compiled.read().unwrap_or_else(PoisonError::into_inner);
+            if let (true, Some(regex)) = (compiled.0 == version, compiled.1.get(pattern)) {
+                return Ok(regex.clone());
+            }
+        }
+        let regex = Regex::new(pattern)?;
+        let mut compiled = self
+            .compiled
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if compiled.0 != version || compiled.1.len() >= MAX_CACHED_REGEXES {
+            *compiled = (version, HashMap::new());
+        }
+        compiled.1.insert(pattern.to_owned(), regex.clone());
+        Ok(regex)
+    }
+}
+
+fn parse_error(message: String) -> EvaluationError {
+    EvaluationError::builder()
+        .code(EvaluationErrorCode::ParseError)
+        .message(message)
+        .build()
+}
+
+/// Splits a sequence of numbers with units, returning the number and the unit of each.
+fn parse_quantities(text: &str) -> Option<Vec<(&str, &str)>> {
+    let mut quantities = Vec::new();
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            return None;
+        }
+        let number = &rest[..number_len];
+        rest = rest[number_len..].trim_start();
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        quantities.push((number, &rest[..unit_len]));
+        rest = rest[unit_len..].trim_start();
+    }
+    Some(quantities)
+}
+
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut secs = 0.0;
+    for (number, unit) in parse_quantities(text)? {
+        let unit_secs = match unit {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3_600.0,
+            "d" => 86_400.0,
+            _ => return None,
+        };
+        secs += number.parse::<f64>().ok()? * unit_secs;
+    }
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+fn parse_bytes(text: &str) -> Option<u64> {
+    let [(number, unit)] = parse_quantities(text)?[..] else {
+        return None;
+    };
+    let unit_bytes: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return None,
+    };
+    // Computed from the digits, as the size has to be a whole number of bytes.
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let scale = 10_u128.checked_pow(u32::try_from(fraction.len()).ok()?)?;
+    let digits = format!("{whole}{fraction}").parse::<u128>().ok()?;
+    let bytes = digits.checked_mul(u128::from(unit_bytes))?;
+    if bytes % scale != 0 {
+        return None;
+    }
+    u64::try_from(bytes / scale).ok()
+}
@@ -0,0 +1,154 @@
+use crate::provider::{to_res_details, to_struct_details, ConfigCatProvider};
+use configcat::User;
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationResult, StructValue};
+
+impl ConfigCatProvider {
+    /// Resolves a feature flag for the given ConfigCat User Object.
+    ///
+    /// Useful for code that already builds ConfigCat users (e.g. migrated from the ConfigCat
+    /// SDK), as the user is passed to the evaluation as is, without converting its attributes
+    /// from and to an [`EvaluationContext`]. The hooks, sinks and callbacks of the provider
+    /// see an evaluation context with the user's identifier as the targeting key.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the same cases as the [`open_feature::provider::FeatureProvider`]
+    /// methods, except for the evaluation context conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::User;
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let user = User::new("user-id").custom("Roles", ["admin", "billing"]);
+    ///     let enabled = provider
+    ///         .resolve_bool_with_user("newDashboard", &user)
+    ///         .await
+    ///         .map_or(false, |details| details.value);
+    /// }
+    /// ```
+    pub async fn resolve_bool_with_user(
+        &self,
+        flag_key: &str,
+        user: &User,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.evaluate(
+            None,
+            flag_key,
+            &user_context(user),
+            Some(user),
+            false,
+            |details, _| to_res_details(details),
+        )
+        .await
+    }
+
+    /// Resolves a whole number setting for the given ConfigCat User Object.
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`].
+    pub async fn resolve_int_with_user(
+        &self,
+        flag_key: &str,
+        user: &User,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.evaluate(
+            None,
+            flag_key,
+            &user_context(user),
+            Some(user),
+            0,
+            |details, _| to_res_details(details),
+        )
+        .await
+    }
+
+    /// Resolves a decimal number setting for the given ConfigCat User Object.
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`].
+    pub async fn resolve_float_with_user(
+        &self,
+        flag_key: &str,
+        user: &User,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.evaluate(
+            None,
+            flag_key,
+            &user_context(user),
+            Some(user),
+            0.0,
+            |details, _| to_res_details(details),
+        )
+        .await
+    }
+
+    /// Resolves a text setting for the given ConfigCat User Object.
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`].
+    pub async fn resolve_string_with_user(
+        &self,
+        flag_key: &str,
+        user: &User,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.evaluate(
+            None,
+            flag_key,
+            &user_context(user),
+            Some(user),
+            String::default(),
+            |details, user| self.to_string_details(flag_key, details, user),
+        )
+        .await
+    }
+
+    /// Resolves a text setting holding an object for the given ConfigCat User Object.
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigCatProvider::resolve_bool_with_user`]. It also fails when the setting's
+    /// value isn't an object in the configured [`crate::StructFormat`].
+    pub async fn resolve_struct_with_user(
+        &self,
+        flag_key: &str,
+        user: &User,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let format = self.struct_format(flag_key);
+        self.evaluate(
+            None,
+            flag_key,
+            &user_context(user),
+            Some(user),
+            String::default(),
+            |details, _| to_struct_details(details, format),
+        )
+        .await
+    }
+}
+
+/// Creates the evaluation context reported for an evaluation with the given user.
+fn user_context(user: &User) -> EvaluationContext {
+    let mut context = EvaluationContext::default();
+    if let Some(identifier) = user.get(User::IDENTIFIER) {
+        context = context.with_targeting_key(identifier.to_string());
+    }
+    context
+}
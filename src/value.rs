@@ -0,0 +1,163 @@
+use crate::provider::ConfigCatProvider;
+use async_trait::async_trait;
+use open_feature::provider::ResolutionDetails;
+use open_feature::{EvaluationContext, EvaluationResult, StructValue, Value};
+
+/// Converts an OpenFeature [`Value`] to its JSON representation.
+pub(crate) fn to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Bool(val) => serde_json::Value::Bool(*val),
+        Value::Int(val) => serde_json::Value::from(*val),
+        Value::Float(val) => serde_json::Value::from(*val),
+        Value::String(val) => serde_json::Value::String(val.clone()),
+        Value::Array(arr) => serde_json::Value::Array(arr.iter().map(to_json).collect()),
+        Value::Struct(struct_val) => serde_json::Value::Object(
+            struct_val
+                .fields
+                .iter()
+                .map(|(key, val)| (key.clone(), to_json(val)))
+                .collect(),
+        ),
+    }
+}
+
+/// Converts an OpenFeature [`Value`] back to the value type of a resolution.
+pub(crate) trait FromValue: Sized {
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+impl FromValue for bool {
+    fn from_value(value: Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: Value) -> Option<Self> {
+        value.as_i64()
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: Value) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::String(val) => Some(val),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for StructValue {
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Struct(val) => Some(val),
+            _ => None,
+        }
+    }
+}
+
+/// Converts the value of a resolution to an OpenFeature [`Value`].
+pub(crate) fn to_value_details<R: Into<Value>>(
+    details: ResolutionDetails<R>,
+) -> ResolutionDetails<Value> {
+    ResolutionDetails {
+        value: details.value.into(),
+        reason: details.reason,
+        variant: details.variant,
+        flag_metadata: details.flag_metadata,
+    }
+}
+
+/// Converts a ConfigCat SDK value to an OpenFeature [`Value`].
+pub(crate) fn from_sdk_value(value: configcat::Value) -> Value {
+    match value {
+        configcat::Value::Bool(val) => Value::Bool(val),
+        configcat::Value::Int(val) => Value::Int(val),
+        configcat::Value::Float(val) => Value::Float(val),
+        configcat::Value::String(val) => Value::String(val),
+    }
+}
+
+/// Converts the value of a resolution back from an OpenFeature [`Value`].
+pub(crate) fn from_value_details<R: FromValue>(
+    details: ResolutionDetails<Value>,
+) -> Option<ResolutionDetails<R>> {
+    Some(ResolutionDetails {
+        value: R::from_value(details.value)?,
+        reason: details.reason,
+        variant: details.variant,
+        flag_metadata: details.flag_metadata,
+    })
+}
+
+/// A flag value type that can be resolved with a [`ConfigCatProvider`].
+///
+/// It's implemented for `bool` (feature flags), `i64` (whole number settings), `f64` (decimal
+/// number settings) and `String` (text settings).
+#[async_trait]
+pub trait FlagValue: Sized {
+    /// Resolves the flag identified by `flag_key` for the given evaluation context.
+    async fn resolve(
+        provider: &ConfigCatProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>>;
+}
+
+#[async_trait]
+impl FlagValue for bool {
+    async fn resolve(
+        provider: &ConfigCatProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_bool_on(None, flag_key, evaluation_context)
+            .await
+    }
+}
+
+#[async_trait]
+impl FlagValue for i64 {
+    async fn resolve(
+        provider: &ConfigCatProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_int_on(None, flag_key, evaluation_context)
+            .await
+    }
+}
+
+#[async_trait]
+impl FlagValue for f64 {
+    async fn resolve(
+        provider: &ConfigCatProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_float_on(None, flag_key, evaluation_context)
+            .await
+    }
+}
+
+#[async_trait]
+impl FlagValue for String {
+    async fn resolve(
+        provider: &ConfigCatProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_string_on(None, flag_key, evaluation_context)
+            .await
+    }
+}
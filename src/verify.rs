@@ -0,0 +1,321 @@
+use crate::provider::ConfigCatProvider;
+use open_feature::{EvaluationError, EvaluationErrorCode, EvaluationResult};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The type a feature flag is requested as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagType {
+    /// A feature flag.
+    Bool,
+    /// A text setting.
+    String,
+    /// A whole number setting.
+    Int,
+    /// A decimal number setting.
+    Float,
+    /// A text setting holding a JSON object.
+    Struct,
+}
+
+impl FlagType {
+    /// Returns whether a setting of the given config JSON setting type can be requested as
+    /// this type.
+    fn matches(self, setting_type: u64) -> bool {
+        matches!(
+            (self, setting_type),
+            (Self::Bool, 0) | (Self::String | Self::Struct, 1) | (Self::Int, 2) | (Self::Float, 3)
+        )
+    }
+
+    fn from_setting_type(setting_type: u64) -> Option<Self> {
+        match setting_type {
+            0 => Some(Self::Bool),
+            1 => Some(Self::String),
+            2 => Some(Self::Int),
+            3 => Some(Self::Float),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FlagType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Bool => "bool",
+            Self::String => "string",
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Struct => "struct",
+        })
+    }
+}
+
+/// A declared feature flag whose setting type differs from the declared type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlagTypeMismatch {
+    /// The key of the feature flag.
+    pub flag_key: String,
+    /// The declared type.
+    pub expected: FlagType,
+    /// The type of the setting in the config JSON, or `None` when it's unknown.
+    pub actual: Option<FlagType>,
+}
+
+/// The result of [`ConfigCatProvider::verify`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// The keys of the declared feature flags missing from the config JSON.
+    pub missing: Vec<String>,
+    /// The declared feature flags whose setting type differs from the declared type.
+    pub mismatched: Vec<FlagTypeMismatch>,
+}
+
+impl VerificationReport {
+    /// Returns whether all declared feature flags exist with the declared types.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return f.write_str("All declared flags exist with the declared types.");
+        }
+        let mut problems = Vec::new();
+        if !self.missing.is_empty() {
+            problems.push(format!("Missing flags: '{}'.", self.missing.join("', '")));
+        }
+        for mismatch in &self.mismatched {
+            let actual = mismatch
+                .actual
+                .map_or_else(|| "unknown".to_owned(), |actual| actual.to_string());
+            problems.push(format!(
+                "'{}' is declared as {}, but it's {actual}.",
+                mismatch.flag_key, mismatch.expected
+            ));
+        }
+        f.write_str(&problems.join(" "))
+    }
+}
+
+/// A feature flag or setting of the config JSON, returned by
+/// [`ConfigCatProvider::flag_definitions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlagDefinition {
+    /// The key of the feature flag.
+    pub flag_key: String,
+    /// The type of the setting.
+    pub flag_type: FlagType,
+    /// The variation IDs of the values the flag can serve: the default value's, followed by
+    /// the ones of the targeting rules and percentage options, without duplicates.
+    pub variation_ids: Vec<String>,
+}
+
+/// A declared flag of a [`FlagSchema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SchemaFlag {
+    flag_key: String,
+    flag_type: FlagType,
+    required: bool,
+}
+
+/// The feature flags an application expects in the config JSON, with their types.
+///
+/// It's validated against the downloaded config JSON when the provider initializes (see
+/// [`crate::ConfigCatProviderBuilder::schema`]), or on demand with
+/// [`ConfigCatProvider::validate`].
+///
+/// # Examples
+///
+/// ```
+/// use configcat_openfeature_provider::{FlagSchema, FlagType};
+///
+/// let schema = FlagSchema::new()
+///     .required("isNewCheckout", FlagType::Bool)
+///     .optional("maxItems", FlagType::Int);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlagSchema {
+    flags: Vec<SchemaFlag>,
+}
+
+impl FlagSchema {
+    /// Creates an empty [`FlagSchema`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a feature flag that must exist in the config JSON with the given type.
+    pub fn required(self, flag_key: &str, flag_type: FlagType) -> Self {
+        self.flag(flag_key, flag_type, true)
+    }
+
+    /// Declares a feature flag that may be missing from the config JSON, but must have the
+    /// given type when it exists.
+    pub fn optional(self, flag_key: &str, flag_type: FlagType) -> Self {
+        self.flag(flag_key, flag_type, false)
+    }
+
+    fn flag(mut self, flag_key: &str, flag_type: FlagType, required: bool) -> Self {
+        self.flags.push(SchemaFlag {
+            flag_key: flag_key.to_owned(),
+            flag_type,
+            required,
+        });
+        self
+    }
+}
+
+impl ConfigCatProvider {
+    /// Verifies that the declared feature flags exist in the downloaded config JSON with
+    /// matching setting types, so deployments can fail fast when the config drifts from the
+    /// code.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ProviderNotReady`] when there's no
+    /// downloaded config JSON to verify (e.g. when the provider uses local-only flag overrides).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::{ConfigCatProvider, FlagType};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let report = provider
+    ///         .verify(&[("isNewCheckout", FlagType::Bool), ("maxItems", FlagType::Int)])
+    ///         .await
+    ///         .unwrap();
+    ///     assert!(report.is_ok(), "{report}");
+    /// }
+    /// ```
+    pub async fn verify(&self, flags: &[(&str, FlagType)]) -> EvaluationResult<VerificationReport> {
+        let schema = flags
+            .iter()
+            .fold(FlagSchema::new(), |schema, (flag_key, flag_type)| {
+                schema.required(flag_key, *flag_type)
+            });
+        self.validate(&schema).await
+    }
+
+    /// Validates the downloaded config JSON against the given schema, and reports all the
+    /// missing required flags and type mismatches at once.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`EvaluationErrorCode::ProviderNotReady`] when there's no
+    /// downloaded config JSON to validate (e.g. when the provider uses local-only flag overrides).
+    pub async fn validate(&self, schema: &FlagSchema) -> EvaluationResult<VerificationReport> {
+        let settings = self.settings().await?;
+        let mut report = VerificationReport::default();
+        for flag in &schema.flags {
+            let Some(setting) = settings.get(self.resolve_key(&flag.flag_key).as_ref()) else {
+                if flag.required {
+                    report.missing.push(flag.flag_key.clone());
+                }
+                continue;
+            };
+            let setting_type = setting.get("t").and_then(serde_json::Value::as_u64);
+            if !setting_type.is_some_and(|setting_type| flag.flag_type.matches(setting_type)) {
+                report.mismatched.push(FlagTypeMismatch {
+                    flag_key: flag.flag_key.clone(),
+                    expected: flag.flag_type,
+                    actual: setting_type.and_then(FlagType::from_setting_type),
+                });
+            }
+        }
+        Ok(report)
+    }
+
+    /// Returns the feature flags and settings of the downloaded config JSON with their types
+    /// and variation IDs, in alphabetical order of the flag keys.
+    ///
+    /// Useful for admin UIs and validation tooling to introspect the flag catalog. The result
+    /// is empty when there's no downloaded config JSON (e.g. when the provider uses local-only
+    /// flag overrides).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     for flag in provider.flag_definitions().await {
+    ///         println!("{}: {} {:?}", flag.flag_key, flag.flag_type, flag.variation_ids);
+    ///     }
+    /// }
+    /// ```
+    pub async fn flag_definitions(&self) -> Vec<FlagDefinition> {
+        let settings = self.settings().await.unwrap_or_default();
+        let mut definitions: Vec<_> = settings
+            .into_iter()
+            .filter_map(|(flag_key, setting)| {
+                let flag_type = setting
+                    .get("t")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(FlagType::from_setting_type)?;
+                Some(FlagDefinition {
+                    flag_key: self.strip_key_prefix(flag_key)?,
+                    flag_type,
+                    variation_ids: variation_ids(&setting),
+                })
+            })
+            .collect();
+        definitions.sort_unstable_by(|a, b| a.flag_key.cmp(&b.flag_key));
+        definitions
+    }
+
+    /// Returns the settings of the downloaded config JSON.
+    pub(crate) async fn settings(
+        &self,
+    ) -> EvaluationResult<serde_json::Map<String, serde_json::Value>> {
+        let settings =
+            self.export_config()
+                .await
+                .and_then(|mut config| match config.get_mut("f")?.take() {
+                    serde_json::Value::Object(settings) => Some(settings),
+                    _ => None,
+                });
+        settings.ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("There's no downloaded config JSON to verify.")
+                .build()
+        })
+    }
+}
+
+/// Collects the variation IDs of a setting of the config JSON.
+fn variation_ids(setting: &serde_json::Value) -> Vec<String> {
+    fn array_items(value: Option<&serde_json::Value>) -> impl Iterator<Item = &serde_json::Value> {
+        value
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+    }
+
+    let mut served = vec![setting];
+    served.extend(array_items(setting.get("p")));
+    for rule in array_items(setting.get("r")) {
+        served.extend(rule.get("s"));
+        served.extend(array_items(rule.get("p")));
+    }
+    let mut ids = Vec::new();
+    for value in served {
+        if let Some(id) = value.get("i").and_then(serde_json::Value::as_str) {
+            if !ids.iter().any(|known| known == id) {
+                ids.push(id.to_owned());
+            }
+        }
+    }
+    ids
+}
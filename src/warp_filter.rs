@@ -0,0 +1,45 @@
+use crate::flags::{header_context, Flags, USER_ID_HEADER};
+use crate::provider::ConfigCatProvider;
+use open_feature::EvaluationContext;
+use std::convert::Infallible;
+use warp::http::HeaderMap;
+use warp::Filter;
+
+/// Returns a warp filter that extracts [`Flags`] for each request.
+///
+/// The evaluation context is the [`EvaluationContext`] request extension, or when there is
+/// none, a context with the value of the [`USER_ID_HEADER`] header as the targeting key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat_openfeature_provider::{flags_filter, ConfigCatProvider, Flags};
+/// use warp::Filter;
+///
+/// let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+/// let route = warp::path::end()
+///     .and(flags_filter(provider))
+///     .then(|flags: Flags| async move {
+///         if flags.bool("isAwesomeFeatureEnabled", false).await {
+///             "awesome"
+///         } else {
+///             "regular"
+///         }
+///     });
+/// ```
+pub fn flags_filter(
+    provider: ConfigCatProvider,
+) -> impl Filter<Extract = (Flags,), Error = Infallible> + Clone {
+    warp::ext::optional::<EvaluationContext>()
+        .and(warp::header::headers_cloned())
+        .map(move |ctx: Option<EvaluationContext>, headers: HeaderMap| {
+            let evaluation_context = ctx.unwrap_or_else(|| {
+                header_context(
+                    headers
+                        .get(USER_ID_HEADER)
+                        .and_then(|value| value.to_str().ok()),
+                )
+            });
+            Flags::new(provider.clone(), evaluation_context)
+        })
+}
@@ -0,0 +1,215 @@
+use crate::bulk::FlagSet;
+use crate::provider::ConfigCatProvider;
+use crate::runtime;
+use crate::tap::ConfigTap;
+use crate::value::FlagValue;
+use open_feature::EvaluationContext;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+impl ConfigCatProvider {
+    /// Watches the value of a feature flag for the given evaluation context.
+    ///
+    /// The returned receiver holds the current value, and gets the new one each time a config
+    /// JSON change flips it, so applications can react to flag changes without polling. The
+    /// default value is used when the evaluation fails. The flag is re-evaluated in the
+    /// background until all receivers are dropped.
+    ///
+    /// Changes are only tracked when the provider was created with [`ConfigCatProvider::builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::ConfigCatProvider;
+    /// use open_feature::EvaluationContext;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let mut maintenance = provider
+    ///         .watch_bool("maintenanceMode", EvaluationContext::default(), false)
+    ///         .await;
+    ///     while maintenance.changed().await.is_ok() {
+    ///         println!("maintenance mode: {}", *maintenance.borrow());
+    ///     }
+    /// }
+    /// ```
+    pub async fn watch_bool(
+        &self,
+        flag_key: &str,
+        evaluation_context: EvaluationContext,
+        default: bool,
+    ) -> watch::Receiver<bool> {
+        self.watch(flag_key, evaluation_context, default).await
+    }
+
+    /// Watches the value of a whole number setting for the given evaluation context.
+    ///
+    /// See [`ConfigCatProvider::watch_bool`] for details.
+    pub async fn watch_int(
+        &self,
+        flag_key: &str,
+        evaluation_context: EvaluationContext,
+        default: i64,
+    ) -> watch::Receiver<i64> {
+        self.watch(flag_key, evaluation_context, default).await
+    }
+
+    /// Watches the value of a decimal number setting for the given evaluation context.
+    ///
+    /// See [`ConfigCatProvider::watch_bool`] for details.
+    pub async fn watch_float(
+        &self,
+        flag_key: &str,
+        evaluation_context: EvaluationContext,
+        default: f64,
+    ) -> watch::Receiver<f64> {
+        self.watch(flag_key, evaluation_context, default).await
+    }
+
+    /// Watches the value of a text setting for the given evaluation context.
+    ///
+    /// See [`ConfigCatProvider::watch_bool`] for details.
+    pub async fn watch_string(
+        &self,
+        flag_key: &str,
+        evaluation_context: EvaluationContext,
+        default: &str,
+    ) -> watch::Receiver<String> {
+        self.watch(flag_key, evaluation_context, default.to_owned())
+            .await
+    }
+
+    async fn watch<T: FlagValue + Clone + PartialEq + Send + Sync + 'static>(
+        &self,
+        flag_key: &str,
+        evaluation_context: EvaluationContext,
+        default: T,
+    ) -> watch::Receiver<T> {
+        // Subscribe before the first evaluation, so changes in the meantime aren't missed.
+        let changes = self.source().tap().map(ConfigTap::subscribe);
+        let initial = T::resolve(self, flag_key, &evaluation_context)
+            .await
+            .map_or_else(|_| default.clone(), |details| details.value);
+        let (sender, receiver) = watch::channel(initial);
+        let Some(mut versions) = changes else {
+            return receiver;
+        };
+        let provider = self.clone();
+        let flag_key = flag_key.to_owned();
+        runtime::spawn(async move {
+            while next_change(&sender, &mut versions).await {
+                let value = T::resolve(&provider, &flag_key, &evaluation_context)
+                    .await
+                    .map_or_else(|_| default.clone(), |details| details.value);
+                sender.send_if_modified(|current| {
+                    if *current == value {
+                        return false;
+                    }
+                    *current = value;
+                    true
+                });
+            }
+        });
+        receiver
+    }
+
+    /// Binds a [`FlagSet`] to the given evaluation context.
+    ///
+    /// The returned handle holds the flag set evaluated against the current config JSON, and
+    /// gets re-evaluated in the background each time the config JSON changes, until all clones
+    /// of the handle are dropped. Long-lived components can read the current flag values
+    /// with [`FlagBinding::load`] without awaiting anything.
+    ///
+    /// Changes are only tracked when the provider was created with [`ConfigCatProvider::builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat_openfeature_provider::{ConfigCatProvider, FlagSet, FlagValues};
+    /// use open_feature::EvaluationContext;
+    ///
+    /// struct WorkerFlags {
+    ///     batch_size: i64,
+    /// }
+    ///
+    /// impl FlagSet for WorkerFlags {
+    ///     fn from_flags(flags: &FlagValues) -> Self {
+    ///         Self {
+    ///             batch_size: flags.int("batchSize", 100),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let provider = ConfigCatProvider::builder("sdk-key").build().unwrap();
+    ///
+    ///     let flags = provider
+    ///         .bind::<WorkerFlags>(EvaluationContext::default())
+    ///         .await;
+    ///     loop {
+    ///         let batch_size = flags.load().batch_size;
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub async fn bind<S: FlagSet + Send + Sync + 'static>(
+        &self,
+        evaluation_context: EvaluationContext,
+    ) -> FlagBinding<S> {
+        let changes = self.source().tap().map(ConfigTap::subscribe);
+        let initial = self.resolve_set::<S>(&evaluation_context).await;
+        let (sender, receiver) = watch::channel(Arc::new(initial));
+        if let Some(mut versions) = changes {
+            let provider = self.clone();
+            runtime::spawn(async move {
+                while next_change(&sender, &mut versions).await {
+                    let flags = provider.resolve_set::<S>(&evaluation_context).await;
+                    sender.send_replace(Arc::new(flags));
+                }
+            });
+        }
+        FlagBinding { receiver }
+    }
+}
+
+/// A [`FlagSet`] kept up to date with the config JSON changes.
+///
+/// Created by [`ConfigCatProvider::bind`].
+pub struct FlagBinding<S> {
+    receiver: watch::Receiver<Arc<S>>,
+}
+
+impl<S> FlagBinding<S> {
+    /// Returns the flag set evaluated against the latest config JSON.
+    pub fn load(&self) -> Arc<S> {
+        self.receiver.borrow().clone()
+    }
+
+    /// Waits until the flag set gets re-evaluated, then returns the new one.
+    ///
+    /// Returns `None` when the flag set can't change anymore.
+    pub async fn changed(&mut self) -> Option<Arc<S>> {
+        self.receiver.changed().await.ok()?;
+        Some(self.receiver.borrow_and_update().clone())
+    }
+}
+
+impl<S> Clone for FlagBinding<S> {
+    fn clone(&self) -> Self {
+        Self {
+            receiver: self.receiver.clone(),
+        }
+    }
+}
+
+/// Waits for the next config JSON change. Returns `false` when there are no receivers left
+/// or no more changes can happen.
+async fn next_change<T>(sender: &watch::Sender<T>, versions: &mut watch::Receiver<u64>) -> bool {
+    tokio::select! {
+        () = sender.closed() => false,
+        result = versions.changed() => result.is_ok(),
+    }
+}
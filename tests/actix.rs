@@ -0,0 +1,71 @@
+#![cfg(feature = "actix")]
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App, HttpMessage};
+use common::provider;
+use configcat_openfeature_provider::{configure_flags, Flags, USER_ID_HEADER};
+use open_feature::EvaluationContext;
+
+async fn handler(flags: Flags) -> String {
+    format!(
+        "{} {} {}",
+        flags.bool("disabledFeature", false).await,
+        flags.int("intSetting", 0).await,
+        flags.string("non-existing", "default").await
+    )
+}
+
+#[actix_web::test]
+async fn user_id_header() {
+    let app = test::init_service(
+        App::new()
+            .configure(configure_flags(provider()))
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let body = test::call_and_read_body(&app, test::TestRequest::get().uri("/").to_request()).await;
+    assert_eq!("false 5 default", body);
+
+    let request = test::TestRequest::get()
+        .uri("/")
+        .insert_header((USER_ID_HEADER, "example@matching.com"))
+        .to_request();
+    assert_eq!(
+        "true 5 default",
+        test::call_and_read_body(&app, request).await
+    );
+}
+
+#[actix_web::test]
+async fn context_extension() {
+    let app = test::init_service(
+        App::new()
+            .configure(configure_flags(provider()))
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let request = test::TestRequest::get()
+        .uri("/")
+        .insert_header((USER_ID_HEADER, "other@example.com"))
+        .to_request();
+    request
+        .extensions_mut()
+        .insert(EvaluationContext::default().with_targeting_key("example@matching.com"));
+
+    assert_eq!(
+        "true 5 default",
+        test::call_and_read_body(&app, request).await
+    );
+}
+
+#[actix_web::test]
+async fn missing_provider() {
+    let app = test::init_service(App::new().route("/", web::get().to(handler))).await;
+
+    let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "axum")]
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::routing::get;
+use axum::{Extension, Router};
+use configcat_openfeature_provider::{Flags, USER_ID_HEADER};
+use open_feature::EvaluationContext;
+use tower::ServiceExt;
+
+async fn handler(flags: Flags) -> String {
+    format!(
+        "{} {} {}",
+        flags.bool("disabledFeature", false).await,
+        flags.int("intSetting", 0).await,
+        flags.string("non-existing", "default").await
+    )
+}
+
+fn app() -> Router {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    Router::new().route("/", get(handler)).with_state(provider)
+}
+
+async fn call(app: Router, request: Request<Body>) -> String {
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn without_context() {
+    let body = call(app(), Request::get("/").body(Body::empty()).unwrap()).await;
+
+    assert_eq!("false 5 default", body);
+}
+
+#[tokio::test]
+async fn user_id_header() {
+    let request = Request::get("/")
+        .header(USER_ID_HEADER, "example@matching.com")
+        .body(Body::empty())
+        .unwrap();
+
+    assert_eq!("true 5 default", call(app(), request).await);
+}
+
+#[tokio::test]
+async fn context_extension() {
+    let app = app().layer(Extension(
+        EvaluationContext::default().with_targeting_key("example@matching.com"),
+    ));
+    let request = Request::get("/")
+        .header(USER_ID_HEADER, "other@example.com")
+        .body(Body::empty())
+        .unwrap();
+
+    assert_eq!("true 5 default", call(app, request).await);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use configcat_openfeature_provider::{BlockingConfigCatProvider, ConfigCatProvider};
+use open_feature::{EvaluationContext, EvaluationErrorCode, EvaluationReason, Value};
+
+fn provider() -> BlockingConfigCatProvider {
+    BlockingConfigCatProvider::new(common::builder("tests/data/test_json_complex.json")).unwrap()
+}
+
+#[test]
+fn evaluates_synchronously() {
+    let provider = provider();
+    let ctx = EvaluationContext::default().with_targeting_key("user-1");
+
+    assert!(provider
+        .get_bool_value("enabledFeature", Some(&ctx))
+        .unwrap());
+    assert_eq!(5, provider.get_int_value("intSetting", None).unwrap());
+    assert!((provider.get_float_value("doubleSetting", None).unwrap() - 1.2).abs() < f64::EPSILON);
+    assert_eq!(
+        "test",
+        provider.get_string_value("stringSetting", None).unwrap()
+    );
+    let object = provider.get_struct_value("objectSetting", None).unwrap();
+    assert_eq!(Some(&Value::Bool(true)), object.fields.get("bool_field"));
+
+    let details = provider.resolve_bool_value("enabledFeature", None).unwrap();
+    assert_eq!(Some("v-enabled".to_owned()), details.variant);
+    assert_eq!(Some(EvaluationReason::Default), details.reason);
+}
+
+#[test]
+fn returns_evaluation_errors() {
+    let provider = provider();
+
+    let err = provider.get_bool_value("missing", None).unwrap_err();
+    assert_eq!(EvaluationErrorCode::FlagNotFound, err.code);
+    let err = provider.get_int_value("stringSetting", None).unwrap_err();
+    assert_eq!(EvaluationErrorCode::TypeMismatch, err.code);
+}
+
+#[test]
+fn runs_async_work() {
+    let provider = provider();
+
+    let keys = provider.block_on(provider.provider().flag_keys());
+
+    assert_eq!(6, keys.len());
+    assert!(provider.refresh().is_err());
+}
+
+#[test]
+fn fails_with_invalid_sdk_key() {
+    assert!(BlockingConfigCatProvider::new(ConfigCatProvider::builder("invalid")).is_err());
+}
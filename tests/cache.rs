@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, ProviderCache};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> String {
+    std::fs::read_to_string("tests/data/test_json_complex.json").unwrap()
+}
+
+#[derive(Clone, Default)]
+struct MapCache(Arc<Mutex<HashMap<String, String>>>);
+
+#[async_trait]
+impl ProviderCache for MapCache {
+    async fn read(&self, key: &str) -> Option<String> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    async fn write(&self, key: &str, value: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), value.to_owned());
+    }
+}
+
+/// Serves the same entry for every key.
+struct FixedCache(String);
+
+#[async_trait]
+impl ProviderCache for FixedCache {
+    async fn read(&self, _: &str) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    async fn write(&self, _: &str, _: &str) {}
+}
+
+#[tokio::test]
+async fn writes_fetched_config() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-1\"")
+        .with_body(config_json())
+        .create_async()
+        .await;
+    let cache = MapCache::default();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+
+    let mut entry = None;
+    for _ in 0..50 {
+        entry = cache.0.lock().unwrap().values().next().cloned();
+        if entry.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let entry = entry.unwrap();
+    assert!(entry.contains("\"etag-1\""));
+    assert!(entry.contains("enabledFeature"));
+}
+
+#[tokio::test]
+async fn fresh_cache_entry_skips_fetch() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .expect(0)
+        .create_async()
+        .await;
+    let fetch_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::LazyLoad(Duration::from_secs(60)))
+        .cache(FixedCache(format!(
+            "{fetch_time}\n\"etag-1\"\n{}",
+            config_json()
+        )))
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert!(result.value);
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(1, metrics.attempts);
+    assert_eq!(1, metrics.successes);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn stale_cache_entry_is_refetched() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .expect(1)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::LazyLoad(Duration::from_secs(60)))
+        .cache(FixedCache(format!("0\n\"etag-1\"\n{}", config_json())))
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert!(result.value);
+    mock.assert_async().await;
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use open_feature::provider::{FeatureProvider, ResolutionDetails};
+use open_feature::{EvaluationContext, EvaluationErrorCode, Value};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn before_rewrites_context() {
+    let provider = common::builder("tests/data/test_json_targeting.json")
+        .before(|flag_key, ctx| {
+            if flag_key == "regionFeature" {
+                ctx.add_custom_field("region", "eu");
+            }
+        })
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value(
+            "regionFeature",
+            &EvaluationContext::default().with_targeting_key("id"),
+        )
+        .await
+        .unwrap();
+    assert!(result.value);
+    assert_eq!("v-region-t", result.variant.unwrap());
+}
+
+#[tokio::test]
+async fn after_post_processes_result() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .after(|flag_key, result| match flag_key {
+            "stringSetting" => {
+                if let Ok(ResolutionDetails {
+                    value: Value::String(val),
+                    ..
+                }) = result
+                {
+                    *val = val.to_uppercase();
+                }
+            }
+            "non-existing" => *result = Ok(ResolutionDetails::new(Value::Bool(true))),
+            "intSetting" => *result = Ok(ResolutionDetails::new(Value::Bool(true))),
+            _ => {}
+        })
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    let result = provider
+        .resolve_string_value("stringSetting", &ctx)
+        .await
+        .unwrap();
+    assert_eq!("TEST", result.value);
+
+    let result = provider
+        .resolve_bool_value("non-existing", &ctx)
+        .await
+        .unwrap();
+    assert!(result.value);
+
+    let err = provider
+        .resolve_int_value("intSetting", &ctx)
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::TypeMismatch, err.code);
+}
+
+#[tokio::test]
+async fn on_evaluated_receives_sdk_details() {
+    let evaluations = Arc::new(Mutex::new(Vec::new()));
+    let collected = evaluations.clone();
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .on_evaluated(move |flag_key, details| {
+            collected.lock().unwrap().push((
+                flag_key.to_owned(),
+                details.value.clone(),
+                details.user.as_ref().map(ToString::to_string),
+                details.matched_targeting_rule.is_some(),
+                details.error.is_some(),
+            ));
+        })
+        .build()
+        .unwrap();
+
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+    _ = provider.resolve_bool_value("disabledFeature", &ctx).await;
+    _ = provider.resolve_int_value("non-existing", &ctx).await;
+
+    let evaluations = evaluations.lock().unwrap();
+    assert_eq!(2, evaluations.len());
+    let (key, value, user, matched_rule, failed) = &evaluations[0];
+    assert_eq!("disabledFeature", key);
+    assert_eq!(&configcat::Value::Bool(true), value);
+    assert!(user.as_ref().unwrap().contains("example@matching.com"));
+    assert!(matched_rule);
+    assert!(!failed);
+    assert_eq!("non-existing", evaluations[1].0);
+    assert!(evaluations[1].4);
+}
@@ -0,0 +1,142 @@
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, ConfigChange};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string("tests/data/test_json_complex.json").unwrap())
+        .unwrap()
+}
+
+/// Serves the given config JSON versions one after the other.
+async fn serve(server: &mut mockito::Server, configs: &[serde_json::Value]) -> Vec<mockito::Mock> {
+    let mut mocks = Vec::new();
+    for (i, config) in configs.iter().enumerate() {
+        mocks.push(
+            server
+                .mock("GET", CONFIG_PATH)
+                .with_status(200)
+                .with_header("ETag", &format!("\"etag-{i}\""))
+                .with_body(config.to_string())
+                .expect(1)
+                .create_async()
+                .await,
+        );
+    }
+    mocks
+}
+
+#[tokio::test]
+async fn reports_changed_keys() {
+    let original = config_json();
+    let mut updated = original.clone();
+    let settings = updated["f"].as_object_mut().unwrap();
+    settings.remove("intSetting");
+    settings.insert(
+        "newFlag".to_owned(),
+        original["f"]["enabledFeature"].clone(),
+    );
+    settings["stringSetting"]["v"]["s"] = "changed".into();
+    let mut server = mockito::Server::new_async().await;
+    serve(&mut server, &[original, updated]).await;
+    let (changed, mut changes) = mpsc::unbounded_channel();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .on_flags_changed(move |change| {
+            _ = changed.send(change.clone());
+        })
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    provider.refresh().await.unwrap();
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        ConfigChange {
+            added: vec!["newFlag".to_owned()],
+            removed: vec!["intSetting".to_owned()],
+            changed: vec!["stringSetting".to_owned()],
+        },
+        change
+    );
+    assert_eq!(
+        vec!["newFlag", "intSetting", "stringSetting"],
+        change.keys().collect::<Vec<_>>()
+    );
+    assert!(changes.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn reports_all_flags_on_segment_change() {
+    let original = config_json();
+    let mut updated = original.clone();
+    updated["s"] = serde_json::json!([{"n": "Beta users", "r": []}]);
+    let mut server = mockito::Server::new_async().await;
+    serve(&mut server, &[original, updated]).await;
+    let (changed, mut changes) = mpsc::unbounded_channel();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .key_prefix("enabled")
+        .on_flags_changed(move |change| {
+            _ = changed.send(change.clone());
+        })
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    provider.refresh().await.unwrap();
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(vec!["Feature"], change.changed);
+    assert!(change.added.is_empty() && change.removed.is_empty());
+}
+
+#[tokio::test]
+async fn filters_by_prefix() {
+    let original = config_json();
+    let mut updated = original.clone();
+    let settings = updated["f"].as_object_mut().unwrap();
+    settings["intSetting"]["v"]["i"] = 6.into();
+    settings["stringSetting"]["v"]["s"] = "changed".into();
+    let mut updated_again = updated.clone();
+    updated_again["f"]["stringSetting"]["v"]["s"] = "changed again".into();
+    let mut server = mockito::Server::new_async().await;
+    serve(&mut server, &[original, updated, updated_again]).await;
+    let (changed, mut changes) = mpsc::unbounded_channel();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .on_flags_changed_with_prefix("int", move |change| {
+            _ = changed.send(change.clone());
+        })
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    provider.refresh().await.unwrap();
+    provider.refresh().await.unwrap();
+
+    let change = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(vec!["intSetting"], change.changed);
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), changes.recv())
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "codegen")]
+mod common;
+
+use configcat_openfeature_provider::{generate_flags, write_flags};
+use std::io::ErrorKind;
+
+#[test]
+fn generates_typed_accessors() {
+    let config_json = std::fs::read_to_string("tests/data/test_json_complex.json").unwrap();
+
+    let code = generate_flags(config_json.as_str()).unwrap();
+
+    assert_eq!(
+        "// @generated by configcat-openfeature-provider from a config JSON. Do not edit.
+
+::configcat_openfeature_provider::declare_flags! {
+    pub DisabledFeature: bool = \"disabledFeature\";
+    pub DoubleSetting: f64 = \"doubleSetting\";
+    pub EnabledFeature: bool = \"enabledFeature\";
+    pub IntSetting: i64 = \"intSetting\";
+    pub ObjectSetting: ::std::string::String = \"objectSetting\";
+    pub StringSetting: ::std::string::String = \"stringSetting\";
+}
+",
+        code
+    );
+}
+
+#[test]
+fn sanitizes_names() {
+    let code = generate_flags(
+        r#"{"f": {"new-checkout.enabled": {"t": 0}, "2fa": {"t": 1}, "self": {"t": 2}}}"#,
+    )
+    .unwrap();
+
+    assert!(code.contains("pub NewCheckoutEnabled: bool = \"new-checkout.enabled\";"));
+    assert!(code.contains("pub F2fa: ::std::string::String = \"2fa\";"));
+    assert!(code.contains("pub Self_: i64 = \"self\";"));
+}
+
+#[test]
+fn rejects_invalid_config() {
+    let colliding = generate_flags(r#"{"f": {"my-flag": {"t": 0}, "my_flag": {"t": 0}}}"#);
+    let unknown_type = generate_flags(r#"{"f": {"flag": {"t": 9}}}"#);
+    let missing_settings = generate_flags("{}");
+
+    assert_eq!(ErrorKind::InvalidData, colliding.unwrap_err().kind());
+    assert_eq!(ErrorKind::InvalidData, unknown_type.unwrap_err().kind());
+    assert_eq!(ErrorKind::InvalidData, missing_settings.unwrap_err().kind());
+}
+
+#[test]
+fn writes_file() {
+    let out_path = std::env::temp_dir().join(format!("configcat_flags_{}.rs", std::process::id()));
+
+    write_flags("tests/data/test_json_complex.json", &out_path).unwrap();
+    let code = std::fs::read_to_string(&out_path).unwrap();
+    std::fs::remove_file(&out_path).unwrap();
+
+    assert!(code.contains("pub IntSetting: i64 = \"intSetting\";"));
+}
+
+mod generated {
+    include!("data/test_flags.rs");
+}
+
+#[tokio::test]
+async fn generated_code_compiles() {
+    use open_feature::EvaluationContext;
+
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        5,
+        generated::IntSetting::get(&provider, &EvaluationContext::default(), 0).await
+    );
+}
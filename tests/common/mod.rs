@@ -0,0 +1,19 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use configcat::FileDataSource;
+use configcat::OverrideBehavior::LocalOnly;
+use configcat_openfeature_provider::{ConfigCatProvider, ConfigCatProviderBuilder};
+
+/// Returns a builder of a provider that evaluates the given config JSON file only.
+pub fn builder(path: &str) -> ConfigCatProviderBuilder {
+    ConfigCatProvider::builder("local")
+        .overrides(Box::new(FileDataSource::new(path).unwrap()), LocalOnly)
+}
+
+/// Returns a provider that evaluates the complex test config JSON.
+pub fn provider() -> ConfigCatProvider {
+    builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "config")]
+mod common;
+
+use common::provider;
+use config::builder::AsyncState;
+use config::ConfigBuilder;
+use configcat_openfeature_provider::ConfigCatSource;
+use open_feature::EvaluationContext;
+
+#[tokio::test]
+async fn all_flags() {
+    let config = ConfigBuilder::<AsyncState>::default()
+        .add_async_source(ConfigCatSource::new(provider()))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(config.get_bool("enabledFeature").unwrap());
+    assert_eq!(5, config.get_int("intSetting").unwrap());
+    assert_eq!(1.2, config.get_float("doubleSetting").unwrap());
+    assert_eq!("test", config.get_string("stringSetting").unwrap());
+}
+
+#[tokio::test]
+async fn selected_flags() {
+    let config = ConfigBuilder::<AsyncState>::default()
+        .set_default("app.name", "sample")
+        .unwrap()
+        .add_async_source(
+            ConfigCatSource::new(provider())
+                .context(EvaluationContext::default().with_targeting_key("example@matching.com"))
+                .flag("disabledFeature")
+                .flag_as("intSetting", "app.limit")
+                .flag("non-existing"),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    assert!(config.get_bool("disabledFeature").unwrap());
+    assert_eq!(5, config.get_int("app.limit").unwrap());
+    assert_eq!("sample", config.get_string("app.name").unwrap());
+    assert!(config.get_string("stringSetting").is_err());
+    assert!(config.get_bool("non-existing").is_err());
+}
@@ -0,0 +1,10 @@
+// @generated by configcat-openfeature-provider from a config JSON. Do not edit.
+
+::configcat_openfeature_provider::declare_flags! {
+    pub DisabledFeature: bool = "disabledFeature";
+    pub DoubleSetting: f64 = "doubleSetting";
+    pub EnabledFeature: bool = "enabledFeature";
+    pub IntSetting: i64 = "intSetting";
+    pub ObjectSetting: ::std::string::String = "objectSetting";
+    pub StringSetting: ::std::string::String = "stringSetting";
+}
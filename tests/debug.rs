@@ -0,0 +1,57 @@
+mod common;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::sync::Mutex;
+
+static LOGGER: CollectingLogger = CollectingLogger(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn debug_trace_for_enabled_flags_only() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Info);
+
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    provider.enable_debug_for(&["disabledFeature"]);
+
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+    _ = provider.resolve_bool_value("disabledFeature", &ctx).await;
+    _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    provider.disable_debug_for(&["disabledFeature"]);
+    _ = provider.resolve_bool_value("disabledFeature", &ctx).await;
+
+    let traces: Vec<String> = LOGGER
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|msg| msg.starts_with("Evaluation trace"))
+        .cloned()
+        .collect();
+    assert_eq!(1, traces.len());
+    assert!(traces[0].starts_with("Evaluation trace of 'disabledFeature' for User"));
+    assert!(traces[0].contains(
+        "Matched targeting rule: IF User.Identifier CONTAINS ANY OF ['@matching.com'] THEN 'true'"
+    ));
+    assert!(traces[0].contains("Result: 'true' (variation 'v-disabled-t')"));
+}
+
+struct CollectingLogger(Mutex<Vec<String>>);
+
+impl Log for CollectingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Keep the SDK's own evaluation logs turned off, like an application would.
+        !metadata.target().starts_with("configcat::")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) && record.level() == Level::Info {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
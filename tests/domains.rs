@@ -0,0 +1,39 @@
+mod common;
+
+use common::builder;
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::OpenFeature;
+
+#[tokio::test]
+async fn register_domains() {
+    let providers = ConfigCatProvider::register_domains([
+        ("complex", builder("tests/data/test_json_complex.json")),
+        (
+            "payments",
+            builder("tests/data/test_settings.json").key_prefix("payments."),
+        ),
+    ])
+    .await
+    .unwrap();
+
+    assert_eq!(2, providers.len());
+    assert_eq!(
+        vec!["provider", "retry.maxAttempts", "timeoutSecs"],
+        providers["payments"].flag_keys().await
+    );
+    let api = OpenFeature::singleton().await;
+    assert_eq!(
+        5,
+        api.create_named_client("complex")
+            .get_int_value("intSetting", None, None)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        "stripe",
+        api.create_named_client("payments")
+            .get_string_value("provider", None, None)
+            .await
+            .unwrap()
+    );
+}
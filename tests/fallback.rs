@@ -0,0 +1,82 @@
+mod common;
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, FallbackProvider};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+use std::sync::{Arc, Mutex};
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+
+fn local() -> ConfigCatProvider {
+    common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn falls_back_without_config() {
+    let primary = ConfigCatProvider::builder(SDK_KEY)
+        .polling_mode(PollingMode::Manual)
+        .offline(true)
+        .build()
+        .unwrap();
+    let fallbacks = Arc::new(Mutex::new(Vec::new()));
+    let recorded = fallbacks.clone();
+    let provider = FallbackProvider::new(primary, local()).on_fallback(move |flag_key, _| {
+        recorded.lock().unwrap().push(flag_key.to_owned());
+    });
+    let ctx = EvaluationContext::default();
+
+    assert!(
+        provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        5,
+        provider
+            .resolve_int_value("intSetting", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        vec!["enabledFeature", "intSetting"],
+        *fallbacks.lock().unwrap()
+    );
+    assert_eq!(
+        "FallbackProvider(ConfigCatProvider, ConfigCatProvider)",
+        provider.metadata().name
+    );
+}
+
+#[tokio::test]
+async fn keeps_primary_errors() {
+    let fallbacks = Arc::new(Mutex::new(0));
+    let recorded = fallbacks.clone();
+    let provider = FallbackProvider::new(local(), local()).on_fallback(move |_, _| {
+        *recorded.lock().unwrap() += 1;
+    });
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        provider
+            .resolve_bool_value("non-existing", &ctx)
+            .await
+            .unwrap_err()
+            .code
+    );
+    assert_eq!(
+        EvaluationErrorCode::TypeMismatch,
+        provider
+            .resolve_bool_value("stringSetting", &ctx)
+            .await
+            .unwrap_err()
+            .code
+    );
+    assert_eq!(0, *fallbacks.lock().unwrap());
+}
@@ -0,0 +1,223 @@
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, STALE_REASON};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationReason};
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> String {
+    std::fs::read_to_string("tests/data/test_json_complex.json").unwrap()
+}
+
+#[tokio::test]
+async fn manual_refresh() {
+    let mut server = mockito::Server::new_async().await;
+    let ok = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+
+    assert_eq!(0, provider.fetch_metrics().attempts);
+
+    provider.refresh().await.unwrap();
+    ok.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(500)
+        .create_async()
+        .await;
+    assert!(provider.refresh().await.is_err());
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(2, metrics.attempts);
+    assert_eq!(1, metrics.successes);
+    assert_eq!(1, metrics.failures);
+    assert!(metrics.last_success.is_some());
+    assert!(metrics.last_failure.unwrap().contains("500"));
+    assert!(metrics.seconds_since_last_success.unwrap() < 5.0);
+}
+
+#[tokio::test]
+async fn auto_poll() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+
+    // The evaluation waits for the first poll.
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert!(result.value);
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(1, metrics.attempts);
+    assert_eq!(1, metrics.successes);
+    assert_eq!(0, metrics.failures);
+}
+
+#[tokio::test]
+async fn auto_poll_init_wait_elapses() {
+    // Accepts the connections, but never responds.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(format!("http://{}", listener.local_addr().unwrap()).as_str())
+        .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+        .max_init_wait_time(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    // The evaluation is served with the default value once the init wait elapses.
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        provider.resolve_bool_value("enabledFeature", &EvaluationContext::default()),
+    )
+    .await
+    .unwrap();
+    assert!(result.is_err());
+    assert_eq!(0, provider.fetch_metrics().successes);
+}
+
+#[tokio::test]
+async fn lazy_load_failure() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(502)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::LazyLoad(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await;
+    assert!(result.is_err());
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(1, metrics.attempts);
+    assert_eq!(0, metrics.successes);
+    assert_eq!(1, metrics.failures);
+    assert!(metrics.last_success.is_none());
+    assert!(metrics.seconds_since_last_success.is_none());
+}
+
+#[tokio::test]
+async fn stale_while_revalidate() {
+    let mut server = mockito::Server::new_async().await;
+    let v1 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .stale_while_revalidate(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    // The first evaluation waits for the initial fetch.
+    let result = provider
+        .resolve_bool_value("enabledFeature", &ctx)
+        .await
+        .unwrap();
+    assert!(result.value);
+    assert_ne!(
+        Some(EvaluationReason::Other(STALE_REASON.to_owned())),
+        result.reason
+    );
+
+    v1.remove_async().await;
+    let mut config: serde_json::Value = serde_json::from_str(config_json().as_str()).unwrap();
+    config["f"]["enabledFeature"]["v"]["b"] = serde_json::Value::Bool(false);
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config.to_string())
+        .create_async()
+        .await;
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    // The expired config JSON is served immediately, and refreshed in the background.
+    let result = provider
+        .resolve_bool_value("enabledFeature", &ctx)
+        .await
+        .unwrap();
+    assert!(result.value);
+    assert_eq!(
+        Some(EvaluationReason::Other(STALE_REASON.to_owned())),
+        result.reason
+    );
+
+    for _ in 0..50 {
+        if provider.fetch_metrics().successes == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let result = provider
+        .resolve_bool_value("enabledFeature", &ctx)
+        .await
+        .unwrap();
+    assert!(!result.value);
+    assert_ne!(
+        Some(EvaluationReason::Other(STALE_REASON.to_owned())),
+        result.reason
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_refresh() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .expect(2)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .refresh_rate_limit(Duration::from_millis(300))
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    let start = std::time::Instant::now();
+    let results = tokio::join!(
+        provider.refresh(),
+        provider.refresh(),
+        provider.refresh(),
+        provider.refresh(),
+    );
+
+    assert!(start.elapsed() >= Duration::from_millis(250));
+    assert!(results.0.is_ok() && results.1.is_ok() && results.2.is_ok() && results.3.is_ok());
+    assert_eq!(2, provider.fetch_metrics().attempts);
+    mock.assert_async().await;
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "derive")]
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, FlagEnum};
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+#[derive(FlagEnum, Debug, PartialEq)]
+#[flag(rename_all = "kebab-case")]
+enum Theme {
+    Light,
+    Dark,
+    HighContrast,
+}
+
+#[derive(FlagEnum, Debug, PartialEq)]
+enum Renamed {
+    #[flag(rename = "dark")]
+    Night,
+    Day,
+}
+
+async fn provider(server: &mut mockito::Server) -> ConfigCatProvider {
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(std::fs::read_to_string("tests/data/test_json_enum.json").unwrap())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+    provider
+}
+
+#[test]
+fn derived_mapping() {
+    assert_eq!(&["light", "dark", "high-contrast"], Theme::VARIANTS);
+    assert_eq!(
+        Some(Theme::HighContrast),
+        Theme::from_flag_value("high-contrast")
+    );
+    assert_eq!(None, Theme::from_flag_value("HighContrast"));
+    assert_eq!("dark", Theme::Dark.flag_value());
+    assert_eq!(&["dark", "Day"], Renamed::VARIANTS);
+    assert_eq!(Some(Renamed::Night), Renamed::from_flag_value("dark"));
+}
+
+#[tokio::test]
+async fn resolve_enum() {
+    let mut server = mockito::Server::new_async().await;
+    let provider = provider(&mut server).await;
+    let admin = EvaluationContext::default().with_targeting_key("admin");
+
+    let res = provider
+        .resolve_enum::<Theme>("theme", &admin)
+        .await
+        .unwrap();
+    assert_eq!(Theme::HighContrast, res.value);
+    assert_eq!("v-theme-high-contrast", res.variant.unwrap());
+
+    let err = provider
+        .resolve_enum::<Theme>("legacyTheme", &admin)
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::TypeMismatch, err.code);
+    assert_eq!(
+        "The value 'purple' of 'legacyTheme' doesn't match any of the allowed values: light, dark, high-contrast.",
+        err.message.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn verify_enum() {
+    let mut server = mockito::Server::new_async().await;
+    let provider = provider(&mut server).await;
+
+    provider.verify_enum::<Theme>("theme").await.unwrap();
+
+    let err = provider.verify_enum::<Renamed>("theme").await.unwrap_err();
+    assert_eq!(EvaluationErrorCode::TypeMismatch, err.code);
+    assert_eq!(
+        "The values 'light', 'high-contrast' of 'theme' don't match any of the allowed values: dark, Day.",
+        err.message.unwrap()
+    );
+    assert_eq!(
+        EvaluationErrorCode::TypeMismatch,
+        provider
+            .verify_enum::<Theme>("legacyTheme")
+            .await
+            .unwrap_err()
+            .code
+    );
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        provider
+            .verify_enum::<Theme>("non-existing")
+            .await
+            .unwrap_err()
+            .code
+    );
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::provider;
+use configcat_openfeature_provider::FlagKey;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+
+const ENABLED_FEATURE: FlagKey<bool> = FlagKey::new("enabledFeature");
+const INT_SETTING: FlagKey<i64> = FlagKey::new("intSetting");
+const DOUBLE_SETTING: FlagKey<f64> = FlagKey::new("doubleSetting");
+const STRING_SETTING: FlagKey<String> = FlagKey::new("stringSetting");
+const MISSING: FlagKey<bool> = FlagKey::new("non-existing");
+
+#[tokio::test]
+async fn get() {
+    let provider = provider();
+    let ctx = EvaluationContext::default();
+
+    assert!(provider.get(ENABLED_FEATURE, &ctx, false).await);
+    assert_eq!(5, provider.get(INT_SETTING, &ctx, 0).await);
+    assert!((provider.get(DOUBLE_SETTING, &ctx, 0.0).await - 1.2).abs() < f64::EPSILON);
+    assert_eq!(
+        "test",
+        provider.get(STRING_SETTING, &ctx, String::new()).await
+    );
+    assert!(provider.get(MISSING, &ctx, true).await);
+}
+
+#[tokio::test]
+async fn resolve() {
+    let provider = provider();
+    let ctx = EvaluationContext::default();
+
+    let res = provider.resolve(ENABLED_FEATURE, &ctx).await.unwrap();
+    assert!(res.value);
+    assert_eq!("v-enabled", res.variant.unwrap());
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        provider.resolve(MISSING, &ctx).await.unwrap_err().code
+    );
+    assert_eq!("FlagKey(\"intSetting\")", format!("{INT_SETTING:?}"));
+}
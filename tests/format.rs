@@ -0,0 +1,136 @@
+mod common;
+
+use configcat_openfeature_provider::{ConfigCatProvider, ConfigCatProviderBuilder, StructFormat};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode, StructValue};
+
+fn create_builder() -> ConfigCatProviderBuilder {
+    common::builder("tests/data/test_json_formats.json")
+}
+
+fn retry_policy() -> StructValue {
+    StructValue::default()
+        .with_field("retries", 3)
+        .with_field("backoff", "exponential")
+}
+
+async fn resolve(provider: &ConfigCatProvider, flag_key: &str) -> Option<StructValue> {
+    provider
+        .resolve_struct_value(flag_key, &EvaluationContext::default())
+        .await
+        .ok()
+        .map(|details| details.value)
+}
+
+#[tokio::test]
+async fn parses_json_by_default() {
+    let provider = create_builder().build().unwrap();
+
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "jsonSetting").await
+    );
+    let err = provider
+        .resolve_struct_value("yamlSetting", &EvaluationContext::default())
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+    assert!(err
+        .message
+        .unwrap()
+        .starts_with("Failed to parse JSON from evaluated string"));
+}
+
+#[cfg(feature = "yaml")]
+#[tokio::test]
+async fn parses_yaml_for_configured_key() {
+    let provider = create_builder()
+        .struct_format_for("yamlSetting", StructFormat::Yaml)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "yamlSetting").await
+    );
+    assert_eq!(None, resolve(&provider, "tomlSetting").await);
+}
+
+#[cfg(feature = "toml")]
+#[tokio::test]
+async fn parses_toml_as_default_format() {
+    let provider = create_builder()
+        .struct_format(StructFormat::Toml)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "tomlSetting").await
+    );
+}
+
+#[cfg(feature = "json5")]
+#[tokio::test]
+async fn parses_json5_for_configured_key() {
+    let provider = create_builder()
+        .struct_format_for("json5Setting", StructFormat::Json5)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "json5Setting").await
+    );
+    assert_eq!(None, resolve(&provider, "yamlSetting").await);
+}
+
+#[cfg(feature = "base64")]
+#[tokio::test]
+async fn decodes_base64_for_configured_key() {
+    let provider = create_builder()
+        .struct_format_for("base64Setting", StructFormat::Base64)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "base64Setting").await
+    );
+    assert_eq!(None, resolve(&provider, "prefixedBase64Setting").await);
+}
+
+#[tokio::test]
+async fn detects_format() {
+    let provider = create_builder()
+        .struct_format(StructFormat::Auto)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "jsonSetting").await
+    );
+    #[cfg(feature = "json5")]
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "json5Setting").await
+    );
+    #[cfg(feature = "yaml")]
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "yamlSetting").await
+    );
+    #[cfg(feature = "base64")]
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "prefixedBase64Setting").await
+    );
+    #[cfg(feature = "toml")]
+    assert_eq!(
+        Some(retry_policy()),
+        resolve(&provider, "tomlSetting").await
+    );
+    #[cfg(not(any(feature = "yaml", feature = "toml")))]
+    assert_eq!(None, resolve(&provider, "yamlSetting").await);
+}
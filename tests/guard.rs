@@ -0,0 +1,62 @@
+#![cfg(feature = "async-graphql")]
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Request, Schema};
+use configcat_openfeature_provider::FlagGuard;
+use open_feature::EvaluationContext;
+
+struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(guard = "FlagGuard::new(\"enabledFeature\")")]
+    async fn enabled(&self) -> i32 {
+        1
+    }
+
+    #[graphql(guard = "FlagGuard::new(\"disabledFeature\")")]
+    async fn disabled(&self) -> i32 {
+        2
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(provider)
+        .finish()
+}
+
+#[tokio::test]
+async fn flag_guard() {
+    let schema = schema();
+
+    let response = schema.execute("{ enabled }").await;
+    assert!(response.errors.is_empty());
+    assert_eq!(
+        serde_json::json!({ "enabled": 1 }),
+        response.data.into_json().unwrap()
+    );
+
+    let response = schema.execute("{ disabled }").await;
+    assert_eq!(
+        "Feature 'disabledFeature' is not enabled",
+        response.errors[0].message
+    );
+
+    let request = Request::new("{ disabled }")
+        .data(EvaluationContext::default().with_targeting_key("example@matching.com"));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn missing_provider() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let response = schema.execute("{ enabled }").await;
+
+    assert_eq!(1, response.errors.len());
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use configcat_openfeature_provider::{
+    ConfigCatLoggingHook, ConfigCatProvider, ContextEnrichmentHook, ContextValidationHook,
+    MATCHED_TARGETING_RULE_METADATA_KEY, VARIATION_ID_METADATA_KEY,
+};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use open_feature::{EvaluationContext, EvaluationErrorCode, FlagMetadataValue, OpenFeature};
+use std::sync::Mutex;
+
+static LOGGER: CollectingLogger = CollectingLogger(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn logging_hook() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let mut api = OpenFeature::default();
+    api.set_provider(create_provider()).await;
+    let client = api
+        .create_client()
+        .with_hook(ConfigCatLoggingHook::new().level(Level::Info));
+
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+    let details = client
+        .get_bool_details("disabledFeature", Some(&ctx), None)
+        .await
+        .unwrap();
+    _ = client
+        .get_bool_value("non-existing", Some(&ctx), None)
+        .await;
+
+    assert_eq!(
+        Some(&FlagMetadataValue::String("v-disabled-t".to_owned())),
+        details.flag_metadata.values.get(VARIATION_ID_METADATA_KEY)
+    );
+    assert_eq!(
+        Some(&FlagMetadataValue::String(
+            "IF User.Identifier CONTAINS ANY OF ['@matching.com'] THEN 'true'".to_owned()
+        )),
+        details
+            .flag_metadata
+            .values
+            .get(MATCHED_TARGETING_RULE_METADATA_KEY)
+    );
+
+    let logs = LOGGER.0.lock().unwrap();
+    assert_eq!(
+        vec![
+            (
+                Level::Info,
+                "Evaluating 'disabledFeature' with provider 'ConfigCatProvider', default value: false".to_owned()
+            ),
+            (
+                Level::Info,
+                "Evaluated 'disabledFeature': value: true, reason: TARGETING_MATCH, variation ID: 'v-disabled-t', \
+                matched targeting rule: IF User.Identifier CONTAINS ANY OF ['@matching.com'] THEN 'true'".to_owned()
+            ),
+            (
+                Level::Info,
+                "Evaluating 'non-existing' with provider 'ConfigCatProvider', default value: false".to_owned()
+            ),
+        ],
+        logs[..3]
+    );
+    assert_eq!(Level::Warn, logs[3].0);
+    assert!(logs[3]
+        .1
+        .starts_with("Failed to evaluate 'non-existing': [FLAG_NOT_FOUND]"));
+}
+
+#[tokio::test]
+async fn context_validation_hook() {
+    let mut api = OpenFeature::default();
+    api.set_provider(create_provider()).await;
+    let client = api.create_client().with_hook(
+        ContextValidationHook::new()
+            .require_targeting_key()
+            .allow_attributes(&["Email"]),
+    );
+
+    let err = client
+        .get_bool_details("enabledFeature", Some(&EvaluationContext::default()), None)
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::InvalidContext, err.code);
+    assert_eq!(
+        "The evaluation context of 'enabledFeature' is invalid: the targeting key is missing.",
+        err.message.unwrap()
+    );
+
+    let ctx = EvaluationContext::default()
+        .with_targeting_key("id")
+        .with_custom_field("Email", "a@example.com")
+        .with_custom_field("plan", "pro");
+    let err = client
+        .get_bool_details("enabledFeature", Some(&ctx), None)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        "The evaluation context of 'enabledFeature' is invalid: the 'plan' attribute is not allowed.",
+        err.message.unwrap()
+    );
+
+    let ctx = EvaluationContext::default()
+        .with_targeting_key("id")
+        .with_custom_field("Email", "a@example.com");
+    assert!(client
+        .get_bool_value("enabledFeature", Some(&ctx), None)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn context_enrichment_hook() {
+    let mut api = OpenFeature::default();
+    api.set_provider(
+        common::builder("tests/data/test_json_targeting.json")
+            .build()
+            .unwrap(),
+    )
+    .await;
+    let client = api.create_client().with_hook(
+        ContextEnrichmentHook::new()
+            .attribute("environment", "test")
+            .computed("region", || Some("eu".into())),
+    );
+
+    let ctx = EvaluationContext::default().with_targeting_key("id");
+    assert!(client
+        .get_bool_value("regionFeature", Some(&ctx), None)
+        .await
+        .unwrap());
+
+    // Attributes of the evaluation context aren't overwritten.
+    let ctx = ctx.with_custom_field("region", "us");
+    assert!(!client
+        .get_bool_value("regionFeature", Some(&ctx), None)
+        .await
+        .unwrap());
+}
+
+fn create_provider() -> ConfigCatProvider {
+    common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap()
+}
+
+struct CollectingLogger(Mutex<Vec<(Level, String)>>);
+
+impl Log for CollectingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata
+            .target()
+            .starts_with("configcat_openfeature_provider::hooks")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
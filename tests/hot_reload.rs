@@ -0,0 +1,122 @@
+#![cfg(feature = "hot-reload")]
+
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "configcat_hot_reload_{name}_{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("flags.json");
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn reloads_on_change() {
+    let path = temp_file(
+        "change",
+        r#"{"flags": {"enabledFeature": false, "maxItems": 5}}"#,
+    );
+    let (changed, mut changes) = mpsc::unbounded_channel();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .watch_file_overrides(&path)
+        .on_configuration_changed(move || {
+            _ = changed.send(());
+        })
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+    let mut watched = provider
+        .watch_bool("enabledFeature", ctx.clone(), false)
+        .await;
+
+    assert!(!*watched.borrow());
+    assert_eq!(
+        5,
+        provider
+            .resolve_int_value("maxItems", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+
+    std::fs::write(
+        &path,
+        r#"{"flags": {"enabledFeature": true, "maxItems": 10}}"#,
+    )
+    .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), changes.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), watched.changed())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(*watched.borrow());
+    assert!(
+        provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        10,
+        provider
+            .resolve_int_value("maxItems", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn keeps_previous_flags_on_invalid_file() {
+    let path = temp_file(
+        "invalid",
+        r#"{"f": {"theme": {"t": 1, "v": {"s": "dark"}, "i": "v-dark"}}}"#,
+    );
+    let (changed, mut changes) = mpsc::unbounded_channel();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .watch_file_overrides(&path)
+        .on_configuration_changed(move || {
+            _ = changed.send(());
+        })
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    std::fs::write(&path, "{ not json").unwrap();
+    std::fs::write(&path, r#"{"flags": {"theme": ["dark"]}}"#).unwrap();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), changes.recv())
+            .await
+            .is_err()
+    );
+
+    let details = provider.resolve_string_value("theme", &ctx).await.unwrap();
+    assert_eq!("dark", details.value);
+    assert_eq!(Some("v-dark".to_owned()), details.variant);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn fails_on_missing_file() {
+    let result = ConfigCatProvider::builder(SDK_KEY)
+        .watch_file_overrides("tests/data/missing.json")
+        .build();
+
+    assert!(result.is_err());
+}
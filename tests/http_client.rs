@@ -0,0 +1,245 @@
+use configcat::{ErrorKind, PollingMode};
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use reqwest::header::{HeaderMap, HeaderValue};
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string("tests/data/test_json_complex.json").unwrap())
+        .unwrap()
+}
+
+/// An HTTP client that identifies itself with a header, standing in for a client certificate.
+fn http_client() -> reqwest::Client {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Client-Identity", HeaderValue::from_static("checkout"));
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap()
+}
+
+async fn int_setting(provider: &ConfigCatProvider) -> i64 {
+    provider
+        .resolve_int_value("intSetting", &EvaluationContext::default())
+        .await
+        .unwrap()
+        .value
+}
+
+#[tokio::test]
+async fn downloads_with_custom_client() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", CONFIG_PATH)
+        .match_header("X-Client-Identity", "checkout")
+        .with_status(200)
+        .with_header("ETag", "\"etag-1\"")
+        .with_body(config_json().to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::LazyLoad(std::time::Duration::from_secs(60)))
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    assert_eq!(5, int_setting(&provider).await);
+    assert_eq!(5, int_setting(&provider).await);
+
+    mock.assert_async().await;
+    assert_eq!(1, provider.fetch_metrics().successes);
+}
+
+#[tokio::test]
+async fn picks_up_changes_and_revalidates() {
+    let original = config_json();
+    let mut updated = original.clone();
+    updated["f"]["intSetting"]["v"]["i"] = 42.into();
+    let mut server = mockito::Server::new_async().await;
+    // Without an ETag header, the content hash identifies the config JSON version.
+    let first = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(original.to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    assert_eq!(5, int_setting(&provider).await);
+    first.assert_async().await;
+
+    let second = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-2\"")
+        .with_body(updated.to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+    assert_eq!(42, int_setting(&provider).await);
+    second.assert_async().await;
+
+    let not_modified = server
+        .mock("GET", CONFIG_PATH)
+        .match_header("If-None-Match", "\"etag-2\"")
+        .with_status(304)
+        .expect(1)
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+    assert_eq!(42, int_setting(&provider).await);
+    not_modified.assert_async().await;
+}
+
+#[tokio::test]
+async fn reports_rejected_key() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(403)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    let err = provider.refresh().await.unwrap_err();
+
+    assert_eq!(ErrorKind::InvalidSdkKey, err.kind);
+    assert_eq!(1, provider.fetch_metrics().failures);
+}
+
+#[tokio::test]
+async fn offline_client_does_not_download() {
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .polling_mode(PollingMode::Manual)
+        .offline(true)
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    let err = provider.refresh().await.unwrap_err();
+
+    assert_eq!(ErrorKind::OfflineClient, err.kind);
+}
+
+/// Returns the test config JSON with preferences redirecting to the given URL.
+fn redirecting_config_json(url: &str, redirect: u8) -> String {
+    let mut config = config_json();
+    config["p"] = serde_json::json!({ "u": url, "r": redirect, "s": "test-salt" });
+    config.to_string()
+}
+
+#[tokio::test]
+async fn follows_forced_redirect() {
+    let mut custom = mockito::Server::new_async().await;
+    let mut eu = mockito::Server::new_async().await;
+    let custom_mock = custom
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(redirecting_config_json(eu.url().as_str(), 2))
+        .expect(1)
+        .create_async()
+        .await;
+    let mut redirected = config_json();
+    redirected["f"]["intSetting"]["v"]["i"] = 42.into();
+    redirected["p"] = serde_json::json!({ "u": eu.url(), "r": 0, "s": "test-salt" });
+    let eu_mock = eu
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(redirected.to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(custom.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    assert_eq!(42, int_setting(&provider).await);
+    // The next downloads go to the redirected URL.
+    provider.refresh().await.unwrap();
+
+    custom_mock.assert_async().await;
+    eu_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn keeps_custom_url_when_redirect_is_not_forced() {
+    let mut custom = mockito::Server::new_async().await;
+    let mut eu = mockito::Server::new_async().await;
+    let custom_mock = custom
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(redirecting_config_json(eu.url().as_str(), 1))
+        .expect(2)
+        .create_async()
+        .await;
+    let eu_mock = eu.mock("GET", CONFIG_PATH).expect(0).create_async().await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(custom.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    provider.refresh().await.unwrap();
+    assert_eq!(5, int_setting(&provider).await);
+
+    custom_mock.assert_async().await;
+    eu_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn reports_redirect_loop() {
+    let mut first = mockito::Server::new_async().await;
+    let mut second = mockito::Server::new_async().await;
+    let first_mock = first
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(redirecting_config_json(second.url().as_str(), 2))
+        .expect(2)
+        .create_async()
+        .await;
+    let second_mock = second
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(redirecting_config_json(first.url().as_str(), 2))
+        .expect(1)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(first.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .http_client(http_client())
+        .build()
+        .unwrap();
+
+    let err = provider.refresh().await.unwrap_err();
+
+    assert_eq!(ErrorKind::RedirectLoop, err.kind);
+    first_mock.assert_async().await;
+    second_mock.assert_async().await;
+}
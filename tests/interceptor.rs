@@ -0,0 +1,65 @@
+#![cfg(feature = "tonic")]
+
+use configcat_openfeature_provider::MetadataContextInterceptor;
+use open_feature::{EvaluationContext, EvaluationContextFieldValue};
+use tonic::service::Interceptor;
+use tonic::Request;
+
+fn interceptor() -> MetadataContextInterceptor {
+    MetadataContextInterceptor::new()
+        .targeting_key_metadata("x-user-id")
+        .attribute_metadata("X-Tenant-Id", "Tenant")
+}
+
+fn context(request: Request<()>) -> EvaluationContext {
+    interceptor()
+        .call(request)
+        .unwrap()
+        .extensions()
+        .get::<EvaluationContext>()
+        .cloned()
+        .unwrap()
+}
+
+#[test]
+fn metadata_to_context() {
+    let mut request = Request::new(());
+    request
+        .metadata_mut()
+        .insert("x-user-id", "user-1".parse().unwrap());
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+
+    let ctx = context(request);
+
+    assert_eq!(Some("user-1"), ctx.targeting_key.as_deref());
+    assert_eq!(
+        Some(&EvaluationContextFieldValue::String("acme".to_owned())),
+        ctx.custom_fields.get("Tenant")
+    );
+}
+
+#[test]
+fn missing_metadata() {
+    let ctx = context(Request::new(()));
+
+    assert_eq!(None, ctx.targeting_key);
+    assert!(ctx.custom_fields.is_empty());
+}
+
+#[test]
+fn extends_existing_context() {
+    let mut request = Request::new(());
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+    request
+        .extensions_mut()
+        .insert(EvaluationContext::default().with_targeting_key("user-2"));
+
+    let ctx = context(request);
+
+    assert_eq!(Some("user-2"), ctx.targeting_key.as_deref());
+    assert_eq!(1, ctx.custom_fields.len());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "tower")]
+mod common;
+
+use configcat_openfeature_provider::{EvaluationContextLayer, FlagGateLayer};
+use http::{Request, Response, StatusCode};
+use open_feature::{EvaluationContext, EvaluationContextFieldValue};
+use std::convert::Infallible;
+use tower::{service_fn, Layer, ServiceExt};
+
+fn layer() -> EvaluationContextLayer {
+    EvaluationContextLayer::new()
+        .targeting_key_header("x-user-id")
+        .attribute_header("X-Tenant-Id", "Tenant")
+        .attribute_header("cf-ipcountry", "Country")
+}
+
+async fn context(layer: EvaluationContextLayer, request: Request<()>) -> EvaluationContext {
+    let service = layer.layer(service_fn(|request: Request<()>| async move {
+        Ok::<_, Infallible>(request.extensions().get::<EvaluationContext>().cloned())
+    }));
+    service.oneshot(request).await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn headers_to_context() {
+    let request = Request::get("/")
+        .header("X-User-Id", "user-1")
+        .header("x-tenant-id", "acme")
+        .header("CF-IPCountry", "HU")
+        .body(())
+        .unwrap();
+
+    let ctx = context(layer(), request).await;
+
+    assert_eq!(Some("user-1"), ctx.targeting_key.as_deref());
+    assert_eq!(
+        Some(&EvaluationContextFieldValue::String("acme".to_owned())),
+        ctx.custom_fields.get("Tenant")
+    );
+    assert_eq!(
+        Some(&EvaluationContextFieldValue::String("HU".to_owned())),
+        ctx.custom_fields.get("Country")
+    );
+}
+
+#[tokio::test]
+async fn missing_headers() {
+    let request = Request::get("/")
+        .header("x-tenant-id", "acme")
+        .body(())
+        .unwrap();
+
+    let ctx = context(layer(), request).await;
+
+    assert_eq!(None, ctx.targeting_key);
+    assert_eq!(1, ctx.custom_fields.len());
+}
+
+#[tokio::test]
+async fn extends_existing_context() {
+    let mut request = Request::get("/")
+        .header("cf-ipcountry", "HU")
+        .body(())
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(EvaluationContext::default().with_targeting_key("user-2"));
+
+    let ctx = context(layer(), request).await;
+
+    assert_eq!(Some("user-2"), ctx.targeting_key.as_deref());
+    assert_eq!(
+        Some(&EvaluationContextFieldValue::String("HU".to_owned())),
+        ctx.custom_fields.get("Country")
+    );
+}
+
+fn gated(
+    flag_key: &str,
+) -> impl tower::Service<Request<()>, Response = Response<String>, Error = Infallible> {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    tower::ServiceBuilder::new()
+        .layer(layer())
+        .layer(FlagGateLayer::new(
+            provider,
+            flag_key,
+            StatusCode::NOT_FOUND,
+        ))
+        .service(service_fn(|_: Request<()>| async {
+            Ok::<_, Infallible>(Response::new("ok".to_owned()))
+        }))
+}
+
+#[tokio::test]
+async fn flag_gate() {
+    let response = gated("enabledFeature")
+        .oneshot(Request::get("/").body(()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("ok", response.body());
+
+    let response = gated("disabledFeature")
+        .oneshot(Request::get("/").body(()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!("", response.body());
+
+    let request = Request::get("/")
+        .header("x-user-id", "example@matching.com")
+        .body(())
+        .unwrap();
+    let response = gated("disabledFeature").oneshot(request).await.unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+
+    let response = gated("stringSetting")
+        .oneshot(Request::get("/").body(()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
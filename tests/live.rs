@@ -0,0 +1,180 @@
+//! End-to-end tests against a real ConfigCat config, skipped unless the `CONFIGCAT_LIVE_TESTS`
+//! environment variable is set:
+//!
+//! ```bash
+//! CONFIGCAT_LIVE_TESTS=1 cargo test --features testing --test live
+//! ```
+//!
+//! They use the SDK key of the public sample config (the one in `examples/print_eval.rs`), or
+//! the one in the `CONFIGCAT_LIVE_SDK_KEY` environment variable. The evaluations of every flag
+//! are compared with the snapshot in `tests/data/live`, which is recorded on the first run, and
+//! can be updated with `UPDATE_SNAPSHOTS=1`.
+#![cfg(feature = "testing")]
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::testing::EvaluationMatrix;
+use configcat_openfeature_provider::{ConfigCatProvider, FlagType};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+use std::time::Duration;
+
+const SAMPLE_SDK_KEY: &str = "PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ";
+
+const UNKNOWN_SDK_KEY: &str = "configcat-sdk-1/AAAAAAAAAAAAAAAAAAAAAA/AAAAAAAAAAAAAAAAAAAAAA";
+
+/// Returns the SDK key of the live tests, or `None` when they are skipped.
+fn sdk_key() -> Option<String> {
+    if std::env::var_os("CONFIGCAT_LIVE_TESTS").is_none() {
+        eprintln!("Skipped, set CONFIGCAT_LIVE_TESTS=1 to run the live tests.");
+        return None;
+    }
+    Some(std::env::var("CONFIGCAT_LIVE_SDK_KEY").unwrap_or_else(|_| SAMPLE_SDK_KEY.to_owned()))
+}
+
+fn manual(sdk_key: &str) -> ConfigCatProvider {
+    ConfigCatProvider::builder(sdk_key)
+        .polling_mode(PollingMode::Manual)
+        .http_timeout(Duration::from_secs(10))
+        .build()
+        .unwrap()
+}
+
+/// Declares every setting of the downloaded config JSON, evaluated for a set of typical
+/// evaluation contexts.
+async fn matrix(provider: &ConfigCatProvider) -> EvaluationMatrix {
+    let config = provider.export_config().await.unwrap();
+    let mut matrix = EvaluationMatrix::new();
+    for (flag_key, setting) in config["f"].as_object().unwrap() {
+        let flag_type = match setting["t"].as_u64() {
+            Some(0) => FlagType::Bool,
+            Some(1) => FlagType::String,
+            Some(2) => FlagType::Int,
+            _ => FlagType::Float,
+        };
+        matrix = matrix.flag(flag_key, flag_type);
+    }
+    matrix
+        .context("anonymous", EvaluationContext::default())
+        .context(
+            "user",
+            EvaluationContext::default().with_targeting_key("#SOME-USER-ID#"),
+        )
+        .context(
+            "example-email",
+            EvaluationContext::default()
+                .with_targeting_key("#SOME-USER-ID#")
+                .with_custom_field("Email", "configcat@example.com"),
+        )
+        .context(
+            "country",
+            EvaluationContext::default()
+                .with_targeting_key("#OTHER-USER-ID#")
+                .with_custom_field("Country", "Hungary"),
+        )
+}
+
+#[tokio::test]
+async fn evaluation_matrix() {
+    let Some(sdk_key) = sdk_key() else {
+        return;
+    };
+    let provider = manual(&sdk_key);
+    provider.refresh().await.unwrap();
+
+    let snapshot = format!("tests/data/live/{}.json", sdk_key.replace('/', "_"));
+    std::fs::create_dir_all("tests/data/live").unwrap();
+    matrix(&provider)
+        .await
+        .assert_snapshot(&provider, snapshot)
+        .await;
+}
+
+#[tokio::test]
+async fn polls() {
+    let Some(sdk_key) = sdk_key() else {
+        return;
+    };
+    let provider = ConfigCatProvider::builder(&sdk_key)
+        .polling_mode(PollingMode::AutoPoll(Duration::from_secs(1)))
+        .build()
+        .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while provider.fetch_metrics().successes < 2 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(None, metrics.last_failure);
+    assert!(provider.export_config().await.is_some());
+}
+
+#[tokio::test]
+async fn refreshes() {
+    let Some(sdk_key) = sdk_key() else {
+        return;
+    };
+    let provider = manual(&sdk_key);
+
+    provider.refresh().await.unwrap();
+    provider.refresh().await.unwrap();
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(2, metrics.successes);
+    assert_eq!(0, metrics.failures);
+    assert!(metrics.last_success.is_some());
+}
+
+#[tokio::test]
+async fn maps_errors() {
+    let Some(sdk_key) = sdk_key() else {
+        return;
+    };
+    let provider = manual(&sdk_key);
+    provider.refresh().await.unwrap();
+    let ctx = EvaluationContext::default();
+    let config = provider.export_config().await.unwrap();
+    let (flag_key, setting) = config["f"].as_object().unwrap().iter().next().unwrap();
+    let mismatched = if setting["t"].as_u64() == Some(0) {
+        provider
+            .resolve_int_value(flag_key, &ctx)
+            .await
+            .map(drop)
+            .unwrap_err()
+    } else {
+        provider
+            .resolve_bool_value(flag_key, &ctx)
+            .await
+            .map(drop)
+            .unwrap_err()
+    };
+    let missing = provider
+        .resolve_bool_value("__missing_live_flag__", &ctx)
+        .await
+        .unwrap_err();
+
+    assert_eq!(EvaluationErrorCode::TypeMismatch, mismatched.code);
+    assert_eq!(EvaluationErrorCode::FlagNotFound, missing.code);
+}
+
+#[tokio::test]
+async fn fails_with_unknown_sdk_key() {
+    if sdk_key().is_none() {
+        return;
+    }
+    let provider = manual(UNKNOWN_SDK_KEY);
+
+    assert!(provider.refresh().await.is_err());
+
+    let metrics = provider.fetch_metrics();
+    assert_eq!(1, metrics.failures);
+    assert!(metrics.last_failure.is_some());
+    let err = provider
+        .resolve_bool_value("isAwesomeFeatureEnabled", &EvaluationContext::default())
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "tracing")]
+mod common;
+
+use configcat_openfeature_provider::{SdkLogBridge, SDK_LOG_TARGET};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+#[tokio::test]
+async fn sdk_logs_are_routed_to_tracing() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = tracing_subscriber::registry().with(CollectingLayer(events.clone()));
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .sdk_log_bridge(SdkLogBridge::new().map_level(log::Level::Error, Level::WARN))
+        .build()
+        .unwrap();
+
+    _ = provider
+        .resolve_bool_value("non-existing", &EvaluationContext::default())
+        .await;
+
+    let events = events.lock().unwrap();
+    let (target, level, message) = events
+        .iter()
+        .find(|(target, _, _)| target == SDK_LOG_TARGET)
+        .unwrap();
+    assert_eq!(SDK_LOG_TARGET, target);
+    assert_eq!(Level::WARN, *level);
+    assert!(message.starts_with("Failed to evaluate setting 'non-existing'"));
+}
+
+struct CollectingLayer(Arc<Mutex<Vec<(String, Level, String)>>>);
+
+impl<S: Subscriber> Layer<S> for CollectingLayer {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut visitor = MessageVisitor(String::new());
+        event.record(&mut visitor);
+        self.0.lock().unwrap().push((
+            event.metadata().target().to_owned(),
+            *event.metadata().level(),
+            visitor.0,
+        ));
+    }
+}
+
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            write!(self.0, "{value:?}").unwrap();
+        }
+    }
+}
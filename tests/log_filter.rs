@@ -0,0 +1,95 @@
+#![cfg(feature = "tracing-reload")]
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::EvaluationContext;
+use std::time::Duration;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+#[tokio::test]
+async fn reload_log_filter() {
+    let mut server = mockito::Server::new_async().await;
+    let v1 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json("debug"))
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+    let (filter, handle) = reload::Layer::new(LevelFilter::INFO);
+    let subscriber = tracing_subscriber::registry().with(filter);
+
+    let task = provider
+        .reload_log_filter("logLevel", EvaluationContext::default(), handle.clone())
+        .await;
+    wait_for(&handle, LevelFilter::DEBUG).await;
+
+    v1.remove_async().await;
+    let v2 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json("not a level"))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(Some(LevelFilter::DEBUG), handle.clone_current());
+
+    v2.remove_async().await;
+    let v3 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json("warn"))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+    wait_for(&handle, LevelFilter::WARN).await;
+
+    drop(subscriber);
+    v3.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json("error"))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), task)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+async fn wait_for<S>(handle: &reload::Handle<LevelFilter, S>, expected: LevelFilter) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while handle.clone_current() != Some(expected) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+fn config_json(level: &str) -> String {
+    let mut config: serde_json::Value = serde_json::from_str(
+        std::fs::read_to_string("tests/data/test_json_complex.json")
+            .unwrap()
+            .as_str(),
+    )
+    .unwrap();
+    let mut setting = config["f"]["stringSetting"].clone();
+    setting["v"]["s"] = serde_json::Value::from(level);
+    config["f"]["logLevel"] = setting;
+    config.to_string()
+}
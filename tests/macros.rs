@@ -0,0 +1,50 @@
+mod common;
+
+use common::provider;
+use configcat_openfeature_provider::declare_flags;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+
+declare_flags! {
+    /// A boolean flag.
+    pub EnabledFeature: bool = "enabledFeature";
+    IntSetting: i64 = "intSetting";
+    DoubleSetting: f64 = "doubleSetting";
+    pub(crate) StringSetting: String = "stringSetting";
+    Missing: bool = "non-existing";
+}
+
+#[tokio::test]
+async fn typed_accessors() {
+    let provider = provider();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!("enabledFeature", EnabledFeature::KEY);
+    assert!(EnabledFeature::get(&provider, &ctx, false).await);
+    assert_eq!(5, IntSetting::get(&provider, &ctx, 0).await);
+    assert!((DoubleSetting::get(&provider, &ctx, 0.0).await - 1.2).abs() < f64::EPSILON);
+    assert_eq!(
+        "test",
+        StringSetting::get(&provider, &ctx, String::new()).await
+    );
+}
+
+#[tokio::test]
+async fn default_on_error() {
+    let provider = provider();
+    let ctx = EvaluationContext::default();
+
+    assert!(Missing::get(&provider, &ctx, true).await);
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        Missing::details(&provider, &ctx).await.unwrap_err().code
+    );
+}
+
+#[tokio::test]
+async fn typed_keys() {
+    let provider = provider();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!("intSetting", IntSetting::FLAG.key());
+    assert_eq!(5, provider.get(IntSetting::FLAG, &ctx, 0).await);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::provider;
+use configcat_openfeature_provider::{
+    EvaluationEvent, EvaluationSink, FlagMigration, MigrationExposure,
+};
+use open_feature::EvaluationContext;
+use std::sync::{Arc, Mutex};
+
+fn user(targeting_key: &str) -> EvaluationContext {
+    EvaluationContext::default().with_targeting_key(targeting_key)
+}
+
+#[tokio::test]
+async fn serves_by_percentage() {
+    let old_only = FlagMigration::new(provider(), "disabledFeature", "enabledFeature", 0);
+    let new_only = FlagMigration::new(provider(), "disabledFeature", "enabledFeature", 100);
+
+    for i in 0..20 {
+        let ctx = user(&format!("user-{i}"));
+        assert!(!old_only.resolve_bool(&ctx).await.unwrap().value);
+        assert!(new_only.resolve_bool(&ctx).await.unwrap().value);
+    }
+}
+
+#[tokio::test]
+async fn split_is_consistent() {
+    let migration = FlagMigration::new(provider(), "disabledFeature", "enabledFeature", 50);
+    let wider = FlagMigration::new(provider(), "disabledFeature", "enabledFeature", 80);
+
+    let mut served_new = 0;
+    for i in 0..200 {
+        let ctx = user(&format!("user-{i}"));
+        let value = migration.resolve_bool(&ctx).await.unwrap().value;
+        assert_eq!(value, migration.serves_new(&ctx));
+        assert_eq!(value, migration.resolve_bool(&ctx).await.unwrap().value);
+        if value {
+            served_new += 1;
+            assert!(wider.serves_new(&ctx));
+        }
+    }
+    assert!((60..140).contains(&served_new), "{served_new}");
+}
+
+#[tokio::test]
+async fn no_targeting_key_serves_old() {
+    let migration = FlagMigration::new(provider(), "intSetting", "non-existing", 100);
+
+    let res = migration
+        .resolve_int(&EvaluationContext::default())
+        .await
+        .unwrap();
+    assert_eq!(5, res.value);
+    assert!(migration.resolve_int(&user("user-1")).await.is_err());
+}
+
+#[tokio::test]
+async fn exposure() {
+    let exposures = Arc::new(Mutex::new(Vec::new()));
+    let recorded = exposures.clone();
+    let migration = FlagMigration::new(provider(), "stringSetting", "stringSetting2", 100)
+        .on_exposure(move |exposure| recorded.lock().unwrap().push(exposure.clone()));
+
+    let res = migration
+        .resolve_string(&EvaluationContext::default())
+        .await
+        .unwrap();
+    assert_eq!("test", res.value);
+    let _ = migration.resolve_string(&user("user-1")).await;
+
+    assert_eq!(
+        vec![
+            MigrationExposure {
+                flag_key: "stringSetting".to_owned(),
+                served_new: false,
+                old_key: "stringSetting".to_owned(),
+                new_key: "stringSetting2".to_owned(),
+                targeting_key: None,
+            },
+            MigrationExposure {
+                flag_key: "stringSetting2".to_owned(),
+                served_new: true,
+                old_key: "stringSetting".to_owned(),
+                new_key: "stringSetting2".to_owned(),
+                targeting_key: Some("user-1".to_owned()),
+            },
+        ],
+        *exposures.lock().unwrap()
+    );
+}
+
+#[derive(Clone, Default)]
+struct CollectingSink {
+    flag_keys: Arc<Mutex<Vec<String>>>,
+}
+
+impl EvaluationSink for CollectingSink {
+    fn record(&self, event: &EvaluationEvent) {
+        self.flag_keys.lock().unwrap().push(event.flag_key.clone());
+    }
+}
+
+#[tokio::test]
+async fn evaluates_served_flag_only() {
+    let sink = CollectingSink::default();
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .sink(sink.clone())
+        .build()
+        .unwrap();
+    let migration = FlagMigration::new(provider, "disabledFeature", "enabledFeature", 100);
+
+    _ = migration.resolve_bool(&EvaluationContext::default()).await;
+    _ = migration.resolve_bool(&user("user-1")).await;
+
+    assert_eq!(
+        vec!["disabledFeature", "enabledFeature"],
+        *sink.flag_keys.lock().unwrap()
+    );
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use configcat_openfeature_provider::{ConfigCatProvider, MultiEnvConfigCatProvider};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+
+fn provider(path: &str) -> ConfigCatProvider {
+    common::builder(path).build().unwrap()
+}
+
+fn tenant(tenant: &str) -> EvaluationContext {
+    EvaluationContext::default().with_custom_field("Tenant", tenant)
+}
+
+#[tokio::test]
+async fn routes_by_attribute() {
+    let multi = MultiEnvConfigCatProvider::new("Tenant")
+        .environment("acme", provider("tests/data/test_json_complex.json"))
+        .environment("globex", provider("tests/data/test_settings.json"));
+
+    assert!(
+        multi
+            .resolve_bool_value("enabledFeature", &tenant("acme"))
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        "stripe",
+        multi
+            .resolve_string_value("payments.provider", &tenant("globex"))
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        multi
+            .resolve_bool_value("enabledFeature", &tenant("globex"))
+            .await
+            .unwrap_err()
+            .code
+    );
+    assert_eq!(
+        EvaluationErrorCode::InvalidContext,
+        multi
+            .resolve_bool_value("enabledFeature", &tenant("initech"))
+            .await
+            .unwrap_err()
+            .code
+    );
+    assert_eq!(
+        EvaluationErrorCode::InvalidContext,
+        multi
+            .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+            .await
+            .unwrap_err()
+            .code
+    );
+}
+
+#[tokio::test]
+async fn default_environment() {
+    let multi = MultiEnvConfigCatProvider::new("Tenant")
+        .environment("globex", provider("tests/data/test_settings.json"))
+        .default_environment(provider("tests/data/test_json_complex.json"));
+
+    assert_eq!(
+        5,
+        multi
+            .resolve_int_value("intSetting", &tenant("initech"))
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        5,
+        multi
+            .resolve_int_value("intSetting", &EvaluationContext::default())
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!("MultiEnvConfigCatProvider", multi.metadata().name);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "ofrep")]
+mod common;
+
+use configcat_openfeature_provider::OfrepHandler;
+use serde_json::json;
+
+fn handler() -> OfrepHandler {
+    OfrepHandler::new(
+        common::builder("tests/data/test_json_complex.json")
+            .build()
+            .unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn evaluate_flag() {
+    let response = handler()
+        .evaluate_flag(
+            "disabledFeature",
+            br#"{"context":{"targetingKey":"example@matching.com"}}"#,
+        )
+        .await;
+
+    assert_eq!(200, response.status);
+    assert_eq!("disabledFeature", response.body["key"]);
+    assert_eq!(true, response.body["value"]);
+    assert_eq!("TARGETING_MATCH", response.body["reason"]);
+    assert_eq!("v-disabled-t", response.body["variant"]);
+    assert_eq!(
+        json!({ "variationId": "v-disabled-t" }),
+        response.body["metadata"]
+    );
+}
+
+#[tokio::test]
+async fn evaluate_flag_errors() {
+    let handler = handler();
+
+    let response = handler.evaluate_flag("non-existing", b"{}").await;
+    assert_eq!(404, response.status);
+    assert_eq!("non-existing", response.body["key"]);
+    assert_eq!("FLAG_NOT_FOUND", response.body["errorCode"]);
+
+    let response = handler.evaluate_flag("enabledFeature", b"{").await;
+    assert_eq!(400, response.status);
+    assert_eq!("PARSE_ERROR", response.body["errorCode"]);
+
+    let response = handler
+        .evaluate_flag("enabledFeature", br#"{"context":{"address":{"city":"x"}}}"#)
+        .await;
+    assert_eq!(400, response.status);
+    assert_eq!("INVALID_CONTEXT", response.body["errorCode"]);
+}
+
+#[tokio::test]
+async fn evaluate_flags() {
+    let response = handler()
+        .evaluate_flags(br#"{"context":{"targetingKey":"example@matching.com"}}"#)
+        .await;
+
+    assert_eq!(200, response.status);
+    assert!(!response.body.to_string().contains("matchedTargetingRule"));
+    assert!(!response
+        .body
+        .to_string()
+        .contains("matchedPercentageOption"));
+    let flags = response.body["flags"].as_array().unwrap();
+    assert_eq!(6, flags.len());
+    assert_eq!(
+        &json!({
+            "key": "intSetting",
+            "value": 5,
+            "reason": "DEFAULT",
+            "variant": "v-int",
+            "metadata": { "variationId": "v-int" }
+        }),
+        flags
+            .iter()
+            .find(|flag| flag["key"] == "intSetting")
+            .unwrap()
+    );
+}
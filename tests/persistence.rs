@@ -0,0 +1,137 @@
+use configcat::PollingMode;
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::path::PathBuf;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> String {
+    std::fs::read_to_string("tests/data/test_json_complex.json").unwrap()
+}
+
+fn disabled_config_json() -> String {
+    let mut config: serde_json::Value = serde_json::from_str(config_json().as_str()).unwrap();
+    config["f"]["enabledFeature"]["v"]["b"] = serde_json::Value::Bool(false);
+    config.to_string()
+}
+
+fn persist_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "configcat-openfeature-{name}-{}.txt",
+        std::process::id()
+    ));
+    _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn persists_fetched_config() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-1\"")
+        .with_body(config_json())
+        .create_async()
+        .await;
+    let path = persist_path("fetched");
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .persist_config(&path)
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+
+    let persisted = std::fs::read_to_string(&path).unwrap();
+    assert!(persisted.contains("\"etag-1\""));
+    assert!(persisted.contains("enabledFeature"));
+    _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn loads_persisted_config_when_fetch_fails() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(500)
+        .create_async()
+        .await;
+    let path = persist_path("failed");
+    std::fs::write(&path, format!("0\n\"etag-1\"\n{}", config_json())).unwrap();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::LazyLoad(std::time::Duration::from_secs(60)))
+        .persist_config(&path)
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert!(result.value);
+    assert_eq!(1, provider.fetch_metrics().failures);
+    _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn fallback_config_when_fetch_fails() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(500)
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::AutoPoll(std::time::Duration::from_secs(60)))
+        .fallback_config_bytes(include_bytes!("data/test_json_complex.json"))
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert!(result.value);
+}
+
+#[tokio::test]
+async fn fetched_config_replaces_fallback_config() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-1\"")
+        .with_body(disabled_config_json())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .fallback_config_bytes(include_bytes!("data/test_json_complex.json"))
+        .build()
+        .unwrap();
+
+    let ctx = EvaluationContext::default();
+    assert!(
+        provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    provider.refresh().await.unwrap();
+    assert!(
+        !provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "proptest")]
+
+use configcat_openfeature_provider::testing::{
+    check_evaluation, evaluation_context, ConfigFixture, FlagFixture, RuleFixture,
+};
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContextFieldValue, EvaluationErrorCode};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use std::sync::LazyLock;
+use tokio::runtime::Runtime;
+
+fn provider() -> ConfigCatProvider {
+    ConfigFixture::new()
+        .flag(
+            "discount",
+            FlagFixture::new(0)
+                .rule(
+                    RuleFixture::new()
+                        .is_one_of("Country", &["HU", "AT"])
+                        .number_greater_than("Age", 18.0)
+                        .then(20),
+                )
+                .rule(
+                    RuleFixture::new()
+                        .contains("Email", &["@example.com"])
+                        .then(10),
+                ),
+        )
+        .flag(
+            "theme",
+            FlagFixture::new("light")
+                .rule(
+                    RuleFixture::new()
+                        .number_less_than("Age", 13.0)
+                        .then("kids"),
+                )
+                .percentage(50, "light", "v-light")
+                .percentage(50, "dark", "v-dark"),
+        )
+        .into_provider()
+}
+
+static PROVIDER: LazyLock<ConfigCatProvider> = LazyLock::new(provider);
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+    });
+    RUNTIME.block_on(future)
+}
+
+proptest! {
+    #[test]
+    fn evaluation_is_robust(ctx in evaluation_context(16)) {
+        block_on(async {
+            for flag_key in ["discount", "theme", "missing"] {
+                check_evaluation(&*PROVIDER, flag_key, &ctx).await?;
+            }
+            Ok::<_, TestCaseError>(())
+        })?;
+    }
+
+    #[test]
+    fn struct_fields_are_invalid_context(mut ctx in evaluation_context(4)) {
+        ctx.custom_fields.insert(
+            "Roles".to_owned(),
+            EvaluationContextFieldValue::new_struct(vec!["admin".to_owned()]),
+        );
+        let err = block_on(PROVIDER.resolve_int_value("discount", &ctx)).unwrap_err();
+        prop_assert_eq!(EvaluationErrorCode::InvalidContext, err.code);
+    }
+}
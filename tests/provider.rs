@@ -1,10 +1,12 @@
+mod common;
+
 use configcat::FileDataSource;
 use configcat::OverrideBehavior::LocalOnly;
-use configcat_openfeature_provider::ConfigCatProvider;
+use configcat_openfeature_provider::{ConfigCatProvider, FlagSet, FlagValues};
 use open_feature::provider::FeatureProvider;
 use open_feature::{
     EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, OpenFeature,
-    StructValue,
+    StructValue, Value,
 };
 
 #[test]
@@ -164,6 +166,239 @@ async fn eval_type_mismatch() {
     assert_eq!(details.clone().err().unwrap().message.unwrap(), "The type of a setting must match the requested type. Setting's type was 'String' but the requested type was 'bool'. Learn more: https://configcat.com/docs/sdk-reference/rust/#setting-type-mapping");
 }
 
+#[tokio::test]
+async fn resolve_all() {
+    let provider = ConfigCatProvider::new(create_client());
+
+    let all = provider
+        .resolve_all(&EvaluationContext::default().with_targeting_key("example@matching.com"))
+        .await;
+
+    assert_eq!(6, all.len());
+    let disabled = &all["disabledFeature"];
+    assert_eq!(Value::Bool(true), disabled.value);
+    assert_eq!("v-disabled-t", disabled.variant.as_deref().unwrap());
+    assert_eq!(Some(EvaluationReason::TargetingMatch), disabled.reason);
+    assert_eq!(Value::Int(5), all["intSetting"].value);
+    assert_eq!(Value::Float(1.2), all["doubleSetting"].value);
+    assert_eq!(Value::String("test".to_owned()), all["stringSetting"].value);
+    assert_eq!(
+        Some(EvaluationReason::Default),
+        all["enabledFeature"].reason
+    );
+}
+
+#[tokio::test]
+async fn flag_keys() {
+    let provider = ConfigCatProvider::new(create_client());
+
+    assert_eq!(
+        vec![
+            "disabledFeature",
+            "doubleSetting",
+            "enabledFeature",
+            "intSetting",
+            "objectSetting",
+            "stringSetting"
+        ],
+        provider.flag_keys().await
+    );
+}
+
+#[tokio::test]
+async fn resolve_set() {
+    let provider = ConfigCatProvider::new(create_client());
+
+    let flags: Flags = provider.resolve_set(&EvaluationContext::default()).await;
+
+    assert!(flags.enabled);
+    assert!(!flags.disabled);
+    assert_eq!(5, flags.int);
+    assert_eq!(1.2, flags.double);
+    assert_eq!("test", flags.string);
+    assert!(flags.object.unwrap().fields.contains_key("bool_field"));
+    assert!(flags.missing);
+}
+
+#[tokio::test]
+async fn bootstrap_payload() {
+    let provider = ConfigCatProvider::new(create_client());
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+
+    let payload = provider
+        .bootstrap_payload(&ctx, Some(&["disabledFeature", "intSetting"]))
+        .await;
+
+    assert_eq!(
+        serde_json::json!({
+            "flags": [
+                {
+                    "key": "disabledFeature",
+                    "value": true,
+                    "reason": "TARGETING_MATCH",
+                    "variant": "v-disabled-t",
+                    "metadata": { "variationId": "v-disabled-t" }
+                },
+                {
+                    "key": "intSetting",
+                    "value": 5,
+                    "reason": "DEFAULT",
+                    "variant": "v-int",
+                    "metadata": { "variationId": "v-int" }
+                }
+            ]
+        }),
+        payload
+    );
+    let all = provider.bootstrap_payload(&ctx, None).await;
+    assert_eq!(6, all["flags"].as_array().unwrap().len());
+    assert!(!all.to_string().contains("matchedTargetingRule"));
+    assert!(!all.to_string().contains("@matching.com"));
+}
+
+#[tokio::test]
+async fn when_enabled() {
+    let provider = ConfigCatProvider::new(create_client());
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(
+        Some(1),
+        provider
+            .when_enabled("enabledFeature", &ctx, async { 1 })
+            .await
+    );
+    assert_eq!(
+        None,
+        provider
+            .when_enabled("disabledFeature", &ctx, async { 1 })
+            .await
+    );
+    assert_eq!(
+        "old",
+        provider
+            .when_enabled("disabledFeature", &ctx, async { "new" })
+            .or_else(async { "old" })
+            .await
+    );
+    assert_eq!(
+        "new",
+        provider
+            .when_enabled(
+                "disabledFeature",
+                &EvaluationContext::default().with_targeting_key("example@matching.com"),
+                async { "new" }
+            )
+            .or_else(async { "old" })
+            .await
+    );
+    assert_eq!(
+        "old",
+        provider
+            .when_enabled("stringSetting", &ctx, async { "new" })
+            .or_else(async { "old" })
+            .await
+    );
+}
+
+#[tokio::test]
+async fn load_settings() {
+    let provider = ConfigCatProvider::new(
+        configcat::Client::builder("local")
+            .overrides(
+                Box::new(FileDataSource::new("tests/data/test_settings.json").unwrap()),
+                LocalOnly,
+            )
+            .build()
+            .unwrap(),
+    );
+    let ctx = EvaluationContext::default();
+
+    let settings: PaymentSettings = provider.load_settings("payments.", &ctx).await.unwrap();
+    assert_eq!("stripe", settings.provider);
+    assert_eq!(30, settings.timeout_secs);
+    assert_eq!(3, settings.retry.max_attempts);
+    assert!(!settings.sandbox);
+
+    let err = provider
+        .load_settings::<PaymentSettings>("search.", &ctx)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+}
+
+#[tokio::test]
+async fn alias() {
+    let evaluated = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = evaluated.clone();
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .alias("old_int_setting", "intSetting")
+        .on_evaluated(move |flag_key, _| recorded.lock().unwrap().push(flag_key.to_owned()))
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    for _ in 0..2 {
+        let details = provider
+            .resolve_int_value("old_int_setting", &ctx)
+            .await
+            .unwrap();
+        assert_eq!(5, details.value);
+        assert_eq!("v-int", details.variant.unwrap());
+    }
+    assert_eq!(
+        5,
+        provider
+            .resolve_int_value("intSetting", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(vec!["intSetting"; 3], *evaluated.lock().unwrap());
+}
+
+#[tokio::test]
+async fn key_prefix() {
+    let provider = common::builder("tests/data/test_settings.json")
+        .key_prefix("payments.")
+        .alias("vendor", "provider")
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(
+        "stripe",
+        provider
+            .resolve_string_value("provider", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        "stripe",
+        provider
+            .resolve_string_value("vendor", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        provider
+            .resolve_bool_value("search.enabled", &ctx)
+            .await
+            .unwrap_err()
+            .code
+    );
+    assert_eq!(
+        vec!["provider", "retry.maxAttempts", "timeoutSecs"],
+        provider.flag_keys().await
+    );
+    let all = provider.resolve_all(&ctx).await;
+    assert_eq!(3, all.len());
+    assert_eq!(Value::Int(30), all["timeoutSecs"].value);
+}
+
 fn create_client() -> configcat::Client {
     configcat::Client::builder("local")
         .overrides(
@@ -194,3 +429,43 @@ impl TryFrom<StructValue> for Sample {
         Ok(sample)
     }
 }
+
+struct Flags {
+    enabled: bool,
+    disabled: bool,
+    int: i64,
+    double: f64,
+    string: String,
+    object: Option<StructValue>,
+    missing: bool,
+}
+
+impl FlagSet for Flags {
+    fn from_flags(flags: &FlagValues) -> Self {
+        Self {
+            enabled: flags.bool("enabledFeature", false),
+            disabled: flags.bool("disabledFeature", true),
+            int: flags.int("intSetting", 0),
+            double: flags.float("doubleSetting", 0.0),
+            string: flags.string("stringSetting", ""),
+            object: flags.object("objectSetting"),
+            missing: flags.bool("non-existing", true),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaymentSettings {
+    provider: String,
+    timeout_secs: i64,
+    retry: RetrySettings,
+    #[serde(default)]
+    sandbox: bool,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RetrySettings {
+    max_attempts: i64,
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "proxy-grpc")]
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::body::Body;
+use tonic::codegen::http;
+use tonic::server::NamedService;
+use tonic::transport::{Channel, Server};
+use tonic::Status;
+use tonic_prost::ProstCodec;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct EvalRequest {
+    #[prost(string, tag = "1")]
+    sdk_id: String,
+    #[prost(string, tag = "2")]
+    key: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct EvalAllResponse {}
+
+type Updates = mpsc::Receiver<Result<EvalAllResponse, Status>>;
+
+/// A ConfigCat Proxy `FlagService` that streams the updates sent by the test, and reports the
+/// called methods with the requested SDK identifiers.
+#[derive(Clone)]
+struct FlagService {
+    updates: Arc<Mutex<Option<Updates>>>,
+    calls: mpsc::UnboundedSender<(String, String)>,
+}
+
+impl NamedService for FlagService {
+    const NAME: &'static str = "configcat.FlagService";
+}
+
+impl tower::Service<http::Request<Body>> for FlagService {
+    type Response = http::Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let service = self.clone();
+        let method = request.uri().path().to_owned();
+        Box::pin(async move {
+            let handler = tower::service_fn(move |request: tonic::Request<EvalRequest>| {
+                _ = service
+                    .calls
+                    .send((method.clone(), request.into_inner().sdk_id));
+                let updates = service.updates.lock().unwrap().take().unwrap();
+                async move { Ok::<_, Status>(tonic::Response::new(ReceiverStream::new(updates))) }
+            });
+            let mut grpc =
+                tonic::server::Grpc::new(ProstCodec::<EvalAllResponse, EvalRequest>::default());
+            Ok(grpc.server_streaming(handler, request).await)
+        })
+    }
+}
+
+async fn start_proxy(updates: Updates) -> (Channel, mpsc::UnboundedReceiver<(String, String)>) {
+    let (calls, called) = mpsc::unbounded_channel();
+    let service = FlagService {
+        updates: Arc::new(Mutex::new(Some(updates))),
+        calls,
+    };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(service)
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let channel = Channel::from_shared(format!("http://{address}"))
+        .unwrap()
+        .connect_lazy();
+    (channel, called)
+}
+
+async fn wait_for_int_setting(provider: &ConfigCatProvider, expected: i64) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let value = provider
+                .resolve_int_value("intSetting", &EvaluationContext::default())
+                .await
+                .map(|details| details.value);
+            if value == Ok(expected) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn refreshes_on_proxy_updates() {
+    let original: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string("tests/data/test_json_complex.json").unwrap(),
+    )
+    .unwrap();
+    let mut updated = original.clone();
+    updated["f"]["intSetting"]["v"]["i"] = 42.into();
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = Vec::new();
+    for (i, config) in [original, updated].iter().enumerate() {
+        mocks.push(
+            server
+                .mock("GET", CONFIG_PATH)
+                .with_status(200)
+                .with_header("ETag", &format!("\"etag-{i}\""))
+                .with_body(config.to_string())
+                .expect(1)
+                .create_async()
+                .await,
+        );
+    }
+    let (updates, received) = mpsc::channel(4);
+    let (channel, mut called) = start_proxy(received).await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .proxy_grpc(channel, "my-sdk")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        (
+            "/configcat.FlagService/EvalAllFlagsStream".to_owned(),
+            "my-sdk".to_owned()
+        ),
+        called.recv().await.unwrap()
+    );
+    updates.send(Ok(EvalAllResponse {})).await.unwrap();
+    wait_for_int_setting(&provider, 5).await;
+    updates.send(Ok(EvalAllResponse {})).await.unwrap();
+    wait_for_int_setting(&provider, 42).await;
+
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+    assert_eq!(2, provider.fetch_metrics().successes);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "proxy-sse")]
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+async fn int_setting(provider: &ConfigCatProvider) -> i64 {
+    provider
+        .resolve_int_value("intSetting", &EvaluationContext::default())
+        .await
+        .unwrap()
+        .value
+}
+
+#[tokio::test]
+async fn refreshes_on_proxy_events() {
+    let original: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string("tests/data/test_json_complex.json").unwrap(),
+    )
+    .unwrap();
+    let mut updated = original.clone();
+    updated["f"]["intSetting"]["v"]["i"] = 42.into();
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = Vec::new();
+    for (i, config) in [original, updated].iter().enumerate() {
+        mocks.push(
+            server
+                .mock("GET", CONFIG_PATH)
+                .with_status(200)
+                .with_header("ETag", &format!("\"etag-{i}\""))
+                .with_body(config.to_string())
+                .expect(1)
+                .create_async()
+                .await,
+        );
+    }
+    // Each value sent on the channel is written as an event, until the sender is dropped.
+    let (events, received) = mpsc::channel::<&'static str>();
+    let received = Mutex::new(received);
+    server
+        .mock("GET", "/sse/my-sdk/eval-all/e30=")
+        .match_header("Accept", "text/event-stream")
+        .with_status(200)
+        .with_header("Content-Type", "text/event-stream")
+        .with_chunked_body(move |writer| {
+            let received = received.lock().unwrap();
+            while let Ok(event) = received.recv() {
+                writer.write_all(event.as_bytes())?;
+                writer.flush()?;
+            }
+            Ok(())
+        })
+        .create_async()
+        .await;
+    let (changed, mut changes) = tokio::sync::mpsc::unbounded_channel();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .proxy_sse(server.url().as_str(), "my-sdk")
+        .on_configuration_changed(move || _ = changed.send(()))
+        .build()
+        .unwrap();
+
+    events.send("data: {\"intSetting\":").unwrap();
+    events.send("{\"value\":5}}\r\n\r\n").unwrap();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while provider.fetch_metrics().successes < 1 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(5, int_setting(&provider).await);
+
+    events.send(": keep-alive\n\ndata: {}\n\n").unwrap();
+    tokio::time::timeout(Duration::from_secs(5), changes.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(42, int_setting(&provider).await);
+
+    drop(events);
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+    assert_eq!(2, provider.fetch_metrics().successes);
+}
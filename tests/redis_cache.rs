@@ -0,0 +1,98 @@
+#![cfg(feature = "redis")]
+
+use configcat_openfeature_provider::{ProviderCache, RedisCache};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+type Commands = Arc<Mutex<Vec<Vec<String>>>>;
+
+/// A minimal Redis server that supports GET and SETEX, and records the received commands.
+async fn start_server() -> (String, Commands) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}/", listener.local_addr().unwrap());
+    let commands = Commands::default();
+    let store = Arc::new(Mutex::new(HashMap::new()));
+    let recorded = commands.clone();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(serve(stream, recorded.clone(), store.clone()));
+        }
+    });
+    (url, commands)
+}
+
+async fn serve(stream: TcpStream, commands: Commands, store: Arc<Mutex<HashMap<String, String>>>) {
+    let mut stream = BufReader::new(stream);
+    while let Some(command) = read_command(&mut stream).await {
+        let reply = match command[0].to_uppercase().as_str() {
+            "GET" => match store.lock().unwrap().get(&command[1]) {
+                Some(value) => format!("${}\r\n{value}\r\n", value.len()),
+                None => "$-1\r\n".to_owned(),
+            },
+            "SETEX" => {
+                store
+                    .lock()
+                    .unwrap()
+                    .insert(command[1].clone(), command[3].clone());
+                "+OK\r\n".to_owned()
+            }
+            "PING" => "+PONG\r\n".to_owned(),
+            _ => "+OK\r\n".to_owned(),
+        };
+        commands.lock().unwrap().push(command);
+        stream.write_all(reply.as_bytes()).await.unwrap();
+    }
+}
+
+async fn read_command(stream: &mut BufReader<TcpStream>) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        stream.read_line(&mut line).await.ok()?;
+        let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+        let mut arg = vec![0; len + 2];
+        stream.read_exact(&mut arg).await.ok()?;
+        arg.truncate(len);
+        args.push(String::from_utf8(arg).ok()?);
+    }
+    Some(args)
+}
+
+#[tokio::test]
+async fn read_write() {
+    let (url, commands) = start_server().await;
+    let cache = RedisCache::new(url.as_str())
+        .await
+        .unwrap()
+        .ttl(Duration::from_secs(90))
+        .key_prefix("app:");
+
+    assert_eq!(None, cache.read("key").await);
+
+    cache.write("key", "1700000000000\netag\n{}").await;
+    assert_eq!(
+        Some("1700000000000\netag\n{}".to_owned()),
+        cache.read("key").await
+    );
+
+    let set = commands
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|command| command[0] == "SETEX")
+        .cloned()
+        .unwrap();
+    assert_eq!(
+        vec!["SETEX", "app:key", "90", "1700000000000\netag\n{}"],
+        set
+    );
+}
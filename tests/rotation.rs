@@ -0,0 +1,122 @@
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, KeyRotationProvider};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::sync::{Arc, Mutex};
+
+const PRIMARY_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const PRIMARY_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+const SECONDARY_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/abcdefghijklmnopqrstuv";
+const SECONDARY_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/abcdefghijklmnopqrstuv/config_v6.json";
+
+fn config_json(int: i64) -> String {
+    let mut config: serde_json::Value = serde_json::from_str(
+        std::fs::read_to_string("tests/data/test_json_complex.json")
+            .unwrap()
+            .as_str(),
+    )
+    .unwrap();
+    config["f"]["intSetting"]["v"]["i"] = serde_json::Value::from(int);
+    config.to_string()
+}
+
+fn provider(server: &mockito::Server, sdk_key: &str) -> ConfigCatProvider {
+    ConfigCatProvider::builder(sdk_key)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn switches_on_rejected_key() {
+    let mut server = mockito::Server::new_async().await;
+    let primary_ok = server
+        .mock("GET", PRIMARY_PATH)
+        .with_status(200)
+        .with_body(config_json(1))
+        .create_async()
+        .await;
+    server
+        .mock("GET", SECONDARY_PATH)
+        .with_status(200)
+        .with_body(config_json(2))
+        .create_async()
+        .await;
+    let primary = provider(&server, PRIMARY_KEY);
+    let secondary = provider(&server, SECONDARY_KEY);
+    primary.refresh().await.unwrap();
+    secondary.refresh().await.unwrap();
+
+    let switches = Arc::new(Mutex::new(Vec::new()));
+    let recorded = switches.clone();
+    let rotation = KeyRotationProvider::new(primary.clone(), secondary).on_switch(move |reason| {
+        recorded.lock().unwrap().push(reason.to_owned());
+    });
+    let ctx = EvaluationContext::default();
+
+    let res = rotation
+        .resolve_int_value("intSetting", &ctx)
+        .await
+        .unwrap();
+    assert_eq!(1, res.value);
+    assert!(!rotation.is_switched());
+
+    primary_ok.remove_async().await;
+    server
+        .mock("GET", PRIMARY_PATH)
+        .with_status(403)
+        .create_async()
+        .await;
+    assert!(primary.refresh().await.is_err());
+
+    let res = rotation
+        .resolve_int_value("intSetting", &ctx)
+        .await
+        .unwrap();
+    assert_eq!(2, res.value);
+    let res = rotation
+        .resolve_int_value("intSetting", &ctx)
+        .await
+        .unwrap();
+    assert_eq!(2, res.value);
+    assert!(rotation.is_switched());
+    assert_eq!(1, switches.lock().unwrap().len());
+}
+
+#[tokio::test]
+async fn keeps_primary_on_other_failures() {
+    let mut server = mockito::Server::new_async().await;
+    let primary_ok = server
+        .mock("GET", PRIMARY_PATH)
+        .with_status(200)
+        .with_body(config_json(1))
+        .create_async()
+        .await;
+    server
+        .mock("GET", SECONDARY_PATH)
+        .with_status(200)
+        .with_body(config_json(2))
+        .create_async()
+        .await;
+    let primary = provider(&server, PRIMARY_KEY);
+    primary.refresh().await.unwrap();
+    let rotation = KeyRotationProvider::new(primary.clone(), provider(&server, SECONDARY_KEY));
+
+    primary_ok.remove_async().await;
+    server
+        .mock("GET", PRIMARY_PATH)
+        .with_status(500)
+        .create_async()
+        .await;
+    assert!(primary.refresh().await.is_err());
+
+    let res = rotation
+        .resolve_int_value("intSetting", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert_eq!(1, res.value);
+    assert!(!rotation.is_switched());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "runtime-agnostic")]
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::ConfigCatProvider;
+use futures::executor::block_on;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> String {
+    std::fs::read_to_string("tests/data/test_json_complex.json").unwrap()
+}
+
+#[test]
+fn runs_outside_tokio() {
+    let mut server = mockito::Server::new();
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .create();
+    let mut provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    block_on(provider.initialize(&ctx));
+    block_on(provider.refresh()).unwrap();
+    let details = block_on(provider.resolve_bool_value("enabledFeature", &ctx)).unwrap();
+
+    assert!(details.value);
+    assert_eq!(1, provider.fetch_metrics().successes);
+}
+
+#[test]
+fn polls_outside_tokio() {
+    let mut server = mockito::Server::new();
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .create();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::AutoPoll(Duration::from_millis(100)))
+        .build()
+        .unwrap();
+
+    while provider.fetch_metrics().successes < 2 {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let details =
+        block_on(provider.resolve_int_value("intSetting", &EvaluationContext::default())).unwrap();
+    assert_eq!(5, details.value);
+}
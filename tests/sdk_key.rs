@@ -0,0 +1,86 @@
+mod common;
+
+use configcat::ErrorKind;
+use configcat_openfeature_provider::ConfigCatProvider;
+
+const CONFIG_ID: &str = "PKDVCLf-Hq-h-kCzMp-L7Q";
+const ENVIRONMENT_ID: &str = "1234567890123456789012";
+
+fn build_error(sdk_key: &str) -> String {
+    let err = ConfigCatProvider::builder(sdk_key)
+        .build()
+        .map(drop)
+        .unwrap_err();
+    assert_eq!(ErrorKind::InvalidSdkKey, err.kind);
+    err.message
+}
+
+#[tokio::test]
+async fn accepts_valid_keys() {
+    let sdk_key = format!("configcat-sdk-1/{CONFIG_ID}/{ENVIRONMENT_ID}");
+    let legacy_sdk_key = format!("{CONFIG_ID}/{ENVIRONMENT_ID}");
+
+    assert!(ConfigCatProvider::builder(&sdk_key)
+        .offline(true)
+        .build()
+        .is_ok());
+    assert!(ConfigCatProvider::builder(&legacy_sdk_key)
+        .offline(true)
+        .build()
+        .is_ok());
+    assert!(ConfigCatProvider::builder("configcat-proxy/my-sdk")
+        .base_url("http://localhost:8050")
+        .offline(true)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn describes_invalid_keys() {
+    let cases = [
+        ("", "it's empty."),
+        (
+            &format!(" configcat-sdk-1/{CONFIG_ID}/{ENVIRONMENT_ID}\n"),
+            "it contains whitespace",
+        ),
+        (
+            &format!("https://cdn-global.configcat.com/configuration-files/configcat-sdk-1/{CONFIG_ID}/{ENVIRONMENT_ID}/config_v6.json"),
+            "it's a URL",
+        ),
+        (
+            "configcat-proxy/my-sdk",
+            "'configcat-proxy/' keys require the URL of the ConfigCat Proxy",
+        ),
+        ("configcat-proxy/", "the SDK identifier is missing"),
+        ("sdk-key", "but it has 1 '/'-separated parts."),
+        (
+            &format!("configcat-sdk-2/{CONFIG_ID}/{ENVIRONMENT_ID}"),
+            "it should start with 'configcat-sdk-1/', but it starts with 'configcat-sdk-2/'.",
+        ),
+        (
+            &format!("configcat-sdk-1/{CONFIG_ID}/{}", &ENVIRONMENT_ID[..20]),
+            "its environment part is 20 characters long instead of 22, it was probably truncated.",
+        ),
+        (
+            &format!("{CONFIG_ID}x/{ENVIRONMENT_ID}"),
+            "its config part is 23 characters long instead of 22, it probably has extra characters.",
+        ),
+    ];
+    for (sdk_key, problem) in cases {
+        let message = build_error(sdk_key);
+        assert!(message.contains(problem), "{message}");
+        assert!(
+            message.ends_with(
+                "Copy the SDK key of the config and environment from the ConfigCat Dashboard."
+            ),
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn skips_validation_with_local_only_overrides() {
+    let provider = common::builder("tests/data/test_json_complex.json").build();
+
+    assert!(provider.is_ok());
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "sentry")]
+mod common;
+
+use configcat_openfeature_provider::SentrySink;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+
+#[test]
+fn failed_evaluations_are_captured() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let events = sentry_core::test::with_captured_events(|| {
+        runtime.block_on(async {
+            let provider = common::builder("tests/data/test_json_complex.json")
+                .sink(SentrySink::new().capture_events(true))
+                .build()
+                .unwrap();
+            let ctx = EvaluationContext::default();
+            _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+            _ = provider.resolve_bool_value("non-existing", &ctx).await;
+        });
+    });
+
+    assert_eq!(1, events.len());
+    assert_eq!("non-existing", events[0].tags["flag_key"]);
+    assert_eq!("FLAG_NOT_FOUND", events[0].tags["error_code"]);
+    assert!(events[0]
+        .message
+        .as_ref()
+        .unwrap()
+        .starts_with("Failed to evaluate 'non-existing'"));
+}
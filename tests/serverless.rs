@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use configcat_openfeature_provider::{ConfigCatProvider, ProviderCache};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> String {
+    std::fs::read_to_string("tests/data/test_json_complex.json").unwrap()
+}
+
+#[derive(Clone, Default)]
+struct MapCache(Arc<Mutex<HashMap<String, String>>>);
+
+#[async_trait]
+impl ProviderCache for MapCache {
+    async fn read(&self, key: &str) -> Option<String> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    async fn write(&self, key: &str, value: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), value.to_owned());
+    }
+}
+
+/// Serves the same entry for every key.
+struct FixedCache(String);
+
+#[async_trait]
+impl ProviderCache for FixedCache {
+    async fn read(&self, _: &str) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    async fn write(&self, _: &str, _: &str) {}
+}
+
+#[tokio::test]
+async fn fetches_on_first_evaluation_and_persists() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-1\"")
+        .with_body(config_json())
+        .expect(2)
+        .create_async()
+        .await;
+    let cache = MapCache::default();
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .serverless(Duration::from_secs(60), Duration::from_secs(5))
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(0, provider.fetch_metrics().attempts);
+    assert!(
+        provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        5,
+        provider
+            .resolve_int_value("intSetting", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+
+    // Written before the evaluation completes, without a background task.
+    let entry = cache.0.lock().unwrap().values().next().cloned().unwrap();
+    assert!(entry.contains("\"etag-1\""));
+    assert_eq!(1, provider.fetch_metrics().attempts);
+
+    provider.refresh().await.unwrap();
+    assert_eq!(2, provider.fetch_metrics().successes);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn falls_back_to_cache_on_timeout() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_chunked_body(|writer| {
+            std::thread::sleep(Duration::from_secs(1));
+            writer.write_all(config_json().as_bytes())
+        })
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .serverless(Duration::from_secs(60), Duration::from_millis(200))
+        .cache(FixedCache(format!("0\n\"etag-0\"\n{}", config_json())))
+        .build()
+        .unwrap();
+
+    let result = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await
+        .unwrap();
+
+    assert!(result.value);
+    let metrics = provider.fetch_metrics();
+    assert_eq!(1, metrics.failures);
+    assert!(metrics.last_failure.unwrap().contains("timed out"));
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use configcat_openfeature_provider::{ConfigCatProvider, ShadowMismatch, ShadowProvider};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn provider(path: &str) -> ConfigCatProvider {
+    common::builder(path).build().unwrap()
+}
+
+async fn wait_for_mismatches<P, S>(shadow: &ShadowProvider<P, S>, count: u64)
+where
+    P: FeatureProvider,
+    S: FeatureProvider,
+{
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while shadow.mismatches() < count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn reports_mismatches() {
+    let mismatches = Arc::new(Mutex::new(Vec::new()));
+    let recorded = mismatches.clone();
+    let shadow = ShadowProvider::new(
+        provider("tests/data/test_json_complex.json"),
+        provider("tests/data/test_settings.json"),
+    )
+    .on_mismatch(move |mismatch| recorded.lock().unwrap().push(mismatch.clone()));
+    let ctx = EvaluationContext::default();
+
+    assert!(
+        shadow
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(shadow
+        .resolve_string_value("payments.provider", &ctx)
+        .await
+        .is_err());
+
+    // The results are compared in the background.
+    wait_for_mismatches(&shadow, 2).await;
+    let mut mismatches = mismatches.lock().unwrap().clone();
+    mismatches.sort_by(|a, b| a.flag_key.cmp(&b.flag_key));
+    assert_eq!(
+        vec![
+            ShadowMismatch {
+                flag_key: "enabledFeature".to_owned(),
+                primary: Some(Value::Bool(true)),
+                shadow: None,
+            },
+            ShadowMismatch {
+                flag_key: "payments.provider".to_owned(),
+                primary: None,
+                shadow: Some(Value::String("stripe".to_owned())),
+            }
+        ],
+        mismatches
+    );
+}
+
+#[tokio::test]
+async fn matching_results() {
+    let shadow = ShadowProvider::new(
+        provider("tests/data/test_json_complex.json"),
+        provider("tests/data/test_json_complex.json"),
+    );
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+
+    assert!(
+        shadow
+            .resolve_bool_value("disabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(shadow
+        .resolve_int_value("non-existing", &ctx)
+        .await
+        .is_err());
+    assert_eq!(
+        "value",
+        shadow
+            .resolve_struct_value("objectSetting", &ctx)
+            .await
+            .unwrap()
+            .value
+            .fields["text_field"]
+            .as_str()
+            .unwrap()
+    );
+
+    // Gives the background comparisons time to finish.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(0, shadow.mismatches());
+}
+
+#[tokio::test]
+async fn does_not_wait_for_shadow() {
+    // Accepts the connections, but never responds, so the shadow evaluations hang.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let unresponsive =
+        ConfigCatProvider::builder("configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012")
+            .base_url(format!("http://{}", listener.local_addr().unwrap()).as_str())
+            .polling_mode(configcat::PollingMode::LazyLoad(Duration::from_secs(60)))
+            .build()
+            .unwrap();
+    let shadow = ShadowProvider::new(provider("tests/data/test_json_complex.json"), unresponsive);
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(1),
+        shadow.resolve_bool_value("enabledFeature", &EvaluationContext::default()),
+    )
+    .await
+    .unwrap();
+
+    assert!(result.unwrap().value);
+}
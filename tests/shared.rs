@@ -0,0 +1,115 @@
+use configcat::OverrideBehavior::LocalOnly;
+use configcat::{FileDataSource, PollingMode};
+use configcat_openfeature_provider::ConfigCatProvider;
+use log::{LevelFilter, Log, Metadata, Record};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn config_json() -> String {
+    std::fs::read_to_string("tests/data/test_json_complex.json").unwrap()
+}
+
+#[tokio::test]
+async fn shared_providers_download_once() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json())
+        .expect(1)
+        .create_async()
+        .await;
+    let build = || {
+        ConfigCatProvider::builder(SDK_KEY)
+            .base_url(server.url().as_str())
+            .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+            .shared(true)
+            .build()
+            .unwrap()
+    };
+    let first = build();
+    let second = build();
+    let ctx = EvaluationContext::default();
+
+    assert!(
+        first
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(
+        second
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        first.fetch_metrics().last_success,
+        second.fetch_metrics().last_success
+    );
+    mock.assert_async().await;
+
+    drop(first);
+    drop(second);
+    let third = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .shared(true)
+        .build()
+        .unwrap();
+    assert_eq!(0, third.fetch_metrics().attempts);
+}
+
+static LOGGER: CollectingLogger = CollectingLogger(Mutex::new(Vec::new()));
+
+#[test]
+fn warns_about_ignored_options() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Warn);
+    let build = |polling_mode| {
+        ConfigCatProvider::builder("shared-options")
+            .overrides(
+                Box::new(FileDataSource::new("tests/data/test_json_complex.json").unwrap()),
+                LocalOnly,
+            )
+            .polling_mode(polling_mode)
+            .shared(true)
+            .build()
+            .unwrap()
+    };
+
+    let _first = build(PollingMode::Manual);
+    let _same = build(PollingMode::Manual);
+    assert!(LOGGER.0.lock().unwrap().is_empty());
+
+    let _different = build(PollingMode::LazyLoad(Duration::from_secs(60)));
+    let messages = LOGGER.0.lock().unwrap();
+    assert_eq!(1, messages.len());
+    assert!(messages[0].contains("different SDK client or polling options"));
+}
+
+struct CollectingLogger(Mutex<Vec<String>>);
+
+impl Log for CollectingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata
+            .target()
+            .starts_with("configcat_openfeature_provider::source")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use async_trait::async_trait;
+use configcat_openfeature_provider::{
+    AuditLog, BatchExporter, EvaluationEvent, EvaluationSink, ExportError, HttpTransport,
+    SampledSink, Transport,
+};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn sink_records_evaluations() {
+    let sink = CollectingSink::default();
+    let provider = create_builder().sink(sink.clone()).build().unwrap();
+
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+    _ = provider.resolve_bool_value("disabledFeature", &ctx).await;
+    _ = provider.resolve_bool_value("non-existing", &ctx).await;
+
+    let events = sink.events.lock().unwrap();
+    assert_eq!(2, events.len());
+    assert_eq!("disabledFeature", events[0].flag_key);
+    assert_eq!(Some(serde_json::Value::Bool(true)), events[0].value);
+    assert_eq!("v-disabled-t", events[0].variant.as_deref().unwrap());
+    assert_eq!("TARGETING_MATCH", events[0].reason.as_deref().unwrap());
+    assert_eq!(
+        "example@matching.com",
+        events[0].targeting_key.as_deref().unwrap()
+    );
+    assert_eq!(None, events[1].value);
+    assert_eq!("ERROR", events[1].reason.as_deref().unwrap());
+    assert_eq!("FLAG_NOT_FOUND", events[1].error_code.as_deref().unwrap());
+}
+
+#[tokio::test]
+async fn batch_exporter_flush() {
+    let transport = CollectingTransport::default();
+    let exporter = BatchExporter::builder(transport.clone())
+        .flush_interval(Duration::from_secs(3600))
+        .build();
+    let provider = create_builder().sink(exporter.clone()).build().unwrap();
+
+    let ctx = EvaluationContext::default();
+    _ = provider.resolve_int_value("intSetting", &ctx).await;
+    _ = provider.resolve_string_value("stringSetting", &ctx).await;
+    exporter.flush().await;
+
+    let batches = transport.batches.lock().unwrap();
+    assert_eq!(1, batches.len());
+    assert_eq!(2, batches[0].len());
+    assert_eq!("intSetting", batches[0][0].flag_key);
+    assert_eq!("stringSetting", batches[0][1].flag_key);
+}
+
+#[tokio::test]
+async fn batch_exporter_max_batch_size() {
+    let transport = CollectingTransport::default();
+    let exporter = BatchExporter::builder(transport.clone())
+        .max_batch_size(2)
+        .flush_interval(Duration::from_secs(3600))
+        .build();
+    let provider = create_builder().sink(exporter.clone()).build().unwrap();
+
+    let ctx = EvaluationContext::default();
+    for _ in 0..5 {
+        _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    }
+    exporter.flush().await;
+
+    let batches = transport.batches.lock().unwrap();
+    let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+    assert_eq!(vec![2, 2, 1], sizes);
+    assert_eq!(0, exporter.dropped_events());
+}
+
+#[tokio::test]
+async fn batch_exporter_zero_flush_interval() {
+    let transport = CollectingTransport::default();
+    let exporter = BatchExporter::builder(transport.clone())
+        .flush_interval(Duration::ZERO)
+        .build();
+    let provider = create_builder().sink(exporter.clone()).build().unwrap();
+
+    _ = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await;
+    exporter.flush().await;
+
+    let batches = transport.batches.lock().unwrap();
+    assert_eq!(1, batches.iter().map(Vec::len).sum::<usize>());
+}
+
+#[tokio::test]
+async fn http_transport_timeout() {
+    // Accepts the connections, but never responds.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/events", listener.local_addr().unwrap());
+    let transport = HttpTransport::new(url.as_str()).timeout(Duration::from_millis(100));
+
+    let result = tokio::time::timeout(Duration::from_secs(5), transport.send(&[])).await;
+
+    assert!(result.unwrap().is_err());
+}
+
+#[tokio::test]
+async fn audit_log_writes_json_lines() {
+    let buffer = SharedBuffer::default();
+    let provider = create_builder()
+        .sink(AuditLog::new(buffer.clone()))
+        .build()
+        .unwrap();
+
+    let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+    _ = provider.resolve_bool_value("disabledFeature", &ctx).await;
+    _ = provider.resolve_bool_value("stringSetting", &ctx).await;
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(2, lines.len());
+    assert_eq!("disabledFeature", lines[0]["flag_key"]);
+    assert_eq!("v-disabled-t", lines[0]["variant"]);
+    assert_eq!("TARGETING_MATCH", lines[0]["reason"]);
+    assert_eq!(64, lines[0]["targeting_key_hash"].as_str().unwrap().len());
+    assert!(!output.contains("example@matching.com"));
+    assert_eq!("TYPE_MISMATCH", lines[1]["error_code"]);
+}
+
+#[tokio::test]
+async fn sampled_sink_every() {
+    let sink = CollectingSink::default();
+    let provider = create_builder()
+        .sink(SampledSink::new(sink.clone()).every(3))
+        .build()
+        .unwrap();
+
+    let ctx = EvaluationContext::default();
+    for _ in 0..7 {
+        _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+        _ = provider.resolve_int_value("intSetting", &ctx).await;
+    }
+
+    let events = sink.events.lock().unwrap();
+    let count = |key: &str| events.iter().filter(|e| e.flag_key == key).count();
+    assert_eq!(3, count("enabledFeature"));
+    assert_eq!(3, count("intSetting"));
+}
+
+#[tokio::test]
+async fn sampled_sink_rate_limit() {
+    let sink = CollectingSink::default();
+    let sampled = Arc::new(SampledSink::new(sink.clone()).rate_limit(2, Duration::from_secs(3600)));
+
+    let ctx = EvaluationContext::default();
+    let provider = create_builder().sink(sampled.clone()).build().unwrap();
+    for _ in 0..5 {
+        _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    }
+
+    assert_eq!(2, sink.events.lock().unwrap().len());
+    assert_eq!(3, sampled.suppressed_events());
+}
+
+#[tokio::test]
+async fn sampled_sink_zero_rate_window() {
+    let sink = CollectingSink::default();
+    let sampled = Arc::new(SampledSink::new(sink.clone()).rate_limit(1, Duration::ZERO));
+
+    let ctx = EvaluationContext::default();
+    let provider = create_builder().sink(sampled.clone()).build().unwrap();
+    for _ in 0..100 {
+        _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    }
+
+    // The window is clamped, so the events of the same millisecond are still limited.
+    assert!(sampled.suppressed_events() > 0);
+    assert_eq!(
+        100,
+        sink.events.lock().unwrap().len() as u64 + sampled.suppressed_events()
+    );
+}
+
+fn create_builder() -> configcat_openfeature_provider::ConfigCatProviderBuilder {
+    common::builder("tests/data/test_json_complex.json")
+}
+
+#[derive(Clone, Default)]
+struct CollectingSink {
+    events: Arc<Mutex<Vec<EvaluationEvent>>>,
+}
+
+impl EvaluationSink for CollectingSink {
+    fn record(&self, event: &EvaluationEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+#[derive(Clone, Default)]
+struct CollectingTransport {
+    batches: Arc<Mutex<Vec<Vec<EvaluationEvent>>>>,
+}
+
+#[async_trait]
+impl Transport for CollectingTransport {
+    async fn send(&self, batch: &[EvaluationEvent]) -> Result<(), ExportError> {
+        self.batches.lock().unwrap().push(batch.to_vec());
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use configcat::PollingMode;
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+#[tokio::test]
+async fn snapshot_is_not_affected_by_refresh() {
+    let mut server = mockito::Server::new_async().await;
+    let v1 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(true))
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    provider.refresh().await.unwrap();
+    let snapshot = provider.snapshot();
+    assert!(
+        snapshot
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+
+    v1.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(false))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+
+    assert!(
+        snapshot
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(
+        !provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(
+        !provider
+            .snapshot()
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(4, provider.stats().evaluations);
+}
+
+#[tokio::test]
+async fn snapshot_with_local_only_overrides() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+
+    let result = provider
+        .snapshot()
+        .resolve_int_value("intSetting", &EvaluationContext::default())
+        .await
+        .unwrap();
+    assert_eq!(5, result.value);
+}
+
+#[tokio::test]
+async fn export_config() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(true))
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+
+    assert!(provider.export_config().await.is_none());
+
+    provider.refresh().await.unwrap();
+    let exported = provider.export_config().await.unwrap();
+    let expected: serde_json::Value = serde_json::from_str(config_json(true).as_str()).unwrap();
+    assert_eq!(expected, exported);
+}
+
+fn config_json(enabled: bool) -> String {
+    let mut config: serde_json::Value = serde_json::from_str(
+        std::fs::read_to_string("tests/data/test_json_complex.json")
+            .unwrap()
+            .as_str(),
+    )
+    .unwrap();
+    config["f"]["enabledFeature"]["v"]["b"] = serde_json::Value::Bool(enabled);
+    config.to_string()
+}
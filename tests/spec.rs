@@ -0,0 +1,107 @@
+#![cfg(feature = "testing")]
+
+use configcat_openfeature_provider::testing::{
+    ChaosProvider, ConfigFixture, Fault, FlagFixture, ProviderContract, RuleFixture, TestFlags,
+};
+use configcat_openfeature_provider::FlagType;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+
+fn contract() -> ProviderContract {
+    ProviderContract::new()
+        .flag("isNewCheckout", FlagType::Bool)
+        .flag("theme", FlagType::String)
+        .flag("maxItems", FlagType::Int)
+        .flag("discount", FlagType::Float)
+        .flag("limits", FlagType::Struct)
+}
+
+fn fixture() -> ConfigFixture {
+    ConfigFixture::new()
+        .flag(
+            "isNewCheckout",
+            FlagFixture::new(false).variation_id("v-off").rule(
+                RuleFixture::new()
+                    .contains("Email", &["@example.com"])
+                    .then(true)
+                    .variation_id("v-on"),
+            ),
+        )
+        .flag(
+            "theme",
+            FlagFixture::new("light")
+                .variation_id("v-light")
+                .percentage(50, "light", "v-light")
+                .percentage(50, "dark", "v-dark"),
+        )
+        .flag("maxItems", FlagFixture::new(5).variation_id("v-5"))
+        .flag("discount", FlagFixture::new(0.5).variation_id("v-half"))
+        .flag(
+            "limits",
+            FlagFixture::new(r#"{"items": 5}"#).variation_id("v-limits"),
+        )
+}
+
+#[tokio::test]
+async fn default_values_meet_the_spec() {
+    let mut provider = fixture().into_provider();
+
+    let report = contract().verify(&mut provider).await;
+
+    assert!(report.is_ok(), "{report}");
+}
+
+#[tokio::test]
+async fn targeted_values_meet_the_spec() {
+    let mut provider = fixture().into_provider();
+    let ctx = EvaluationContext::default()
+        .with_targeting_key("user-1")
+        .with_custom_field("Email", "jane@example.com");
+
+    let report = contract()
+        .evaluation_context(ctx)
+        .verify(&mut provider)
+        .await;
+
+    assert!(report.is_ok(), "{report}");
+}
+
+#[tokio::test]
+async fn test_flags_meet_the_spec() {
+    let mut provider = TestFlags::new()
+        .bool("isNewCheckout", true)
+        .string("theme", "dark")
+        .into_provider();
+
+    let report = ProviderContract::new()
+        .flag("isNewCheckout", FlagType::Bool)
+        .flag("theme", FlagType::String)
+        .verify(&mut provider)
+        .await;
+
+    assert!(report.is_ok(), "{report}");
+}
+
+#[tokio::test]
+async fn reports_violations() {
+    let mut provider =
+        ChaosProvider::new(TestFlags::new().bool("isNewCheckout", true).into_provider()).fault(
+            100,
+            Fault::Error(EvaluationErrorCode::General("Provider error".to_owned())),
+        );
+
+    let report = ProviderContract::new()
+        .flag("isNewCheckout", FlagType::Bool)
+        .verify(&mut provider)
+        .await;
+
+    let requirements: Vec<_> = report
+        .violations
+        .iter()
+        .map(|violation| violation.requirement)
+        .collect();
+    assert_eq!(vec!["2.2.3", "2.2.7", "2.2.7"], requirements);
+    assert_eq!(
+        "The bool resolution of 'isNewCheckout' failed with Provider error.",
+        report.violations[0].message
+    );
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use configcat_openfeature_provider::{MAX_COUNTED_VALUES, OTHER_VALUES_KEY};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+
+#[tokio::test]
+async fn stats() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    _ = provider.resolve_int_value("intSetting", &ctx).await;
+    _ = provider.resolve_float_value("doubleSetting", &ctx).await;
+    _ = provider.resolve_bool_value("non-existing", &ctx).await;
+    _ = provider.resolve_bool_value("stringSetting", &ctx).await;
+    _ = provider.resolve_bool_value("stringSetting", &ctx).await;
+
+    let stats = provider.clone().stats();
+    assert_eq!(6, stats.evaluations);
+    assert_eq!(Some(&1), stats.errors_by_code.get("FLAG_NOT_FOUND"));
+    assert_eq!(Some(&2), stats.errors_by_code.get("TYPE_MISMATCH"));
+    assert_eq!(2, stats.cache_hits);
+    assert!(stats.last_fetch_time.is_some());
+    assert!(stats
+        .last_error
+        .unwrap()
+        .starts_with("The type of a setting must match the requested type."));
+}
+
+#[tokio::test]
+async fn unused_flags() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(provider.flag_keys().await, provider.unused_flags().await);
+
+    _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    _ = provider.resolve_int_value("intSetting", &ctx).await;
+    _ = provider.resolve_bool_value("stringSetting", &ctx).await;
+    _ = provider.resolve_bool_value("non-existing", &ctx).await;
+
+    assert_eq!(
+        vec!["disabledFeature", "doubleSetting", "objectSetting"],
+        provider.unused_flags().await
+    );
+}
+
+#[tokio::test]
+async fn usage_report() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .count_served_values()
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    _ = provider.resolve_string_value("stringSetting", &ctx).await;
+    _ = provider.resolve_bool_value("stringSetting", &ctx).await;
+
+    let report = provider.usage_report();
+    assert_eq!(2, report.flags.len());
+    let enabled = &report.flags["enabledFeature"];
+    assert_eq!(2, enabled.evaluations);
+    assert_eq!(0, enabled.errors);
+    assert!(enabled.last_evaluated.is_some());
+    assert_eq!(Some(&2), enabled.values.get("true"));
+    let string = &report.flags["stringSetting"];
+    assert_eq!(2, string.evaluations);
+    assert_eq!(1, string.errors);
+    assert_eq!(Some(&1), string.values.get("\"test\""));
+}
+
+#[tokio::test]
+async fn usage_report_without_values() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+
+    _ = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await;
+
+    let enabled = &provider.usage_report().flags["enabledFeature"];
+    assert_eq!(1, enabled.evaluations);
+    assert!(enabled.values.is_empty());
+}
+
+#[tokio::test]
+async fn usage_report_caps_values() {
+    let provider = common::builder("tests/data/test_json_templates.json")
+        .interpolate_strings()
+        .count_served_values()
+        .build()
+        .unwrap();
+
+    for i in 0..MAX_COUNTED_VALUES + 10 {
+        let ctx = EvaluationContext::default().with_targeting_key(format!("user-{i}"));
+        _ = provider.resolve_string_value("welcomeMessage", &ctx).await;
+    }
+
+    let message = &provider.usage_report().flags["welcomeMessage"];
+    assert_eq!(MAX_COUNTED_VALUES + 1, message.values.len());
+    assert_eq!(Some(&10), message.values.get(OTHER_VALUES_KEY));
+}
+
+#[tokio::test]
+async fn evaluation_summary() {
+    let provider = common::builder("tests/data/test_json_complex.json")
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(
+        "0 evaluations, 0 errors, no config JSON.",
+        provider.evaluation_summary()
+    );
+
+    for _ in 0..3 {
+        _ = provider.resolve_int_value("intSetting", &ctx).await;
+    }
+    _ = provider.resolve_bool_value("enabledFeature", &ctx).await;
+    _ = provider.resolve_bool_value("non-existing", &ctx).await;
+    _ = provider.resolve_bool_value("stringSetting", &ctx).await;
+
+    let summary = provider.evaluation_summary();
+    assert!(
+        summary.starts_with("6 evaluations, 2 errors (33.3%), config JSON age: "),
+        "{summary}"
+    );
+    assert!(
+        summary.ends_with(
+            ", top flags: 'intSetting' (3), 'enabledFeature' (1), 'non-existing' (1), 'stringSetting' (1)."
+        ),
+        "{summary}"
+    );
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "otel")]
+mod common;
+
+use configcat_openfeature_provider::OtelTelemetryHook;
+use open_feature::{EvaluationContext, OpenFeature};
+use opentelemetry::metrics::MeterProvider;
+use opentelemetry::trace::{TraceContextExt, Tracer, TracerProvider};
+use opentelemetry::{Context, Value};
+use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::trace::{InMemorySpanExporterBuilder, SdkTracerProvider};
+
+#[tokio::test]
+async fn telemetry_hook() {
+    let span_exporter = InMemorySpanExporterBuilder::new().build();
+    let tracer_provider = SdkTracerProvider::builder()
+        .with_simple_exporter(span_exporter.clone())
+        .build();
+    let metric_exporter = InMemoryMetricExporter::default();
+    let meter_provider = SdkMeterProvider::builder()
+        .with_reader(PeriodicReader::builder(metric_exporter.clone()).build())
+        .build();
+
+    let mut api = OpenFeature::default();
+    api.set_provider(
+        common::builder("tests/data/test_json_complex.json")
+            .build()
+            .unwrap(),
+    )
+    .await;
+    let client = api
+        .create_client()
+        .with_hook(OtelTelemetryHook::with_meter(&meter_provider.meter("test")));
+
+    let span = tracer_provider.tracer("test").start("request");
+    {
+        let _guard = Context::current_with_span(span).attach();
+        let ctx = EvaluationContext::default().with_targeting_key("example@matching.com");
+        _ = client
+            .get_bool_value("disabledFeature", Some(&ctx), None)
+            .await;
+        _ = client
+            .get_bool_value("non-existing", Some(&ctx), None)
+            .await;
+        Context::current().span().end();
+    }
+
+    let spans = span_exporter.get_finished_spans().unwrap();
+    let events = &spans[0].events;
+    assert_eq!(2, events.len());
+    assert_eq!("feature_flag.evaluation", events[0].name);
+    let attribute = |index: usize, key: &str| {
+        events[index]
+            .attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.clone())
+    };
+    assert_eq!(
+        Some(Value::from("disabledFeature")),
+        attribute(0, "feature_flag.key")
+    );
+    assert_eq!(
+        Some(Value::from("v-disabled-t")),
+        attribute(0, "feature_flag.result.variant")
+    );
+    assert_eq!(
+        Some(Value::from("targeting_match")),
+        attribute(0, "feature_flag.result.reason")
+    );
+    assert_eq!(
+        Some(Value::from("flag_not_found")),
+        attribute(1, "error.type")
+    );
+
+    meter_provider.force_flush().unwrap();
+    let metrics = metric_exporter.get_finished_metrics().unwrap();
+    let names: Vec<&str> = metrics
+        .iter()
+        .flat_map(|resource| resource.scope_metrics())
+        .flat_map(|scope| scope.metrics())
+        .map(|metric| metric.name())
+        .collect();
+    for name in [
+        "feature_flag.evaluation_requests_total",
+        "feature_flag.evaluation_success_total",
+        "feature_flag.evaluation_error_total",
+        "feature_flag.evaluation_active_count",
+    ] {
+        assert!(names.contains(&name), "missing metric: {name}");
+    }
+}
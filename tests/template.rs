@@ -0,0 +1,78 @@
+mod common;
+
+use configcat::User;
+use configcat_openfeature_provider::{ConfigCatProvider, ConfigCatProviderBuilder};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, Value};
+
+fn create_builder() -> ConfigCatProviderBuilder {
+    common::builder("tests/data/test_json_templates.json")
+}
+
+fn context() -> EvaluationContext {
+    EvaluationContext::default()
+        .with_targeting_key("user-1")
+        .with_custom_field("Email", "jane@example.com")
+        .with_custom_field("region", "eu")
+}
+
+async fn resolve(provider: &ConfigCatProvider, flag_key: &str, ctx: &EvaluationContext) -> String {
+    provider
+        .resolve_string_value(flag_key, ctx)
+        .await
+        .unwrap()
+        .value
+}
+
+#[tokio::test]
+async fn keeps_placeholders_by_default() {
+    let provider = create_builder().build().unwrap();
+
+    assert_eq!(
+        "Welcome, {{Email}} ({{ Identifier }})!",
+        resolve(&provider, "welcomeMessage", &context()).await
+    );
+}
+
+#[tokio::test]
+async fn interpolates_context_attributes() {
+    let provider = create_builder().interpolate_strings().build().unwrap();
+
+    assert_eq!(
+        "Welcome, jane@example.com (user-1)!",
+        resolve(&provider, "welcomeMessage", &context()).await
+    );
+    assert_eq!(
+        "https://eu.example.com/{{custom.tier}}",
+        resolve(&provider, "regionEndpoint", &context()).await
+    );
+    assert_eq!(
+        "Welcome, {{Email}} ({{ Identifier }})!",
+        resolve(&provider, "welcomeMessage", &EvaluationContext::default()).await
+    );
+}
+
+#[tokio::test]
+async fn interpolates_user_attributes() {
+    let provider = create_builder().interpolate_strings().build().unwrap();
+    let user = User::new("user-2").custom("region", "us").custom("tier", 2);
+
+    let details = provider
+        .resolve_string_with_user("regionEndpoint", &user)
+        .await
+        .unwrap();
+
+    assert_eq!("https://us.example.com/2", details.value);
+}
+
+#[tokio::test]
+async fn interpolates_bulk_evaluations() {
+    let provider = create_builder().interpolate_strings().build().unwrap();
+
+    let all = provider.resolve_all(&context()).await;
+
+    assert_eq!(
+        Value::String("Welcome, jane@example.com (user-1)!".to_owned()),
+        all["welcomeMessage"].value
+    );
+}
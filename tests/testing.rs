@@ -0,0 +1,668 @@
+#![cfg(feature = "testing")]
+
+use configcat_openfeature_provider::testing::{
+    ChaosProvider, ConfigFixture, EvaluationMatrix, Fault, FixedBucket, FlagFixture,
+    LatencyProvider, RecordingProvider, ReplayProvider, RuleFixture, SeededBuckets, TestFlags,
+};
+use configcat_openfeature_provider::FlagType;
+use configcat_openfeature_provider::{assert_flag, assert_flag_eq, assert_flag_err};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode, EvaluationReason, Value};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_flags() {
+    let provider = TestFlags::new()
+        .bool("enabledFeature", true)
+        .int("maxItems", 5)
+        .float("ratio", 0.5)
+        .string("theme", "dark")
+        .object("settings", &serde_json::json!({"retries": 3}))
+        .into_provider();
+    let ctx = EvaluationContext::default();
+
+    assert!(
+        provider
+            .resolve_bool_value("enabledFeature", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        5,
+        provider
+            .resolve_int_value("maxItems", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(
+        (provider
+            .resolve_float_value("ratio", &ctx)
+            .await
+            .unwrap()
+            .value
+            - 0.5)
+            .abs()
+            < f64::EPSILON
+    );
+    assert_eq!(
+        "dark",
+        provider
+            .resolve_string_value("theme", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    let settings = provider
+        .resolve_struct_value("settings", &ctx)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(Some(&Value::Int(3)), settings.fields.get("retries"));
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        provider
+            .resolve_bool_value("non-existing", &ctx)
+            .await
+            .unwrap_err()
+            .code
+    );
+}
+
+#[tokio::test]
+async fn test_flags_builder() {
+    let provider = TestFlags::new()
+        .bool("payments_enabled", true)
+        .builder()
+        .key_prefix("payments_")
+        .build()
+        .unwrap();
+
+    assert!(
+        provider
+            .resolve_bool_value("enabled", &EvaluationContext::default())
+            .await
+            .unwrap()
+            .value
+    );
+}
+
+#[tokio::test]
+async fn config_fixture_targeting() {
+    let provider = ConfigFixture::new()
+        .flag(
+            "discount",
+            FlagFixture::new(0)
+                .variation_id("v-none")
+                .rule(
+                    RuleFixture::new()
+                        .is_one_of("Country", &["HU", "AT"])
+                        .number_greater_than("Age", 18.0)
+                        .then(20)
+                        .variation_id("v-adult"),
+                )
+                .rule(
+                    RuleFixture::new()
+                        .contains("Email", &["@example.com"])
+                        .then(10),
+                ),
+        )
+        .into_provider();
+
+    let adult = EvaluationContext::default()
+        .with_targeting_key("user-1")
+        .with_custom_field("Country", "HU")
+        .with_custom_field("Age", 30);
+    let details = provider
+        .resolve_int_value("discount", &adult)
+        .await
+        .unwrap();
+    assert_eq!(20, details.value);
+    assert_eq!(Some("v-adult".to_owned()), details.variant);
+
+    let employee = EvaluationContext::default()
+        .with_targeting_key("user-2")
+        .with_custom_field("Email", "jane@example.com");
+    let details = provider
+        .resolve_int_value("discount", &employee)
+        .await
+        .unwrap();
+    assert_eq!(10, details.value);
+
+    let details = provider
+        .resolve_int_value(
+            "discount",
+            &EvaluationContext::default().with_targeting_key("user-3"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(0, details.value);
+    assert_eq!(Some("v-none".to_owned()), details.variant);
+}
+
+#[tokio::test]
+async fn config_fixture_percentage_options() {
+    let provider = ConfigFixture::new()
+        .flag(
+            "theme",
+            FlagFixture::new("light")
+                .percentage(50, "light", "v-light")
+                .percentage(50, "dark", "v-dark"),
+        )
+        .into_provider();
+
+    let mut variants = std::collections::HashSet::new();
+    for i in 0..50 {
+        let ctx = EvaluationContext::default().with_targeting_key(format!("user-{i}"));
+        let details = provider.resolve_string_value("theme", &ctx).await.unwrap();
+        let again = provider.resolve_string_value("theme", &ctx).await.unwrap();
+        assert_eq!(details.value, again.value);
+        variants.insert(details.variant.unwrap());
+    }
+    assert_eq!(2, variants.len());
+}
+
+#[test]
+fn config_fixture_json() {
+    let config = ConfigFixture::new()
+        .flag(
+            "enabledFeature",
+            FlagFixture::new(true).variation_id("v-on"),
+        )
+        .to_json();
+
+    assert_eq!(
+        serde_json::json!({"f": {"enabledFeature": {"t": 0, "v": {"b": true}, "i": "v-on"}}}),
+        config
+    );
+}
+
+#[test]
+#[should_panic(expected = "the targeting rule should serve a value of the setting's type")]
+fn config_fixture_type_mismatch() {
+    _ = FlagFixture::new(true).rule(RuleFixture::new().is_one_of("Country", &["HU"]).then(5));
+}
+
+#[tokio::test]
+async fn override_flag() {
+    let provider = TestFlags::new()
+        .bool("isNewCheckout", false)
+        .int("maxItems", 5)
+        .into_provider();
+    let ctx = EvaluationContext::default();
+
+    let outer = provider.override_flag("isNewCheckout", true);
+    assert!(
+        provider
+            .resolve_bool_value("isNewCheckout", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    {
+        let _inner = provider.override_flag("isNewCheckout", false);
+        assert!(
+            !provider
+                .resolve_bool_value("isNewCheckout", &ctx)
+                .await
+                .unwrap()
+                .value
+        );
+    }
+    assert!(
+        provider
+            .resolve_bool_value("isNewCheckout", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    drop(outer);
+    assert!(
+        !provider
+            .resolve_bool_value("isNewCheckout", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+
+    let _missing = provider.override_flag("notInConfig", "on");
+    assert_eq!(
+        "on",
+        provider
+            .resolve_string_value("notInConfig", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+}
+
+#[tokio::test]
+async fn override_flag_type_mismatch() {
+    let provider = TestFlags::new().int("maxItems", 5).into_provider();
+    let _guard = provider.override_flag("maxItems", "ten");
+
+    let err = provider
+        .resolve_int_value("maxItems", &EvaluationContext::default())
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::TypeMismatch, err.code);
+}
+
+#[tokio::test]
+async fn record_and_replay() {
+    let provider = ConfigFixture::new()
+        .flag(
+            "isNewCheckout",
+            FlagFixture::new(false).variation_id("v-off").rule(
+                RuleFixture::new()
+                    .is_one_of("Country", &["HU"])
+                    .then(true)
+                    .variation_id("v-on"),
+            ),
+        )
+        .flag("maxItems", FlagFixture::new(5))
+        .into_provider();
+    let recorder = RecordingProvider::new(provider);
+    let hungarian = EvaluationContext::default()
+        .with_targeting_key("user-1")
+        .with_custom_field("Country", "HU");
+    let other = EvaluationContext::default().with_targeting_key("user-2");
+
+    recorder
+        .resolve_bool_value("isNewCheckout", &hungarian)
+        .await
+        .unwrap();
+    recorder
+        .resolve_bool_value("isNewCheckout", &other)
+        .await
+        .unwrap();
+    recorder
+        .resolve_int_value("maxItems", &other)
+        .await
+        .unwrap();
+    recorder
+        .resolve_int_value("maxItems", &other)
+        .await
+        .unwrap();
+    recorder
+        .resolve_bool_value("missing", &other)
+        .await
+        .unwrap_err();
+    assert_eq!(4, recorder.recorded().len());
+
+    let path = std::env::temp_dir().join(format!("configcat_replay_{}.json", std::process::id()));
+    recorder.save(&path).unwrap();
+    let replay = ReplayProvider::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let details = replay
+        .resolve_bool_value("isNewCheckout", &hungarian)
+        .await
+        .unwrap();
+    assert!(details.value);
+    assert_eq!(Some("v-on".to_owned()), details.variant);
+    assert_eq!(Some(EvaluationReason::TargetingMatch), details.reason);
+    assert!(
+        !replay
+            .resolve_bool_value("isNewCheckout", &other)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        5,
+        replay
+            .resolve_int_value("maxItems", &other)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        EvaluationErrorCode::FlagNotFound,
+        replay
+            .resolve_bool_value("missing", &other)
+            .await
+            .unwrap_err()
+            .code
+    );
+
+    // Evaluations that weren't recorded aren't served.
+    let unknown = EvaluationContext::default().with_targeting_key("user-3");
+    assert!(replay
+        .resolve_bool_value("isNewCheckout", &unknown)
+        .await
+        .is_err());
+    assert!(replay
+        .resolve_string_value("maxItems", &other)
+        .await
+        .is_err());
+}
+
+fn rollout() -> ConfigFixture {
+    ConfigFixture::new().flag(
+        "theme",
+        FlagFixture::new("light")
+            .percentage(30, "light", "v-light")
+            .percentage(70, "dark", "v-dark"),
+    )
+}
+
+#[tokio::test]
+async fn fixed_bucket() {
+    let ctx = EvaluationContext::default().with_targeting_key("user-1");
+
+    for (bucket, expected) in [(0, "light"), (29, "light"), (30, "dark"), (99, "dark")] {
+        let provider = rollout()
+            .builder()
+            .bucketing(FixedBucket(bucket))
+            .build()
+            .unwrap();
+        let details = provider.resolve_string_value("theme", &ctx).await.unwrap();
+        assert_eq!(expected, details.value, "bucket {bucket}");
+    }
+}
+
+#[tokio::test]
+async fn closure_bucketing() {
+    let provider = rollout()
+        .builder()
+        .bucketing(|_flag_key: &str, targeting_key: Option<&str>| {
+            if targeting_key == Some("beta-tester") {
+                0
+            } else {
+                99
+            }
+        })
+        .build()
+        .unwrap();
+
+    let beta = EvaluationContext::default().with_targeting_key("beta-tester");
+    let other = EvaluationContext::default();
+    assert_eq!(
+        "light",
+        provider
+            .resolve_string_value("theme", &beta)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        "dark",
+        provider
+            .resolve_string_value("theme", &other)
+            .await
+            .unwrap()
+            .value
+    );
+}
+
+#[tokio::test]
+async fn seeded_buckets() {
+    let first = rollout()
+        .builder()
+        .bucketing(SeededBuckets(42))
+        .build()
+        .unwrap();
+    let second = rollout()
+        .builder()
+        .bucketing(SeededBuckets(42))
+        .build()
+        .unwrap();
+
+    let mut values = std::collections::HashSet::new();
+    for i in 0..20 {
+        let ctx = EvaluationContext::default().with_targeting_key(format!("user-{i}"));
+        let value = first
+            .resolve_string_value("theme", &ctx)
+            .await
+            .unwrap()
+            .value;
+        assert_eq!(
+            value,
+            second
+                .resolve_string_value("theme", &ctx)
+                .await
+                .unwrap()
+                .value
+        );
+        values.insert(value);
+    }
+    assert_eq!(2, values.len());
+}
+
+#[tokio::test]
+async fn chaos_provider() {
+    let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+    let chaos = ChaosProvider::new(provider)
+        .fault(30, Fault::Error(EvaluationErrorCode::FlagNotFound))
+        .fault(20, Fault::Error(EvaluationErrorCode::ProviderNotReady))
+        .seed(7);
+    let ctx = EvaluationContext::default();
+
+    let mut not_found = 0;
+    let mut not_ready = 0;
+    let mut served = 0;
+    for _ in 0..1000 {
+        match chaos.resolve_bool_value("isNewCheckout", &ctx).await {
+            Ok(details) => {
+                assert!(details.value);
+                served += 1;
+            }
+            Err(err) if err.code == EvaluationErrorCode::FlagNotFound => not_found += 1,
+            Err(err) if err.code == EvaluationErrorCode::ProviderNotReady => not_ready += 1,
+            Err(err) => panic!("unexpected error: {err:?}"),
+        }
+    }
+    assert!((250..350).contains(&not_found), "{not_found}");
+    assert!((150..250).contains(&not_ready), "{not_ready}");
+    assert!((450..550).contains(&served), "{served}");
+    assert_eq!(not_found + not_ready, chaos.injected());
+}
+
+#[tokio::test]
+async fn chaos_provider_timeout() {
+    let provider = TestFlags::new().int("maxItems", 5).into_provider();
+    let chaos = ChaosProvider::new(provider).fault(100, Fault::Timeout(Duration::from_millis(20)));
+
+    let started = std::time::Instant::now();
+    let err = chaos
+        .resolve_int_value("maxItems", &EvaluationContext::default())
+        .await
+        .unwrap_err();
+    assert!(started.elapsed() >= Duration::from_millis(20));
+    assert_eq!(EvaluationErrorCode::General("Timeout".to_owned()), err.code);
+}
+
+#[tokio::test]
+async fn latency_provider() {
+    let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+    let slow = LatencyProvider::new(provider)
+        .delay(Duration::from_millis(20))
+        .jitter(Duration::from_millis(10))
+        .seed(1);
+    let ctx = EvaluationContext::default();
+
+    let started = std::time::Instant::now();
+    assert!(
+        slow.resolve_bool_value("isNewCheckout", &ctx)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(started.elapsed() >= Duration::from_millis(20));
+
+    let timed_out = tokio::time::timeout(
+        Duration::from_millis(5),
+        slow.resolve_bool_value("isNewCheckout", &ctx),
+    )
+    .await;
+    assert!(timed_out.is_err());
+}
+
+#[tokio::test]
+async fn latency_provider_slow_tail() {
+    let provider = TestFlags::new().int("maxItems", 5).into_provider();
+    let slow = LatencyProvider::new(provider)
+        .slow(50, Duration::from_secs(10))
+        .seed(3);
+    let ctx = EvaluationContext::default();
+
+    let mut timed_out = 0;
+    for _ in 0..20 {
+        let result = tokio::time::timeout(
+            Duration::from_millis(50),
+            slow.resolve_int_value("maxItems", &ctx),
+        )
+        .await;
+        if result.is_err() {
+            timed_out += 1;
+        }
+    }
+    assert!((1..20).contains(&timed_out), "{timed_out}");
+}
+
+#[tokio::test]
+async fn assertion_macros() {
+    let provider = ConfigFixture::new()
+        .flag("isNewCheckout", FlagFixture::new(true))
+        .flag("maxItems", FlagFixture::new(5).variation_id("v-5"))
+        .flag("ratio", FlagFixture::new(0.5))
+        .flag("theme", FlagFixture::new("dark"))
+        .into_provider();
+    let ctx = EvaluationContext::default().with_targeting_key("user-1");
+
+    assert_flag!(provider, "isNewCheckout");
+    assert_flag!(provider, "isNewCheckout", ctx);
+    assert_flag_eq!(provider, "maxItems", 5, ctx);
+    assert_flag_eq!(provider, "maxItems", 5_i64);
+    assert_flag_eq!(provider, "ratio", 0.5);
+    assert_flag_eq!(provider, "theme", "dark", ctx);
+    assert_flag_eq!(provider, "theme", "dark".to_owned());
+    assert_flag_err!(
+        provider,
+        "missing",
+        false,
+        EvaluationErrorCode::FlagNotFound
+    );
+    assert_flag_err!(
+        provider,
+        "maxItems",
+        "",
+        EvaluationErrorCode::TypeMismatch,
+        ctx
+    );
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "assertion failed for flag 'maxItems'\n  expected: 4\n    actual: 5\n    reason: DEFAULT\n   variant: v-5"
+)]
+async fn assert_flag_eq_failure() {
+    let provider = ConfigFixture::new()
+        .flag("maxItems", FlagFixture::new(5).variation_id("v-5"))
+        .into_provider();
+
+    assert_flag_eq!(provider, "maxItems", 4);
+}
+
+#[tokio::test]
+#[should_panic(expected = "error: FLAG_NOT_FOUND")]
+async fn assert_flag_eq_error() {
+    let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+
+    assert_flag_eq!(provider, "missing", true);
+}
+
+#[tokio::test]
+#[should_panic(expected = "expected error: FLAG_NOT_FOUND\n    actual value: true")]
+async fn assert_flag_err_failure() {
+    let provider = TestFlags::new().bool("isNewCheckout", true).into_provider();
+
+    assert_flag_err!(
+        provider,
+        "isNewCheckout",
+        false,
+        EvaluationErrorCode::FlagNotFound
+    );
+}
+
+fn matrix() -> EvaluationMatrix {
+    EvaluationMatrix::new()
+        .flag("theme", FlagType::String)
+        .flag("missing", FlagType::Bool)
+        .context("anonymous", EvaluationContext::default())
+        .context(
+            "beta",
+            EvaluationContext::default()
+                .with_targeting_key("user-1")
+                .with_custom_field("Email", "jane@example.com"),
+        )
+}
+
+fn themes(beta: &str) -> ConfigFixture {
+    ConfigFixture::new().flag(
+        "theme",
+        FlagFixture::new("light").variation_id("v-light").rule(
+            RuleFixture::new()
+                .contains("Email", &["@example.com"])
+                .then(beta),
+        ),
+    )
+}
+
+fn snapshot_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "configcat_snapshot_{name}_{}.json",
+        std::process::id()
+    ));
+    _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn evaluation_matrix() {
+    let snapshot = matrix().evaluate(&themes("dark").into_provider()).await;
+
+    let beta = &snapshot.results["beta"];
+    assert_eq!(Some(serde_json::json!("dark")), beta["theme"].value);
+    assert_eq!(Some("TARGETING_MATCH".to_owned()), beta["theme"].reason);
+    assert_eq!(
+        Some("FLAG_NOT_FOUND".to_owned()),
+        beta["missing"].error_code
+    );
+    let anonymous = &snapshot.results["anonymous"];
+    assert_eq!(Some(serde_json::json!("light")), anonymous["theme"].value);
+    assert_eq!(Some("v-light".to_owned()), anonymous["theme"].variant);
+}
+
+#[tokio::test]
+async fn evaluation_matrix_snapshot() {
+    let path = snapshot_path("match");
+
+    matrix()
+        .assert_snapshot(&themes("dark").into_provider(), &path)
+        .await;
+    assert!(path.exists());
+    matrix()
+        .assert_snapshot(&themes("dark").into_provider(), &path)
+        .await;
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "'theme' for 'beta': expected \"dark\" [TARGETING_MATCH], actual \"blue\" [TARGETING_MATCH]"
+)]
+async fn evaluation_matrix_snapshot_mismatch() {
+    let path = snapshot_path("mismatch");
+
+    matrix()
+        .evaluate(&themes("dark").into_provider())
+        .await
+        .save(&path)
+        .unwrap();
+    matrix()
+        .assert_snapshot(&themes("blue").into_provider(), &path)
+        .await;
+}
@@ -0,0 +1,76 @@
+#![cfg(any(feature = "otel", feature = "tracing"))]
+mod common;
+
+use configcat_openfeature_provider::{ConfigCatProvider, EvaluationEvent, EvaluationSink};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn tracing_span_id() {
+    use tracing::Instrument;
+
+    let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+    let sink = CollectingSink::default();
+    let provider = create_provider(&sink);
+
+    let span = tracing::info_span!("request");
+    let span_id = format!("{:016x}", span.id().unwrap().into_u64());
+    _ = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .instrument(span)
+        .await;
+
+    let events = sink.0.lock().unwrap();
+    assert_eq!(None, events[0].trace_id);
+    assert_eq!(Some(span_id), events[0].span_id);
+}
+
+#[cfg(feature = "otel")]
+#[tokio::test]
+async fn otel_trace_and_span_id() {
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+
+    let sink = CollectingSink::default();
+    let provider = create_provider(&sink);
+
+    let span_context = SpanContext::new(
+        TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+        SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+        TraceFlags::SAMPLED,
+        true,
+        TraceState::default(),
+    );
+    let _guard = opentelemetry::Context::current()
+        .with_remote_span_context(span_context)
+        .attach();
+    _ = provider
+        .resolve_bool_value("enabledFeature", &EvaluationContext::default())
+        .await;
+
+    let events = sink.0.lock().unwrap();
+    assert_eq!(
+        Some("4bf92f3577b34da6a3ce929d0e0e4736"),
+        events[0].trace_id.as_deref()
+    );
+    assert_eq!(Some("00f067aa0ba902b7"), events[0].span_id.as_deref());
+}
+
+fn create_provider(sink: &CollectingSink) -> ConfigCatProvider {
+    common::builder("tests/data/test_json_complex.json")
+        .sink(sink.clone())
+        .build()
+        .unwrap()
+}
+
+#[derive(Clone, Default)]
+struct CollectingSink(Arc<Mutex<Vec<EvaluationEvent>>>);
+
+impl EvaluationSink for CollectingSink {
+    fn record(&self, event: &EvaluationEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use async_trait::async_trait;
+use configcat_openfeature_provider::{ConfigCatProviderBuilder, TrackingEvent, TrackingSink};
+use open_feature::provider::FeatureProvider;
+use open_feature::EvaluationContext;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Default)]
+struct RecordingSink {
+    events: Mutex<Vec<TrackingEvent>>,
+}
+
+#[async_trait]
+impl TrackingSink for RecordingSink {
+    async fn track(&self, event: &TrackingEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn builder() -> ConfigCatProviderBuilder {
+    common::builder("tests/data/test_json_complex.json")
+}
+
+#[tokio::test]
+async fn track_forwards_to_sink() {
+    let sink = Arc::new(RecordingSink::default());
+    let provider = builder().tracking_sink(sink.clone()).build().unwrap();
+    let ctx = EvaluationContext::default()
+        .with_targeting_key("user-1")
+        .with_custom_field("plan", "pro")
+        .with_custom_field("seats", 5);
+
+    provider.track("checkout-completed", &ctx, Some(99.9)).await;
+    provider
+        .track("page-viewed", &EvaluationContext::default(), None)
+        .await;
+
+    let events = sink.events.lock().unwrap();
+    assert_eq!(2, events.len());
+    assert_eq!("checkout-completed", events[0].event_name);
+    assert_eq!(Some("user-1"), events[0].targeting_key.as_deref());
+    assert_eq!(Some(99.9), events[0].value);
+    assert_eq!(
+        serde_json::json!({"plan": "pro", "seats": 5}),
+        serde_json::Value::Object(events[0].context.clone())
+    );
+    assert_eq!("page-viewed", events[1].event_name);
+    assert_eq!(None, events[1].targeting_key);
+    assert_eq!(None, events[1].value);
+}
+
+#[tokio::test]
+async fn track_without_sink() {
+    let provider = builder().build().unwrap();
+
+    provider
+        .track("checkout-completed", &EvaluationContext::default(), None)
+        .await;
+}
+
+#[tokio::test]
+async fn track_with_exposures() {
+    let sink = Arc::new(RecordingSink::default());
+    let provider = builder()
+        .tracking_sink(sink.clone())
+        .correlate_exposures(Duration::from_secs(60))
+        .build()
+        .unwrap();
+    let user = EvaluationContext::default().with_targeting_key("user-1");
+    let other = EvaluationContext::default().with_targeting_key("user-2");
+
+    provider
+        .resolve_bool_value("enabledFeature", &user)
+        .await
+        .unwrap();
+    provider
+        .resolve_int_value("intSetting", &user)
+        .await
+        .unwrap();
+    provider
+        .resolve_bool_value("enabledFeature", &user)
+        .await
+        .unwrap();
+    provider
+        .resolve_string_value("stringSetting", &other)
+        .await
+        .unwrap();
+    provider.track("checkout-completed", &user, None).await;
+    provider
+        .track("checkout-completed", &EvaluationContext::default(), None)
+        .await;
+
+    let events = sink.events.lock().unwrap();
+    let exposures: Vec<_> = events[0]
+        .exposures
+        .iter()
+        .map(|exposure| (exposure.flag_key.as_str(), exposure.variant.as_str()))
+        .collect();
+    assert_eq!(
+        vec![("intSetting", "v-int"), ("enabledFeature", "v-enabled")],
+        exposures
+    );
+    assert!(events[1].exposures.is_empty());
+}
+
+#[tokio::test]
+async fn exposures_expire() {
+    let sink = Arc::new(RecordingSink::default());
+    let provider = builder()
+        .tracking_sink(sink.clone())
+        .correlate_exposures(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let user = EvaluationContext::default().with_targeting_key("user-1");
+
+    provider
+        .resolve_bool_value("enabledFeature", &user)
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    provider.track("checkout-completed", &user, None).await;
+
+    assert!(sink.events.lock().unwrap()[0].exposures.is_empty());
+}
+
+#[tokio::test]
+async fn exposures_evict_oldest_keys() {
+    let sink = Arc::new(RecordingSink::default());
+    let provider = builder()
+        .tracking_sink(sink.clone())
+        .correlate_exposures(Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    for i in 0..=10_000 {
+        let user = EvaluationContext::default().with_targeting_key(format!("user-{i}"));
+        provider
+            .resolve_bool_value("enabledFeature", &user)
+            .await
+            .unwrap();
+    }
+    for i in [0, 999, 1000, 10_000] {
+        let user = EvaluationContext::default().with_targeting_key(format!("user-{i}"));
+        provider.track("checkout-completed", &user, None).await;
+    }
+
+    // Reaching the cap evicts a batch of the oldest targeting keys at once.
+    let events = sink.events.lock().unwrap();
+    let tracked: Vec<bool> = events.iter().map(|e| !e.exposures.is_empty()).collect();
+    assert_eq!(vec![false, false, true, true], tracked);
+}
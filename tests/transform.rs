@@ -0,0 +1,83 @@
+mod common;
+
+use configcat_openfeature_provider::{
+    ConfigCatProvider, ConfigCatProviderBuilder, StringTransform,
+};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, Value};
+
+fn create_builder() -> ConfigCatProviderBuilder {
+    common::builder("tests/data/test_json_transforms.json")
+}
+
+async fn resolve(provider: &ConfigCatProvider, flag_key: &str) -> String {
+    provider
+        .resolve_string_value(flag_key, &EvaluationContext::default())
+        .await
+        .unwrap()
+        .value
+}
+
+#[tokio::test]
+async fn applies_transforms_in_order() {
+    let provider = create_builder()
+        .transform_strings(StringTransform::Trim)
+        .transform_string("theme", StringTransform::Lowercase)
+        .transform_string(
+            "theme",
+            StringTransform::custom(|theme| format!("{theme}-mode")),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!("dark-mode", resolve(&provider, "theme").await);
+}
+
+#[tokio::test]
+async fn expands_environment_variables() {
+    std::env::set_var("CONFIGCAT_TRANSFORM_TEST_HOST", "https://example.com");
+    std::env::set_var("CONFIGCAT_TRANSFORM_TEST_SECRET", "secret");
+    let provider = create_builder()
+        .transform_string(
+            "serviceUrl",
+            StringTransform::ExpandEnv(&["CONFIGCAT_TRANSFORM_TEST_HOST"]),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        "https://example.com/api/${CONFIGCAT_TRANSFORM_TEST_SECRET}/",
+        resolve(&provider, "serviceUrl").await
+    );
+    assert_eq!("  Dark  ", resolve(&provider, "theme").await);
+}
+
+#[tokio::test]
+async fn applies_transforms_to_bulk_evaluations() {
+    let provider = create_builder()
+        .transform_strings(StringTransform::Trim)
+        .transform_string("theme", StringTransform::Lowercase)
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    let all = provider.resolve_all(&ctx).await;
+    assert_eq!(Value::String("dark".to_owned()), all["theme"].value);
+    let payload = provider.bootstrap_payload(&ctx, Some(&["theme"])).await;
+    assert_eq!("dark", payload["flags"][0]["value"]);
+}
+
+#[cfg(feature = "ofrep")]
+#[tokio::test]
+async fn applies_transforms_to_ofrep_evaluations() {
+    let handler = configcat_openfeature_provider::OfrepHandler::new(
+        create_builder()
+            .transform_string("theme", StringTransform::Trim)
+            .build()
+            .unwrap(),
+    );
+
+    let response = handler.evaluate_flag("theme", b"{}").await;
+
+    assert_eq!("Dark", response.body["value"]);
+}
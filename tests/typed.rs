@@ -0,0 +1,210 @@
+mod common;
+
+use configcat_openfeature_provider::ConfigCatProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+use std::time::Duration;
+
+#[cfg(feature = "regex")]
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+#[cfg(feature = "regex")]
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+fn create_provider() -> ConfigCatProvider {
+    common::builder("tests/data/test_json_typed.json")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn resolves_durations() {
+    let provider = create_provider();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(
+        Ok(Duration::from_secs(30)),
+        provider.resolve_duration("requestTimeout", &ctx).await
+    );
+    assert_eq!(
+        Ok(Duration::from_secs(5_400)),
+        provider.resolve_duration("sessionLifetime", &ctx).await
+    );
+    assert_eq!(
+        Ok(Duration::from_millis(1_500)),
+        provider.resolve_duration("retryDelay", &ctx).await
+    );
+}
+
+#[tokio::test]
+async fn reports_invalid_durations() {
+    let provider = create_provider();
+
+    let err = provider
+        .resolve_duration("invalidDuration", &EvaluationContext::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+    assert!(err
+        .message
+        .unwrap()
+        .starts_with("The value of 'invalidDuration' ('30 seconds') is not a duration. Accepted formats: a number followed by"));
+}
+
+#[tokio::test]
+async fn resolves_byte_sizes() {
+    let provider = create_provider();
+    let ctx = EvaluationContext::default();
+
+    assert_eq!(
+        Ok(524_288),
+        provider.resolve_bytes("maxUploadSize", &ctx).await
+    );
+    assert_eq!(
+        Ok(2_000_000),
+        provider.resolve_bytes("cacheSize", &ctx).await
+    );
+    assert_eq!(
+        Ok(1_610_612_736),
+        provider.resolve_bytes("bufferSize", &ctx).await
+    );
+}
+
+#[tokio::test]
+async fn reports_invalid_byte_sizes() {
+    let provider = create_provider();
+    let ctx = EvaluationContext::default();
+
+    for flag_key in ["invalidBytes", "requestTimeout"] {
+        let err = provider.resolve_bytes(flag_key, &ctx).await.unwrap_err();
+        assert_eq!(EvaluationErrorCode::ParseError, err.code);
+        assert!(err
+            .message
+            .unwrap()
+            .contains("is not a byte size. Accepted formats:"));
+    }
+}
+
+#[tokio::test]
+async fn resolves_urls() {
+    let provider = create_provider();
+
+    let url = provider
+        .resolve_url("paymentsEndpoint", &EvaluationContext::default())
+        .await
+        .unwrap();
+
+    assert_eq!(Some("api.example.com"), url.host_str());
+    assert_eq!("/v2", url.path());
+}
+
+#[tokio::test]
+async fn reports_invalid_urls() {
+    let provider = create_provider();
+    let ctx = EvaluationContext::default();
+
+    let err = provider
+        .resolve_url("hostWithoutScheme", &ctx)
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+    assert_eq!(
+        Some("The value of 'hostWithoutScheme' ('localhost:8080') is not a valid URL: it has no path after the 'localhost:' scheme.".to_owned()),
+        err.message
+    );
+
+    let err = provider.resolve_url("relativeUrl", &ctx).await.unwrap_err();
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+    assert_eq!(
+        Some("The value of 'relativeUrl' ('/api/v2') is not a valid URL: relative URL without a base.".to_owned()),
+        err.message
+    );
+}
+
+#[cfg(feature = "regex")]
+#[tokio::test]
+async fn resolves_regexes() {
+    let provider = create_provider();
+    let ctx = EvaluationContext::default();
+
+    let regex = provider
+        .resolve_regex("blockedUserAgents", &ctx)
+        .await
+        .unwrap();
+    assert!(regex.is_match("curl/8.5.0"));
+    assert!(!regex.is_match("Mozilla/5.0"));
+
+    let err = provider
+        .resolve_regex("invalidRegex", &ctx)
+        .await
+        .unwrap_err();
+    assert_eq!(EvaluationErrorCode::ParseError, err.code);
+    assert!(err
+        .message
+        .unwrap()
+        .starts_with("The value of 'invalidRegex' ('^(curl') is not a valid regular expression:"));
+}
+
+#[cfg(feature = "regex")]
+#[tokio::test]
+async fn recompiles_regexes_on_config_change() {
+    let original: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string("tests/data/test_json_typed.json").unwrap())
+            .unwrap();
+    // The pattern stays the same, only another setting changes.
+    let mut updated = original.clone();
+    updated["f"]["requestTimeout"]["v"]["s"] = "45s".into();
+    let mut server = mockito::Server::new_async().await;
+    for (i, config) in [original, updated].iter().enumerate() {
+        server
+            .mock("GET", CONFIG_PATH)
+            .with_status(200)
+            .with_header("ETag", &format!("\"etag-{i}\""))
+            .with_body(config.to_string())
+            .expect(1)
+            .create_async()
+            .await;
+    }
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(configcat::PollingMode::Manual)
+        .build()
+        .unwrap();
+    let ctx = EvaluationContext::default();
+
+    provider.refresh().await.unwrap();
+    let regex = provider
+        .resolve_regex("blockedUserAgents", &ctx)
+        .await
+        .unwrap();
+    assert!(regex.is_match("curl/8.5.0"));
+    assert_eq!(1, provider.cached_regexes());
+    _ = provider.resolve_regex("blockedUserAgents", &ctx).await;
+    assert_eq!(1, provider.cached_regexes());
+
+    provider.refresh().await.unwrap();
+    assert_eq!(0, provider.cached_regexes());
+    let regex = provider
+        .resolve_regex("blockedUserAgents", &ctx)
+        .await
+        .unwrap();
+    assert!(regex.is_match("curl/8.5.0"));
+    assert_eq!(1, provider.cached_regexes());
+}
+
+#[cfg(feature = "regex")]
+#[tokio::test]
+async fn bounds_regex_cache() {
+    let provider = common::builder("tests/data/test_json_typed.json")
+        .interpolate_strings()
+        .build()
+        .unwrap();
+
+    for i in 0..300 {
+        let ctx = EvaluationContext::default().with_targeting_key(format!("user{i}"));
+        let regex = provider.resolve_regex("userPattern", &ctx).await.unwrap();
+        assert!(regex.is_match(format!("user{i}-42").as_str()));
+    }
+
+    assert!(provider.cached_regexes() <= 256);
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use configcat::User;
+use configcat_openfeature_provider::{
+    ConfigCatProvider, EvaluationEvent, EvaluationSink, USER_CONTEXT_KEY,
+};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationContextFieldValue, EvaluationErrorCode};
+use std::sync::{Arc, Mutex};
+
+fn create_provider(path: &str, sink: CollectingSink) -> ConfigCatProvider {
+    common::builder(path).sink(sink).build().unwrap()
+}
+
+#[derive(Clone, Default)]
+struct CollectingSink {
+    events: Arc<Mutex<Vec<EvaluationEvent>>>,
+}
+
+impl EvaluationSink for CollectingSink {
+    fn record(&self, event: &EvaluationEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn evaluates_with_string_list_attribute() {
+    let provider = create_provider("tests/data/test_json_user.json", CollectingSink::default());
+
+    let admin = User::new("id1").custom("Roles", ["admin", "billing"]);
+    let details = provider
+        .resolve_bool_with_user("adminFeature", &admin)
+        .await
+        .unwrap();
+    assert!(details.value);
+    assert_eq!(Some("v-admin-t".to_owned()), details.variant);
+
+    let viewer = User::new("id2").custom("Roles", ["viewer"]);
+    let details = provider
+        .resolve_bool_with_user("adminFeature", &viewer)
+        .await
+        .unwrap();
+    assert!(!details.value);
+}
+
+#[tokio::test]
+async fn resolves_all_types() {
+    let provider = create_provider(
+        "tests/data/test_json_complex.json",
+        CollectingSink::default(),
+    );
+    let user = User::new("id1");
+
+    assert_eq!(
+        5,
+        provider
+            .resolve_int_with_user("intSetting", &user)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        1.2,
+        provider
+            .resolve_float_with_user("doubleSetting", &user)
+            .await
+            .unwrap()
+            .value
+    );
+    assert_eq!(
+        "test",
+        provider
+            .resolve_string_with_user("stringSetting", &user)
+            .await
+            .unwrap()
+            .value
+    );
+    assert!(provider
+        .resolve_struct_with_user("objectSetting", &user)
+        .await
+        .is_ok());
+    assert!(provider
+        .resolve_int_with_user("stringSetting", &user)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn reports_identifier_as_targeting_key() {
+    let sink = CollectingSink::default();
+    let provider = create_provider("tests/data/test_json_complex.json", sink.clone());
+
+    provider
+        .resolve_bool_with_user("enabledFeature", &User::new("id1").email("a@example.com"))
+        .await
+        .unwrap();
+
+    let events = sink.events.lock().unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!("enabledFeature", events[0].flag_key);
+    assert_eq!(Some("id1".to_owned()), events[0].targeting_key);
+}
+
+#[tokio::test]
+async fn evaluates_prebuilt_user_from_context() {
+    let provider = create_provider("tests/data/test_json_user.json", CollectingSink::default());
+    let user = User::new("id1").custom("Roles", ["admin"]);
+    let context = EvaluationContext::default().with_custom_field(
+        USER_CONTEXT_KEY,
+        EvaluationContextFieldValue::new_struct(user),
+    );
+
+    let details = provider
+        .resolve_bool_value("adminFeature", &context)
+        .await
+        .unwrap();
+
+    assert!(details.value);
+}
+
+#[tokio::test]
+async fn merges_context_attributes_into_prebuilt_user() {
+    let provider = create_provider(
+        "tests/data/test_json_targeting.json",
+        CollectingSink::default(),
+    );
+    let user = User::new("id1").custom("region", "us");
+    let context = EvaluationContext::default()
+        .with_targeting_key("id1")
+        .with_custom_field(
+            USER_CONTEXT_KEY,
+            EvaluationContextFieldValue::new_struct(user),
+        )
+        .with_custom_field("region", "eu");
+
+    let details = provider
+        .resolve_bool_value("regionFeature", &context)
+        .await
+        .unwrap();
+
+    assert!(details.value);
+}
+
+#[tokio::test]
+async fn rejects_other_values_under_user_key() {
+    let provider = create_provider(
+        "tests/data/test_json_complex.json",
+        CollectingSink::default(),
+    );
+    let context = EvaluationContext::default().with_custom_field(USER_CONTEXT_KEY, "id1");
+
+    let err = provider
+        .resolve_bool_value("enabledFeature", &context)
+        .await
+        .unwrap_err();
+
+    assert_eq!(EvaluationErrorCode::InvalidContext, err.code);
+}
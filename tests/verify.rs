@@ -0,0 +1,244 @@
+use configcat::PollingMode;
+use configcat_openfeature_provider::{
+    ConfigCatProvider, FlagDefinition, FlagSchema, FlagType, FlagTypeMismatch, VerificationReport,
+};
+use open_feature::provider::FeatureProvider;
+use open_feature::{EvaluationContext, EvaluationErrorCode};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+async fn provider(server: &mut mockito::Server) -> ConfigCatProvider {
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(std::fs::read_to_string("tests/data/test_json_complex.json").unwrap())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+    provider
+}
+
+#[tokio::test]
+async fn verify_ok() {
+    let mut server = mockito::Server::new_async().await;
+    let provider = provider(&mut server).await;
+
+    let report = provider
+        .verify(&[
+            ("enabledFeature", FlagType::Bool),
+            ("intSetting", FlagType::Int),
+            ("doubleSetting", FlagType::Float),
+            ("stringSetting", FlagType::String),
+            ("objectSetting", FlagType::Struct),
+        ])
+        .await
+        .unwrap();
+
+    assert!(report.is_ok());
+    assert_eq!(VerificationReport::default(), report);
+}
+
+#[tokio::test]
+async fn verify_drift() {
+    let mut server = mockito::Server::new_async().await;
+    let provider = provider(&mut server).await;
+
+    let report = provider
+        .verify(&[
+            ("enabledFeature", FlagType::Bool),
+            ("intSetting", FlagType::String),
+            ("isNewCheckout", FlagType::Bool),
+        ])
+        .await
+        .unwrap();
+
+    assert!(!report.is_ok());
+    assert_eq!(vec!["isNewCheckout"], report.missing);
+    assert_eq!(
+        vec![FlagTypeMismatch {
+            flag_key: "intSetting".to_owned(),
+            expected: FlagType::String,
+            actual: Some(FlagType::Int),
+        }],
+        report.mismatched
+    );
+    assert_eq!(
+        "Missing flags: 'isNewCheckout'. 'intSetting' is declared as string, but it's int.",
+        report.to_string()
+    );
+}
+
+#[tokio::test]
+async fn verify_without_config() {
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .polling_mode(PollingMode::Manual)
+        .offline(true)
+        .build()
+        .unwrap();
+
+    let err = provider
+        .verify(&[("enabledFeature", FlagType::Bool)])
+        .await
+        .unwrap_err();
+
+    assert_eq!(EvaluationErrorCode::ProviderNotReady, err.code);
+}
+
+#[tokio::test]
+async fn validate_schema() {
+    let mut server = mockito::Server::new_async().await;
+    let provider = provider(&mut server).await;
+    let schema = FlagSchema::new()
+        .required("enabledFeature", FlagType::Bool)
+        .required("isNewCheckout", FlagType::Bool)
+        .optional("maxItems", FlagType::Int)
+        .optional("doubleSetting", FlagType::Int);
+
+    let report = provider.validate(&schema).await.unwrap();
+
+    assert_eq!(vec!["isNewCheckout"], report.missing);
+    assert_eq!(
+        vec![FlagTypeMismatch {
+            flag_key: "doubleSetting".to_owned(),
+            expected: FlagType::Int,
+            actual: Some(FlagType::Float),
+        }],
+        report.mismatched
+    );
+}
+
+#[tokio::test]
+async fn validate_schema_at_initialize() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(std::fs::read_to_string("tests/data/test_json_complex.json").unwrap())
+        .create_async()
+        .await;
+    let mut provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::LazyLoad(Duration::from_secs(60)))
+        .schema(
+            FlagSchema::new()
+                .required("enabledFeature", FlagType::Bool)
+                .required("intSetting", FlagType::String),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(None, provider.schema_report());
+
+    provider.initialize(&EvaluationContext::default()).await;
+
+    let report = provider.schema_report().unwrap();
+    assert!(report.missing.is_empty());
+    assert_eq!(
+        "'intSetting' is declared as string, but it's int.",
+        report.to_string()
+    );
+}
+
+#[tokio::test]
+async fn detect_schema_drift() {
+    let config_json = std::fs::read_to_string("tests/data/test_json_complex.json").unwrap();
+    let mut drifted: serde_json::Value = serde_json::from_str(&config_json).unwrap();
+    drifted["f"].as_object_mut().unwrap().remove("intSetting");
+    let mut server = mockito::Server::new_async().await;
+    let original = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-1\"")
+        .with_body(config_json)
+        .create_async()
+        .await;
+    let drifts = Arc::new(Mutex::new(Vec::new()));
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .schema(
+            FlagSchema::new()
+                .required("enabledFeature", FlagType::Bool)
+                .required("intSetting", FlagType::Int),
+        )
+        .on_schema_drift({
+            let drifts = drifts.clone();
+            move |report| drifts.lock().unwrap().push(report.to_string())
+        })
+        .build()
+        .unwrap();
+
+    provider.refresh().await.unwrap();
+    original.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_header("ETag", "\"etag-2\"")
+        .with_body(drifted.to_string())
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+
+    for _ in 0..50 {
+        if !drifts.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(
+        vec!["Missing flags: 'intSetting'.".to_owned()],
+        *drifts.lock().unwrap()
+    );
+    assert_eq!(
+        vec!["intSetting"],
+        provider.schema_report().unwrap().missing
+    );
+}
+
+#[tokio::test]
+async fn flag_definitions() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(std::fs::read_to_string("tests/data/test_json_targeting.json").unwrap())
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+
+    let definitions = provider.flag_definitions().await;
+
+    assert_eq!(
+        vec![FlagDefinition {
+            flag_key: "regionFeature".to_owned(),
+            flag_type: FlagType::Bool,
+            variation_ids: vec!["v-region-f".to_owned(), "v-region-t".to_owned()],
+        }],
+        definitions
+    );
+}
+
+#[tokio::test]
+async fn flag_definitions_without_config() {
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .polling_mode(PollingMode::Manual)
+        .offline(true)
+        .build()
+        .unwrap();
+
+    assert!(provider.flag_definitions().await.is_empty());
+}
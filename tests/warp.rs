@@ -0,0 +1,44 @@
+#![cfg(feature = "warp")]
+mod common;
+
+use common::provider;
+use configcat_openfeature_provider::{flags_filter, Flags, USER_ID_HEADER};
+use open_feature::EvaluationContext;
+use warp::Filter;
+
+#[tokio::test]
+async fn flags_filter_evaluates() {
+    let filter = flags_filter(provider()).then(|flags: Flags| async move {
+        format!(
+            "{} {} {}",
+            flags.bool("disabledFeature", false).await,
+            flags.int("intSetting", 0).await,
+            flags.string("non-existing", "default").await
+        )
+    });
+
+    let body = warp::test::request().reply(&filter).await;
+    assert_eq!("false 5 default", body.body());
+
+    let body = warp::test::request()
+        .header(USER_ID_HEADER, "example@matching.com")
+        .reply(&filter)
+        .await;
+    assert_eq!("true 5 default", body.body());
+}
+
+#[tokio::test]
+async fn context_extension() {
+    let flags = warp::test::request()
+        .header(USER_ID_HEADER, "other@example.com")
+        .extension(EvaluationContext::default().with_targeting_key("example@matching.com"))
+        .filter(&flags_filter(provider()))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Some("example@matching.com"),
+        flags.context().targeting_key.as_deref()
+    );
+    assert!(flags.bool("disabledFeature", false).await);
+}
@@ -0,0 +1,158 @@
+use configcat::PollingMode;
+use configcat_openfeature_provider::{ConfigCatProvider, FlagSet, FlagValues};
+use open_feature::EvaluationContext;
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012";
+const CONFIG_PATH: &str =
+    "/configuration-files/configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/1234567890123456789012/config_v6.json";
+
+#[tokio::test]
+async fn watch_bool() {
+    let mut server = mockito::Server::new_async().await;
+    let v1 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(true, 5))
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+
+    let mut enabled = provider
+        .watch_bool("enabledFeature", EvaluationContext::default(), false)
+        .await;
+    let mut missing = provider
+        .watch_string("non-existing", EvaluationContext::default(), "default")
+        .await;
+    assert!(*enabled.borrow_and_update());
+    assert_eq!("default", *missing.borrow_and_update());
+
+    v1.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(false, 5))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), enabled.changed())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!*enabled.borrow_and_update());
+    assert!(!missing.has_changed().unwrap());
+}
+
+#[tokio::test]
+async fn watch_skips_unrelated_changes() {
+    let mut server = mockito::Server::new_async().await;
+    let v1 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(true, 5))
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+
+    let mut enabled = provider
+        .watch_bool("enabledFeature", EvaluationContext::default(), false)
+        .await;
+    let mut int = provider
+        .watch_int("intSetting", EvaluationContext::default(), 0)
+        .await;
+    assert_eq!(5, *int.borrow_and_update());
+
+    v1.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(true, 10))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), int.changed())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(10, *int.borrow_and_update());
+    assert!(!enabled.has_changed().unwrap());
+    assert!(*enabled.borrow_and_update());
+}
+
+#[tokio::test]
+async fn bind() {
+    let mut server = mockito::Server::new_async().await;
+    let v1 = server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(true, 5))
+        .create_async()
+        .await;
+    let provider = ConfigCatProvider::builder(SDK_KEY)
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::Manual)
+        .build()
+        .unwrap();
+    provider.refresh().await.unwrap();
+
+    let mut flags = provider.bind::<Flags>(EvaluationContext::default()).await;
+    let before = flags.load();
+    assert!(before.enabled);
+    assert_eq!(5, before.int);
+
+    v1.remove_async().await;
+    server
+        .mock("GET", CONFIG_PATH)
+        .with_status(200)
+        .with_body(config_json(false, 10))
+        .create_async()
+        .await;
+    provider.refresh().await.unwrap();
+
+    let after = tokio::time::timeout(Duration::from_secs(5), flags.changed())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!after.enabled);
+    assert_eq!(10, after.int);
+    assert_eq!(10, flags.clone().load().int);
+    assert!(before.enabled);
+}
+
+struct Flags {
+    enabled: bool,
+    int: i64,
+}
+
+impl FlagSet for Flags {
+    fn from_flags(flags: &FlagValues) -> Self {
+        Self {
+            enabled: flags.bool("enabledFeature", false),
+            int: flags.int("intSetting", 0),
+        }
+    }
+}
+
+fn config_json(enabled: bool, int: i64) -> String {
+    let mut config: serde_json::Value = serde_json::from_str(
+        std::fs::read_to_string("tests/data/test_json_complex.json")
+            .unwrap()
+            .as_str(),
+    )
+    .unwrap();
+    config["f"]["enabledFeature"]["v"]["b"] = serde_json::Value::Bool(enabled);
+    config["f"]["intSetting"]["v"]["i"] = serde_json::Value::from(int);
+    config.to_string()
+}